@@ -0,0 +1,527 @@
+//! Accessibility support through [AccessKit](accesskit).
+//!
+//! Floem builds an AccessKit tree from the view tree whenever an assistive technology is
+//! listening. A view is part of the tree if it has a role set with
+//! [`accessibility_role`](crate::views::Decorators::accessibility_role) or if it can be focused
+//! with the keyboard. The accessible children of all other views are moved up to their nearest
+//! accessible ancestor.
+//!
+//! Views add their state to their node in [`View::accessibility`](crate::View::accessibility),
+//! and applications can describe their views further with the `accessibility_*` decorators.
+
+use std::{collections::HashMap, rc::Rc};
+
+use accesskit::{
+    Action, ActionData, ActionRequest, Affine, NodeBuilder, NodeId, Rect, Role, Tree, TreeUpdate,
+};
+use floem_winit::{event_loop::EventLoopProxy, window::WindowId};
+use peniko::kurbo::Point;
+use slotmap::{Key, KeyData};
+
+use crate::{
+    app::UserEvent,
+    app_state::AppState,
+    event::{Event, EventListener},
+    id::ViewId,
+    keyboard::Modifiers,
+    pointer::{PointerButton, PointerId, PointerInputEvent, PointerType},
+    view_storage::VIEW_STORAGE,
+};
+
+pub(crate) type AccessibilityActionHandler = Rc<dyn Fn(Option<&ActionData>)>;
+
+/// The accessibility properties of a view.
+#[derive(Default)]
+pub(crate) struct ViewAccessibility {
+    pub(crate) role: Option<Role>,
+    pub(crate) label: Option<String>,
+    pub(crate) description: Option<String>,
+    /// Called after [`View::accessibility`](crate::View::accessibility) to add more properties.
+    pub(crate) properties: Vec<Rc<dyn Fn(&mut NodeBuilder)>>,
+    pub(crate) actions: Vec<(Action, AccessibilityActionHandler)>,
+}
+
+/// An event from the platform's accessibility adapter, sent to the event loop of the window.
+#[derive(Debug)]
+pub(crate) enum AccessibilityEvent {
+    InitialTreeRequested,
+    ActionRequested(ActionRequest),
+    Deactivated,
+}
+
+pub(crate) fn node_id(id: ViewId) -> NodeId {
+    NodeId(id.data().as_ffi())
+}
+
+fn view_id(node: NodeId) -> Option<ViewId> {
+    let id = ViewId::from(KeyData::from_ffi(node.0));
+    VIEW_STORAGE
+        .with_borrow(|s| s.view_ids.contains_key(id))
+        .then_some(id)
+}
+
+/// Roles that take their label from the text of their children when they don't have one.
+fn label_from_contents(role: Role) -> bool {
+    matches!(
+        role,
+        Role::Button
+            | Role::CheckBox
+            | Role::RadioButton
+            | Role::Switch
+            | Role::Link
+            | Role::Tab
+            | Role::ListItem
+            | Role::ListBoxOption
+            | Role::TreeItem
+            | Role::MenuItem
+            | Role::MenuItemCheckBox
+            | Role::MenuItemRadio
+            | Role::Cell
+            | Role::ColumnHeader
+    )
+}
+
+/// Builds a full tree update for the window with the `root` view. `scale` converts from view
+/// coordinates to physical pixels.
+pub(crate) fn tree_update(app_state: &AppState, root: ViewId, scale: f64) -> TreeUpdate {
+    let mut nodes = HashMap::new();
+    let children = collect(app_state, root, &mut nodes);
+
+    let mut node = NodeBuilder::new(Role::Window);
+    node.set_children(children);
+    if scale != 1.0 {
+        node.set_transform(Affine::scale(scale));
+    }
+    {
+        let state = root.state();
+        let state = state.borrow();
+        if let Some(label) = &state.accessibility.label {
+            node.set_label(label.clone());
+        }
+    }
+    let root_id = node_id(root);
+    nodes.insert(root_id, node.build());
+
+    let focus = app_state
+        .focus
+        .map(node_id)
+        .filter(|focus| nodes.contains_key(focus))
+        .unwrap_or(root_id);
+
+    TreeUpdate {
+        nodes: nodes.into_iter().collect(),
+        tree: Some(Tree::new(root_id)),
+        focus,
+    }
+}
+
+/// Adds the nodes of `id` and its children to `nodes`, returning the ids of the nodes that are
+/// children of the accessible parent of `id`.
+fn collect(
+    app_state: &AppState,
+    id: ViewId,
+    nodes: &mut HashMap<NodeId, accesskit::Node>,
+) -> Vec<NodeId> {
+    if id.style_has_hidden() {
+        return Vec::new();
+    }
+
+    let children: Vec<NodeId> = id
+        .children()
+        .into_iter()
+        .flat_map(|child| collect(app_state, child, nodes))
+        .collect();
+
+    let state = id.state();
+    let state = state.borrow();
+    let focusable = app_state.keyboard_navigable.contains(&id);
+    let Some(role) = state
+        .accessibility
+        .role
+        .or_else(|| focusable.then_some(Role::GenericContainer))
+    else {
+        return children;
+    };
+
+    let mut node = NodeBuilder::new(role);
+    if role == Role::ComboBox {
+        // The value of a combo box is the text of its selected item.
+        let text = contents_text(&children, nodes);
+        if !text.is_empty() {
+            node.set_value(text);
+        }
+    }
+    id.view().borrow().accessibility(&mut node);
+    for properties in &state.accessibility.properties {
+        properties(&mut node);
+    }
+
+    let label = state.accessibility.label.clone().or_else(|| {
+        label_from_contents(role)
+            .then(|| contents_text(&children, nodes))
+            .filter(|text| !text.is_empty())
+    });
+    if let Some(label) = label {
+        node.set_label(label);
+    }
+    if let Some(description) = &state.accessibility.description {
+        node.set_description(description.clone());
+    }
+
+    let rect = state.layout_rect;
+    node.set_bounds(Rect::new(rect.x0, rect.y0, rect.x1, rect.y1));
+    if app_state.is_disabled(&id) {
+        node.set_disabled();
+    } else {
+        if focusable {
+            node.add_action(Action::Focus);
+        }
+        if state.event_listeners.contains_key(&EventListener::Click) {
+            node.add_action(Action::Click);
+        }
+        for (action, _) in &state.accessibility.actions {
+            node.add_action(*action);
+        }
+    }
+    node.set_children(children);
+
+    let node_id = node_id(id);
+    nodes.insert(node_id, node.build());
+    vec![node_id]
+}
+
+/// The text of the labels in the subtrees of `children`, separated by spaces.
+fn contents_text(children: &[NodeId], nodes: &HashMap<NodeId, accesskit::Node>) -> String {
+    let mut text = String::new();
+    for child in children {
+        let Some(node) = nodes.get(child) else {
+            continue;
+        };
+        let child_text = if node.role() == Role::Label {
+            node.value()
+                .or(node.label())
+                .map(str::to_string)
+                .unwrap_or_default()
+        } else if let Some(label) = node.label() {
+            label.to_string()
+        } else {
+            contents_text(node.children(), nodes)
+        };
+        if !child_text.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&child_text);
+        }
+    }
+    text
+}
+
+/// Performs an action requested by an assistive technology. Handlers added with
+/// [`on_accessibility_action`](crate::views::Decorators::on_accessibility_action) take
+/// precedence over the default handling of focus, click and scroll requests.
+pub(crate) fn handle_action(app_state: &mut AppState, request: ActionRequest) {
+    let Some(id) = view_id(request.target) else {
+        return;
+    };
+    if app_state.is_disabled(&id) {
+        return;
+    }
+
+    let handlers: Vec<AccessibilityActionHandler> = id
+        .state()
+        .borrow()
+        .accessibility
+        .actions
+        .iter()
+        .filter(|(action, _)| *action == request.action)
+        .map(|(_, handler)| handler.clone())
+        .collect();
+    if !handlers.is_empty() {
+        for handler in handlers {
+            handler(request.data.as_ref());
+        }
+        return;
+    }
+
+    match request.action {
+        Action::Focus => {
+            if app_state.can_focus(id) && app_state.focus != Some(id) {
+                let old = app_state.focus;
+                app_state.clear_focus();
+                app_state.update_focus(id, true);
+                app_state.focus_changed(old, app_state.focus);
+            }
+        }
+        Action::Click => {
+            let pos = id.layout_rect().size().to_rect().center();
+            id.apply_event(&EventListener::Click, &click_event(pos));
+        }
+        Action::ScrollIntoView => {
+            id.scroll_to(None);
+        }
+        _ => {}
+    }
+}
+
+fn click_event(pos: Point) -> Event {
+    Event::PointerUp(PointerInputEvent {
+        pos,
+        button: PointerButton::Primary,
+        modifiers: Modifiers::default(),
+        count: 1,
+        pointer_id: PointerId::Mouse,
+        pointer_type: PointerType::Mouse,
+        pressure: 0.0,
+        tilt: None,
+    })
+}
+
+/// Forwards the requests of the platform adapter, which may arrive on another thread, to the
+/// event loop.
+#[derive(Clone)]
+struct Handler {
+    window_id: WindowId,
+    proxy: EventLoopProxy<UserEvent>,
+}
+
+impl Handler {
+    fn send(&self, event: AccessibilityEvent) {
+        let _ = self.proxy.send_event(UserEvent::Accessibility {
+            window_id: self.window_id,
+            event,
+        });
+    }
+}
+
+impl accesskit::ActivationHandler for Handler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        // The tree can only be built on the main thread, so it's sent with `update_if_active`
+        // once the event loop receives the request.
+        self.send(AccessibilityEvent::InitialTreeRequested);
+        None
+    }
+}
+
+impl accesskit::ActionHandler for Handler {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.send(AccessibilityEvent::ActionRequested(request));
+    }
+}
+
+impl accesskit::DeactivationHandler for Handler {
+    fn deactivate_accessibility(&mut self) {
+        self.send(AccessibilityEvent::Deactivated);
+    }
+}
+
+/// The platform's accessibility adapter for a window.
+pub(crate) struct Adapter {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    adapter: accesskit_unix::Adapter,
+    #[cfg(target_os = "windows")]
+    adapter: accesskit_windows::SubclassingAdapter,
+    #[cfg(target_os = "macos")]
+    adapter: accesskit_macos::SubclassingAdapter,
+}
+
+impl Adapter {
+    /// Creates the adapter for `window`, or returns `None` if the platform isn't supported.
+    pub(crate) fn new(
+        window: &floem_winit::window::Window,
+        proxy: EventLoopProxy<UserEvent>,
+    ) -> Option<Self> {
+        let handler = Handler {
+            window_id: window.id(),
+            proxy,
+        };
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        {
+            Some(Self {
+                adapter: accesskit_unix::Adapter::new(handler.clone(), handler.clone(), handler),
+            })
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+            let RawWindowHandle::Win32(handle) = window.window_handle().ok()?.as_raw() else {
+                return None;
+            };
+            let hwnd = accesskit_windows::HWND(handle.hwnd.get() as _);
+            Some(Self {
+                adapter: accesskit_windows::SubclassingAdapter::new(hwnd, handler.clone(), handler),
+            })
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+            let RawWindowHandle::AppKit(handle) = window.window_handle().ok()?.as_raw() else {
+                return None;
+            };
+            // Safety: the view stays valid as long as the window, which owns the adapter.
+            let adapter = unsafe {
+                accesskit_macos::SubclassingAdapter::new(
+                    handle.ns_view.as_ptr(),
+                    handler.clone(),
+                    handler,
+                )
+            };
+            Some(Self { adapter })
+        }
+
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "windows",
+            target_os = "macos"
+        )))]
+        {
+            let _ = (window, handler);
+            None
+        }
+    }
+
+    /// Sends the tree created by `update` to the adapter if an assistive technology is
+    /// listening. `update` isn't called otherwise.
+    pub(crate) fn update_if_active(&mut self, update: impl FnOnce() -> TreeUpdate) {
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        self.adapter.update_if_active(update);
+
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        if let Some(events) = self.adapter.update_if_active(update) {
+            events.raise();
+        }
+
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "windows",
+            target_os = "macos"
+        )))]
+        let _ = update;
+    }
+
+    pub(crate) fn window_focused(&mut self, focused: bool) {
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        self.adapter.update_window_focus_state(focused);
+
+        #[cfg(target_os = "macos")]
+        if let Some(events) = self.adapter.update_view_focus_state(focused) {
+            events.raise();
+        }
+
+        // Windows tracks the focus of the window itself.
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "macos"
+        )))]
+        let _ = focused;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit_consumer::Tree;
+
+    use super::*;
+    use crate::style::Style;
+
+    #[test]
+    fn tree_from_views() {
+        let root = ViewId::new();
+        let stack = ViewId::new();
+        let button = ViewId::new();
+        let text = ViewId::new();
+        let checkbox = ViewId::new();
+        let hidden = ViewId::new();
+        root.set_children_ids(vec![stack]);
+        stack.set_children_ids(vec![button, checkbox, hidden]);
+        button.set_children_ids(vec![text]);
+        for (child, parent) in [
+            (stack, root),
+            (button, stack),
+            (text, button),
+            (checkbox, stack),
+            (hidden, stack),
+        ] {
+            child.set_parent(parent);
+        }
+
+        button.set_accessibility_role(Role::Button);
+        text.set_accessibility_role(Role::Label);
+        text.add_accessibility_properties(|node| node.set_value("Save"));
+        checkbox.set_accessibility_role(Role::CheckBox);
+        checkbox.set_accessibility_label(Some("Autosave".to_string()));
+        checkbox.set_accessibility_description(Some("Save every minute".to_string()));
+        hidden.set_accessibility_role(Role::Label);
+        hidden.state().borrow_mut().combined_style = Style::new().hide();
+
+        let mut app_state = AppState::new(root);
+        app_state.keyboard_navigable.insert(button);
+        app_state.focus = Some(button);
+
+        let tree = Tree::new(tree_update(&app_state, root, 2.0), true);
+        let state = tree.state();
+
+        // The stack has no role, so its children belong to the window.
+        let root_node = state.root();
+        assert_eq!(root_node.role(), Role::Window);
+        let children: Vec<_> = root_node.children().map(|node| node.id()).collect();
+        assert_eq!(children, vec![node_id(button), node_id(checkbox)]);
+
+        let button_node = state.node_by_id(node_id(button)).unwrap();
+        assert_eq!(button_node.label().as_deref(), Some("Save"));
+        assert!(button_node.is_focused());
+
+        let checkbox_node = state.node_by_id(node_id(checkbox)).unwrap();
+        assert_eq!(checkbox_node.label().as_deref(), Some("Autosave"));
+        assert_eq!(
+            checkbox_node.description().as_deref(),
+            Some("Save every minute")
+        );
+        assert!(state.node_by_id(node_id(hidden)).is_none());
+
+        for id in [root, stack, button, text, checkbox, hidden] {
+            id.remove();
+        }
+    }
+}
@@ -138,7 +138,18 @@ pub fn add_overlay<V: View + 'static>(
     let id = ViewId::new();
     add_update_message(UpdateMessage::AddOverlay {
         id,
-        position,
+        position: Some(position),
+        view: Box::new(move || Box::new(view(id))),
+    });
+    id
+}
+
+/// Creates a new overlay covering the whole window, which positions its content itself.
+pub(crate) fn add_layer<V: View + 'static>(view: impl FnOnce(ViewId) -> V + 'static) -> ViewId {
+    let id = ViewId::new();
+    add_update_message(UpdateMessage::AddOverlay {
+        id,
+        position: None,
         view: Box::new(move || Box::new(view(id))),
     });
     id
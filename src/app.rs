@@ -10,11 +10,13 @@ use parking_lot::Mutex;
 #[allow(deprecated)]
 use raw_window_handle::HasRawDisplayHandle;
 
+#[cfg(feature = "accessibility")]
+use crate::accessibility::AccessibilityEvent;
 use crate::{
     action::Timer,
     app_handle::ApplicationHandle,
     clipboard::Clipboard,
-    inspector::Capture,
+    inspector::{Capture, CaptureSnapshot},
     profiler::Profile,
     view::{IntoView, View},
     window::WindowConfig,
@@ -42,7 +44,14 @@ pub(crate) enum UserEvent {
     AppUpdate,
     Idle,
     QuitApp,
-    GpuResourcesUpdate { window_id: WindowId },
+    GpuResourcesUpdate {
+        window_id: WindowId,
+    },
+    #[cfg(feature = "accessibility")]
+    Accessibility {
+        window_id: WindowId,
+        event: AccessibilityEvent,
+    },
 }
 
 pub(crate) enum AppUpdateEvent {
@@ -57,6 +66,17 @@ pub(crate) enum AppUpdateEvent {
         window_id: WindowId,
         capture: WriteSignal<Option<Rc<Capture>>>,
     },
+    PickElement {
+        window_id: WindowId,
+    },
+    LongPressTimeout {
+        window_id: WindowId,
+        generation: u64,
+    },
+    SnapshotWindow {
+        window_id: WindowId,
+        callback: Box<dyn FnOnce(Option<CaptureSnapshot>)>,
+    },
     ProfileWindow {
         window_id: WindowId,
         end_profile: Option<WriteSignal<Option<Rc<Profile>>>>,
@@ -66,6 +66,12 @@ impl ApplicationHandle {
                     .unwrap()
                     .init_renderer();
             }
+            #[cfg(feature = "accessibility")]
+            UserEvent::Accessibility { window_id, event } => {
+                if let Some(window_handle) = self.window_handles.get_mut(&window_id) {
+                    window_handle.accessibility_event(event);
+                }
+            }
         }
     }
 
@@ -95,6 +101,25 @@ impl ApplicationHandle {
                 AppUpdateEvent::CaptureWindow { window_id, capture } => {
                     capture.set(self.capture_window(window_id).map(Rc::new));
                 }
+                AppUpdateEvent::SnapshotWindow {
+                    window_id,
+                    callback,
+                } => {
+                    callback(self.capture_window(window_id).map(|c| c.snapshot()));
+                }
+                AppUpdateEvent::LongPressTimeout {
+                    window_id,
+                    generation,
+                } => {
+                    if let Some(handle) = self.window_handles.get_mut(&window_id) {
+                        handle.long_press_timeout(generation);
+                    }
+                }
+                AppUpdateEvent::PickElement { window_id } => {
+                    if let Some(handle) = self.window_handles.get_mut(&window_id) {
+                        handle.start_pick();
+                    }
+                }
                 AppUpdateEvent::ProfileWindow {
                     window_id,
                     end_profile,
@@ -102,12 +127,12 @@ impl ApplicationHandle {
                     let handle = self.window_handles.get_mut(&window_id);
                     if let Some(handle) = handle {
                         if let Some(profile) = end_profile {
-                            profile.set(handle.profile.take().map(|mut profile| {
+                            profile.set(handle.app_state.profile.take().map(|mut profile| {
                                 profile.next_frame();
                                 Rc::new(profile)
                             }));
                         } else {
-                            handle.profile = Some(Profile::default());
+                            handle.app_state.profile = Some(Profile::default());
                         }
                     }
                 }
@@ -143,7 +168,8 @@ impl ApplicationHandle {
             return;
         }
 
-        let start = window_handle.profile.is_some().then(|| {
+        let profiling = window_handle.app_state.profile.is_some();
+        let start = (profiling || cfg!(feature = "tracing")).then(|| {
             let name = match event {
                 WindowEvent::ActivationTokenDone { .. } => "ActivationTokenDone",
                 WindowEvent::Resized(..) => "Resized",
@@ -180,6 +206,8 @@ impl ApplicationHandle {
                 matches!(event, WindowEvent::RedrawRequested),
             )
         });
+        #[cfg(feature = "tracing")]
+        let span = start.map(|(name, ..)| tracing::trace_span!("window_event", name).entered());
 
         match event {
             WindowEvent::ActivationTokenDone { .. } => {}
@@ -202,8 +230,12 @@ impl ApplicationHandle {
             WindowEvent::DroppedFile(path) => {
                 window_handle.dropped_file(path);
             }
-            WindowEvent::HoveredFile(_) => {}
-            WindowEvent::HoveredFileCancelled => {}
+            WindowEvent::HoveredFile(path) => {
+                window_handle.hovered_file(path);
+            }
+            WindowEvent::HoveredFileCancelled => {
+                window_handle.hovered_file_cancelled();
+            }
             WindowEvent::Focused(focused) => {
                 window_handle.focused(focused);
             }
@@ -237,12 +269,22 @@ impl ApplicationHandle {
             WindowEvent::MouseInput { state, button, .. } => {
                 window_handle.mouse_input(button, state);
             }
-            WindowEvent::TouchpadMagnify { .. } => {}
+            WindowEvent::TouchpadMagnify { delta, phase, .. } => {
+                window_handle.touchpad_magnify(delta, phase);
+            }
             WindowEvent::SmartMagnify { .. } => {}
-            WindowEvent::TouchpadRotate { .. } => {}
-            WindowEvent::TouchpadPressure { .. } => {}
+            WindowEvent::TouchpadRotate { delta, phase, .. } => {
+                window_handle.touchpad_rotate(delta, phase);
+            }
+            WindowEvent::TouchpadPressure {
+                pressure, stage, ..
+            } => {
+                window_handle.touchpad_pressure(pressure, stage);
+            }
             WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
+            WindowEvent::Touch(touch) => {
+                window_handle.touch(touch);
+            }
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 window_handle.scale(scale_factor);
             }
@@ -258,16 +300,22 @@ impl ApplicationHandle {
             }
         }
 
-        if let Some((name, start, new_frame)) = start {
+        #[cfg(feature = "tracing")]
+        drop(span);
+        if let Some((name, start, new_frame)) = start.filter(|_| profiling) {
             let end = Instant::now();
 
-            if let Some(window_handle) = self.window_handles.get_mut(&window_id) {
-                let profile = window_handle.profile.as_mut().unwrap();
-
-                profile
-                    .current
-                    .events
-                    .push(ProfileEvent { start, end, name });
+            if let Some(profile) = self
+                .window_handles
+                .get_mut(&window_id)
+                .and_then(|window_handle| window_handle.app_state.profile.as_mut())
+            {
+                profile.current.events.push(ProfileEvent {
+                    start,
+                    end,
+                    name,
+                    view: None,
+                });
 
                 if new_frame {
                     profile.next_frame();
@@ -8,17 +8,37 @@ use crate::{
     context::{DragState, FrameUpdate, InteractionState},
     event::{Event, EventListener},
     id::ViewId,
-    inspector::CaptureState,
+    inspector::{CaptureState, PickState},
     menu::Menu,
+    nav::{is_within, tab_order},
+    pointer::PointerId,
+    profiler::Profile,
     responsive::{GridBreakpoints, ScreenSizeBp},
+    shortcut::Shortcut,
     style::{CursorStyle, Style, StyleClassRef, StyleSelector},
     view_storage::VIEW_STORAGE,
 };
 
+/// A menu item triggered by its accelerator.
+#[derive(Clone, Copy)]
+pub(crate) enum MenuAccelerator {
+    /// An item of a menu bar view.
+    MenuBar(ViewId, u64),
+    /// An item of the window menu.
+    WindowMenu(usize),
+}
+
 /// Encapsulates and owns the global state of the application,
 pub struct AppState {
     /// keyboard focus
     pub(crate) focus: Option<ViewId>,
+    /// The active focus traps, the innermost one last.
+    pub(crate) focus_traps: Vec<ViewId>,
+    /// The views that moved the focus away, such as focus traps and overlays, with the focus to
+    /// return to once they are closed.
+    pub(crate) focus_restore: Vec<(ViewId, Option<ViewId>)>,
+    /// The open popovers that close on Escape, the topmost one last.
+    pub(crate) dismiss_on_escape: Vec<ViewId>,
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<ViewId>,
@@ -35,6 +55,8 @@ pub struct AppState {
     pub(crate) dragging: Option<DragState>,
     pub(crate) drag_start: Option<(ViewId, Point)>,
     pub(crate) dragging_over: HashSet<ViewId>,
+    /// The views under a drag from another application.
+    pub(crate) external_drag_over: HashSet<ViewId>,
     pub(crate) screen_size_bp: ScreenSizeBp,
     pub(crate) grid_bps: GridBreakpoints,
     pub(crate) clicking: HashSet<ViewId>,
@@ -46,9 +68,19 @@ pub struct AppState {
     pub(crate) keyboard_navigation: bool,
     pub(crate) window_menu: HashMap<usize, Box<dyn Fn()>>,
     pub(crate) context_menu: HashMap<usize, Box<dyn Fn()>>,
+    /// The accelerators of the window menu items, with the item ids.
+    pub(crate) menu_accelerators: Vec<(Shortcut, usize)>,
+    /// The accelerators and mnemonics of the menu bar views, with the item ids.
+    pub(crate) menu_bar_shortcuts: HashMap<ViewId, Vec<(Shortcut, u64)>>,
 
     /// This is set if we're currently capturing the window for the inspector.
     pub(crate) capture: Option<CaptureState>,
+    /// This is set while the inspector is picking an element in the window.
+    pub(crate) picking: Option<PickState>,
+    /// This is set while the window is being profiled.
+    pub(crate) profile: Option<Profile>,
+    /// The views that captured pointers with `ViewId::capture_pointer`.
+    pub(crate) pointer_captures: HashMap<PointerId, ViewId>,
 }
 
 impl AppState {
@@ -57,6 +89,9 @@ impl AppState {
             root: None,
             root_view_id,
             focus: None,
+            focus_traps: Vec::new(),
+            focus_restore: Vec::new(),
+            dismiss_on_escape: Vec::new(),
             active: None,
             scale: 1.0,
             root_size: Size::ZERO,
@@ -70,6 +105,7 @@ impl AppState {
             dragging: None,
             drag_start: None,
             dragging_over: HashSet::new(),
+            external_drag_over: HashSet::new(),
             clicking: HashSet::new(),
             hovered: HashSet::new(),
             cursor: None,
@@ -78,7 +114,12 @@ impl AppState {
             grid_bps: GridBreakpoints::default(),
             window_menu: HashMap::new(),
             context_menu: HashMap::new(),
+            menu_accelerators: Vec::new(),
+            menu_bar_shortcuts: HashMap::new(),
             capture: None,
+            picking: None,
+            profile: None,
+            pointer_captures: HashMap::new(),
         }
     }
 
@@ -116,6 +157,8 @@ impl AppState {
         self.keyboard_navigable.remove(&id);
         self.draggable.remove(&id);
         self.dragging_over.remove(&id);
+        self.external_drag_over.remove(&id);
+        self.menu_bar_shortcuts.remove(&id);
         self.clicking.remove(&id);
         self.hovered.remove(&id);
         self.clicking.remove(&id);
@@ -125,6 +168,10 @@ impl AppState {
         if self.active == Some(id) {
             self.active = None;
         }
+        self.pointer_captures.retain(|_, captured| *captured != id);
+        self.focus_traps.retain(|trap| *trap != id);
+        self.dismiss_on_escape.retain(|dismiss| *dismiss != id);
+        self.pop_focus_restore(id);
     }
 
     pub(crate) fn can_focus(&self, id: ViewId) -> bool {
@@ -298,7 +345,118 @@ impl AppState {
         }
     }
 
+    pub(crate) fn update_window_menu(&mut self, menu: &mut Menu) {
+        menu.take_actions(&mut self.window_menu, &mut self.menu_accelerators);
+    }
+
+    /// The accelerators and mnemonics of the menu bar views, followed by the accelerators of the
+    /// window menu, with the items they trigger.
+    pub(crate) fn accelerators(&self) -> Vec<(&Shortcut, MenuAccelerator)> {
+        self.menu_bar_shortcuts
+            .iter()
+            .flat_map(|(bar, shortcuts)| {
+                shortcuts
+                    .iter()
+                    .map(|(shortcut, item)| (shortcut, MenuAccelerator::MenuBar(*bar, *item)))
+            })
+            .chain(
+                self.menu_accelerators
+                    .iter()
+                    .map(|(shortcut, id)| (shortcut, MenuAccelerator::WindowMenu(*id))),
+            )
+            .collect()
+    }
+
+    pub(crate) fn set_focus_trap(&mut self, id: ViewId, active: bool) {
+        if self.focus_traps.contains(&id) == active {
+            return;
+        }
+        if !active {
+            self.focus_traps.retain(|trap| *trap != id);
+            self.pop_focus_restore(id);
+            return;
+        }
+
+        self.focus_traps.push(id);
+        self.push_focus_restore(id);
+        if !self.focus.is_some_and(|focus| is_within(focus, id)) {
+            let old = self.focus;
+            self.clear_focus();
+            if let Some(first) = tab_order(self, id).first() {
+                self.update_focus(*first, true);
+            }
+            self.focus_changed(old, self.focus);
+        }
+    }
+
+    /// Remembers the focus, to return to it when `owner` closes, see [`pop_focus_restore`].
+    ///
+    /// [`pop_focus_restore`]: Self::pop_focus_restore
+    pub(crate) fn push_focus_restore(&mut self, owner: ViewId) {
+        self.focus_restore.push((owner, self.focus));
+    }
+
+    /// Returns the focus to where it was when `owner` called [`push_focus_restore`] if it's now
+    /// inside `owner` or nowhere.
+    ///
+    /// [`push_focus_restore`]: Self::push_focus_restore
+    pub(crate) fn pop_focus_restore(&mut self, owner: ViewId) {
+        let Some(index) = self.focus_restore.iter().rposition(|(id, _)| *id == owner) else {
+            return;
+        };
+        let (_, previous) = self.focus_restore.remove(index);
+        let exists = |id: &ViewId| VIEW_STORAGE.with_borrow(|s| s.view_ids.contains_key(*id));
+        let previous = previous.filter(exists);
+        // Entries pushed later that would return the focus into `owner` return it to where
+        // `owner` would instead.
+        for (_, focus) in &mut self.focus_restore[index..] {
+            if focus.is_some_and(|focus| !exists(&focus) || is_within(focus, owner)) {
+                *focus = previous;
+            }
+        }
+
+        let focus_inside = self
+            .focus
+            .map_or(true, |focus| !exists(&focus) || is_within(focus, owner));
+        if focus_inside && self.focus != previous {
+            let old = self.focus;
+            self.clear_focus();
+            if let Some(previous) = previous {
+                self.update_focus(previous, self.keyboard_navigation);
+            }
+            self.focus_changed(old, self.focus);
+        }
+    }
+
     pub(crate) fn focus_changed(&mut self, old: Option<ViewId>, new: Option<ViewId>) {
+        let with_ancestors = |id: Option<ViewId>| -> Vec<ViewId> {
+            id.map(|id| std::iter::once(id).chain(id.ancestors()).collect())
+                .unwrap_or_default()
+        };
+        let old_within = with_ancestors(old);
+        let new_within = with_ancestors(new);
+        for id in old_within.iter().filter(|id| !new_within.contains(id)) {
+            let listener = id.state().borrow().focus_within_listener.clone();
+            if let Some(listener) = listener {
+                listener(false);
+            }
+        }
+        for id in new_within.iter().filter(|id| !old_within.contains(id)) {
+            let listener = id.state().borrow().focus_within_listener.clone();
+            if let Some(listener) = listener {
+                listener(true);
+            }
+        }
+        if let Some(id) = new {
+            if let Some(group) = new_within
+                .iter()
+                .skip(1)
+                .find(|id| id.is_roving_focus_group())
+            {
+                group.state().borrow_mut().roving_last_focused = Some(id);
+            }
+        }
+
         if let Some(id) = new {
             // To apply the styles of the Focus selector
             if self.has_style_for_sel(id, StyleSelector::Focus)
@@ -21,10 +21,13 @@ use crate::view_state::IsHiddenState;
 use crate::{
     action::{exec_after, show_context_menu},
     app_state::AppState,
+    drag::{self, DropEffect},
     event::{Event, EventListener, EventPropagation},
+    hit_test::PaintHitInfo,
     id::ViewId,
     inspector::CaptureState,
     menu::Menu,
+    profiler::ViewTiming,
     style::{Style, StyleProp, ZIndex},
     view::{paint_bg, paint_border, paint_outline, View},
     view_state::ChangeFlags,
@@ -45,6 +48,24 @@ pub(crate) struct MoveListener {
     pub(crate) callback: Box<dyn Fn(Point)>,
 }
 
+/// The listener when the visible part of the view changes
+pub(crate) struct VisibilityListener {
+    pub(crate) ratio: Option<f64>,
+    pub(crate) callback: Box<dyn Fn(f64)>,
+}
+
+/// Calls the visibility listener of the view if the visible ratio changed.
+fn notify_visibility(id: ViewId, ratio: f64) {
+    let listener = id.state().borrow().visibility_listener.clone();
+    if let Some(listener) = listener {
+        let mut listener = listener.borrow_mut();
+        if listener.ratio != Some(ratio) {
+            listener.ratio = Some(ratio);
+            (*listener.callback)(ratio);
+        }
+    }
+}
+
 pub struct DragState {
     pub(crate) id: ViewId,
     pub(crate) offset: Vec2,
@@ -312,6 +333,7 @@ impl<'a> EventCx<'a> {
                                 released_at: None,
                             });
                             self.app_state.request_paint(view_id);
+                            drag::clear_drag_data();
                             view_id.apply_event(&EventListener::DragStart, &event);
                         }
                     }
@@ -333,9 +355,11 @@ impl<'a> EventCx<'a> {
                         if on_view {
                             if let Some(dragging) = self.app_state.dragging.as_mut() {
                                 let dragging_id = dragging.id;
-                                if view_id
-                                    .apply_event(&EventListener::Drop, &event)
-                                    .is_some_and(|prop| prop.is_processed())
+                                // a target that refused the drop doesn't get it
+                                if drag::drop_effect() != DropEffect::None
+                                    && view_id
+                                        .apply_event(&EventListener::Drop, &event)
+                                        .is_some_and(|prop| prop.is_processed())
                                 {
                                     // if the drop is processed, we set dragging to none so that the animation
                                     // for the dragged view back to its original position isn't played.
@@ -422,6 +446,12 @@ impl<'a> EventCx<'a> {
                     }
                 }
             }
+            Event::ExternalDragOver(drag_event) => {
+                let rect = view_id.get_size().unwrap_or_default().to_rect();
+                if rect.contains(drag_event.pos) {
+                    self.app_state.external_drag_over.insert(view_id);
+                }
+            }
             Event::KeyDown(_) => {
                 if self.app_state.is_focused(&view_id) && event.is_keyboard_trigger() {
                     view_id.apply_event(&EventListener::Click, &event);
@@ -565,6 +595,9 @@ impl<'a> StyleCx<'a> {
             }
             view_state.requested_changes.remove(ChangeFlags::STYLE);
         }
+        let timing = ViewTiming::start("Style", view_id, self.app_state, || {
+            view.borrow().debug_name()
+        });
 
         let view_style = view.borrow().view_style();
         let view_class = view.borrow().view_class();
@@ -634,6 +667,7 @@ impl<'a> StyleCx<'a> {
 
         view.borrow_mut().style_pass(self);
 
+        timing.finish(self.app_state);
         self.restore();
     }
 
@@ -759,9 +793,18 @@ impl<'a> ComputeLayoutCx<'a> {
             id.request_layout();
         }
 
+        let was_hidden = view_state.borrow().is_hidden_state == IsHiddenState::Hidden;
         view_state.borrow_mut().is_hidden_state = is_hidden_state;
         if is_hidden_state == IsHiddenState::Hidden {
             view_state.borrow_mut().layout_rect = Rect::ZERO;
+            if !was_hidden {
+                // The children won't be laid out while this view is hidden, so they
+                // need to be told that they're no longer visible here.
+                notify_visibility(id, 0.0);
+                for child in id.descendants() {
+                    notify_visibility(child, 0.0);
+                }
+            }
             return None;
         }
 
@@ -773,6 +816,9 @@ impl<'a> ComputeLayoutCx<'a> {
 
         view_state.borrow_mut().combined_style = modified;
 
+        let timing = ViewTiming::start("Compute Layout", id, self.app_state, || {
+            id.view().borrow().debug_name()
+        });
         self.save();
 
         let layout = id.get_layout().unwrap_or_default();
@@ -787,6 +833,12 @@ impl<'a> ComputeLayoutCx<'a> {
             ) + this_viewport_origin,
         );
         self.viewport = parent_viewport.intersect(size.to_rect());
+        let ratio = if size.is_zero_area() {
+            0.0
+        } else {
+            (self.viewport.area() / size.area()).clamp(0.0, 1.0)
+        };
+        notify_visibility(id, ratio);
         if let Some(this_viewport) = this_viewport {
             self.viewport = self.viewport.intersect(this_viewport);
         }
@@ -816,6 +868,11 @@ impl<'a> ComputeLayoutCx<'a> {
             }
         }
 
+        let mount_listener = view_state.borrow_mut().mount_listener.take();
+        if let Some(action) = mount_listener {
+            action();
+        }
+
         let view = id.view();
         let child_layout_rect = view.borrow_mut().compute_layout(self);
 
@@ -828,6 +885,7 @@ impl<'a> ComputeLayoutCx<'a> {
         view_state.borrow_mut().layout_rect = layout_rect;
 
         self.restore();
+        timing.finish(self.app_state);
 
         Some(layout_rect)
     }
@@ -897,7 +955,10 @@ impl<'a> LayoutCx<'a> {
 
     /// Internal method used by Floem to invoke the user-defined `View::layout` method.
     pub fn layout_view(&mut self, view: &mut dyn View) -> NodeId {
-        view.layout(self)
+        let timing = ViewTiming::start("Layout", view.id(), self.app_state, || view.debug_name());
+        let node = view.layout(self);
+        timing.finish(self.app_state);
+        node
     }
 }
 
@@ -920,6 +981,8 @@ pub struct PaintCx<'a> {
     pub(crate) saved_transforms: Vec<Affine>,
     pub(crate) saved_clips: Vec<Option<RoundedRect>>,
     pub(crate) saved_z_indexes: Vec<Option<i32>>,
+    /// The number of this paint pass, see [`PaintHitInfo::frame`].
+    pub(crate) paint_frame: u64,
 }
 
 impl<'a> PaintCx<'a> {
@@ -981,6 +1044,7 @@ impl<'a> PaintCx<'a> {
         }
         let view = id.view();
         let view_state = id.state();
+        let timing = ViewTiming::start("Paint", id, self.app_state, || view.borrow().debug_name());
 
         self.save();
         let size = self.transform(id);
@@ -988,6 +1052,7 @@ impl<'a> PaintCx<'a> {
             .clip
             .map(|rect| rect.rect().intersect(size.to_rect()).is_zero_area())
             .unwrap_or(false);
+        view_state.borrow_mut().paint_hit = None;
         if !is_empty {
             let style = view_state.borrow().combined_style.clone();
             let view_style_props = view_state.borrow().view_style_props.clone();
@@ -996,6 +1061,13 @@ impl<'a> PaintCx<'a> {
             if let Some(z_index) = style.get(ZIndex) {
                 self.set_z_index(z_index);
             }
+            view_state.borrow_mut().paint_hit = Some(PaintHitInfo {
+                transform: self.transform,
+                clip: self.clip.map(|clip| clip.rect()),
+                z_index: self.z_index.unwrap_or(0),
+                size,
+                frame: self.paint_frame,
+            });
 
             paint_bg(self, &style, &view_style_props, size);
 
@@ -1074,6 +1146,7 @@ impl<'a> PaintCx<'a> {
         }
 
         self.restore();
+        timing.finish(self.app_state);
     }
 
     /// Clip the drawing area to the given shape.
@@ -1348,3 +1421,76 @@ fn animations_recursive_on_create(id: ViewId) {
         .into_iter()
         .for_each(animations_recursive_on_create);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use floem_reactive::{create_rw_signal, SignalGet, SignalUpdate};
+
+    use super::*;
+    use crate::{
+        view::IntoView,
+        views::{container, empty, Decorators},
+    };
+
+    /// Runs the style, layout and compute layout passes of a frame, like a window does.
+    fn frame(app_state: &mut AppState, root: ViewId) {
+        StyleCx::new(app_state, root).style_view(root);
+        let view = root.view();
+        let node = LayoutCx::new(app_state).layout_view(view.borrow_mut().as_mut());
+        app_state.root = Some(node);
+        app_state.compute_layout();
+        let viewport = app_state.root_size.to_rect();
+        ComputeLayoutCx::new(app_state, viewport).compute_view_layout(root);
+    }
+
+    #[test]
+    fn visibility_and_mount_listeners() {
+        let hidden = create_rw_signal(false);
+        let ratios = Rc::new(RefCell::new(Vec::new()));
+        let mounts = Rc::new(Cell::new(0));
+        let child = empty()
+            .style(move |s| s.size(50.0, 50.0).apply_if(hidden.get(), |s| s.hide()))
+            .on_visibility_change({
+                let ratios = ratios.clone();
+                move |ratio| ratios.borrow_mut().push(ratio)
+            })
+            .on_mount({
+                let mounts = mounts.clone();
+                move || mounts.set(mounts.get() + 1)
+            });
+        let child_id = child.id();
+        let root = container(child).into_view();
+        let root_id = root.id();
+        root_id.set_view(root.into_any());
+
+        let mut app_state = AppState::new(root_id);
+        app_state.root_size = Size::new(200.0, 200.0);
+        frame(&mut app_state, root_id);
+        assert_eq!(*ratios.borrow(), vec![1.0]);
+        assert_eq!(mounts.get(), 1);
+
+        // Laying the view out again doesn't repeat either listener.
+        child_id.request_layout();
+        frame(&mut app_state, root_id);
+        assert_eq!(*ratios.borrow(), vec![1.0]);
+        assert_eq!(mounts.get(), 1);
+
+        hidden.set(true);
+        frame(&mut app_state, root_id);
+        frame(&mut app_state, root_id);
+        assert_eq!(*ratios.borrow(), vec![1.0, 0.0]);
+
+        hidden.set(false);
+        frame(&mut app_state, root_id);
+        frame(&mut app_state, root_id);
+        assert_eq!(*ratios.borrow(), vec![1.0, 0.0, 1.0]);
+        assert_eq!(mounts.get(), 1);
+
+        root_id.remove();
+    }
+}
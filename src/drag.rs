@@ -0,0 +1,78 @@
+//! Typed payloads for drag and drop between views.
+//!
+//! A drag source sets a payload with [`set_drag_data`] when it receives
+//! [`EventListener::DragStart`](crate::event::EventListener::DragStart), and drop targets read it back
+//! with [`drag_data`] in their `DragEnter`, `DragOver` and `Drop` listeners. Drop targets report
+//! what a drop would do with [`set_drop_effect`], which is reflected in the cursor. A drop on a
+//! target that set [`DropEffect::None`] is refused and the dragged view returns to its place.
+//!
+//! The [`Decorators::drag_data`](crate::views::Decorators::drag_data),
+//! [`Decorators::on_drag_over_data`](crate::views::Decorators::on_drag_over_data) and
+//! [`Decorators::on_drop_data`](crate::views::Decorators::on_drop_data) decorators cover the common cases.
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+/// What a drop on the view under the pointer would do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropEffect {
+    /// The drop is refused.
+    None,
+    /// The payload is copied to the target.
+    Copy,
+    /// The payload is moved to the target.
+    #[default]
+    Move,
+}
+
+#[derive(Default)]
+struct DragData {
+    payload: Option<Rc<dyn Any>>,
+    effect: DropEffect,
+}
+
+thread_local! {
+    static DRAG_DATA: RefCell<DragData> = RefCell::new(DragData::default());
+}
+
+/// Sets the payload of the current drag. Call this from a `DragStart` listener.
+pub fn set_drag_data<T: 'static>(data: T) {
+    DRAG_DATA.with_borrow_mut(|drag| drag.payload = Some(Rc::new(data)));
+}
+
+/// Returns the payload of the current drag if there is one and it is a `T`.
+pub fn drag_data<T: 'static>() -> Option<Rc<T>> {
+    DRAG_DATA
+        .with_borrow(|drag| drag.payload.clone())
+        .and_then(|payload| payload.downcast().ok())
+}
+
+/// Returns `true` if the current drag carries a `T`.
+pub fn has_drag_data<T: 'static>() -> bool {
+    DRAG_DATA.with_borrow(|drag| {
+        drag.payload
+            .as_ref()
+            .is_some_and(|payload| payload.is::<T>())
+    })
+}
+
+/// Sets the effect a drop at the current pointer position would have.
+///
+/// The effect is reset to [`DropEffect::Move`] every time the pointer moves, so `DragEnter` and
+/// `DragOver` listeners set it again for every move.
+pub fn set_drop_effect(effect: DropEffect) {
+    DRAG_DATA.with_borrow_mut(|drag| drag.effect = effect);
+}
+
+/// The effect a drop at the current pointer position would have.
+pub fn drop_effect() -> DropEffect {
+    DRAG_DATA.with_borrow(|drag| drag.effect)
+}
+
+pub(crate) fn reset_drop_effect() {
+    set_drop_effect(DropEffect::default());
+}
+
+/// Drops the payload of the finished drag.
+pub(crate) fn clear_drag_data() {
+    DRAG_DATA.with_borrow_mut(|drag| *drag = DragData::default());
+}
@@ -6,3 +6,30 @@ pub struct DroppedFileEvent {
     pub path: PathBuf,
     pub pos: Point,
 }
+
+/// Data dragged into a window from another application.
+///
+/// The windowing backend only reports dragged files, so text and URIs dragged from other
+/// applications aren't received. More kinds of data may be added when it supports them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExternalDragData {
+    /// One or more files, in the order the platform reported them.
+    Files(Vec<PathBuf>),
+}
+
+impl ExternalDragData {
+    /// The dragged files.
+    pub fn files(&self) -> &[PathBuf] {
+        match self {
+            ExternalDragData::Files(files) => files,
+        }
+    }
+}
+
+/// Data from another application that is dragged over or dropped on a view.
+#[derive(Debug, Clone)]
+pub struct ExternalDragEvent {
+    pub data: ExternalDragData,
+    pub pos: Point,
+}
@@ -5,9 +5,10 @@ use floem_winit::{
 use peniko::kurbo::{Point, Size};
 
 use crate::{
-    dropped_file::DroppedFileEvent,
+    dropped_file::{DroppedFileEvent, ExternalDragEvent},
     keyboard::KeyEvent,
-    pointer::{PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
+    pointer::{PointerId, PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
+    touch::{LongPressEvent, PanEvent, PinchEvent, RotateEvent, SwipeEvent, TapEvent, TouchEvent},
 };
 
 /// Control whether an event will continue propagating or whether it should stop.
@@ -97,6 +98,35 @@ pub enum EventListener {
     WindowMaximizeChanged,
     /// Receives [`Event::DroppedFile`]
     DroppedFile,
+    /// Receives [`Event::ExternalDragOver`] when data from another application is first dragged over the view
+    ExternalDragEnter,
+    /// Receives [`Event::ExternalDragOver`]
+    ExternalDragOver,
+    /// Receives [`Event::ExternalDragLeave`] when the drag leaves the view, is cancelled or is dropped
+    ExternalDragLeave,
+    /// Receives [`Event::ExternalDrop`]. Only dropped files are reported, see
+    /// [`ExternalDragData`](crate::dropped_file::ExternalDragData)
+    ExternalDrop,
+    /// Receives [`Event::TouchStart`]
+    TouchStart,
+    /// Receives [`Event::TouchMove`]
+    TouchMove,
+    /// Receives [`Event::TouchEnd`]
+    TouchEnd,
+    /// Receives [`Event::TouchCancel`]
+    TouchCancel,
+    /// Receives [`Event::Tap`]
+    Tap,
+    /// Receives [`Event::LongPress`]
+    LongPress,
+    /// Receives [`Event::Pan`]
+    Pan,
+    /// Receives [`Event::Swipe`]
+    Swipe,
+    /// Receives [`Event::Pinch`]
+    Pinch,
+    /// Receives [`Event::Rotate`]
+    Rotate,
 }
 
 #[derive(Debug, Clone)]
@@ -106,7 +136,20 @@ pub enum Event {
     PointerMove(PointerMoveEvent),
     PointerWheel(PointerWheelEvent),
     PointerLeave,
+    TouchStart(TouchEvent),
+    TouchMove(TouchEvent),
+    TouchEnd(TouchEvent),
+    TouchCancel(TouchEvent),
+    Tap(TapEvent),
+    LongPress(LongPressEvent),
+    Pan(PanEvent),
+    Swipe(SwipeEvent),
+    Pinch(PinchEvent),
+    Rotate(RotateEvent),
     DroppedFile(DroppedFileEvent),
+    ExternalDragOver(ExternalDragEvent),
+    ExternalDragLeave,
+    ExternalDrop(ExternalDragEvent),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     ImeEnabled,
@@ -148,7 +191,20 @@ impl Event {
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
+            | Event::DroppedFile(_)
+            | Event::ExternalDragOver(_)
+            | Event::ExternalDragLeave
+            | Event::ExternalDrop(_)
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::TouchCancel(_)
+            | Event::Tap(_)
+            | Event::LongPress(_)
+            | Event::Pan(_)
+            | Event::Swipe(_)
+            | Event::Pinch(_)
+            | Event::Rotate(_) => false,
             Event::KeyDown(_) | Event::KeyUp(_) => true,
         }
     }
@@ -175,7 +231,20 @@ impl Event {
             | Event::WindowMaximizeChanged(_)
             | Event::WindowGotFocus
             | Event::WindowLostFocus
-            | Event::DroppedFile(_) => false,
+            | Event::DroppedFile(_)
+            | Event::ExternalDragOver(_)
+            | Event::ExternalDragLeave
+            | Event::ExternalDrop(_)
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::TouchCancel(_)
+            | Event::Tap(_)
+            | Event::LongPress(_)
+            | Event::Pan(_)
+            | Event::Swipe(_)
+            | Event::Pinch(_)
+            | Event::Rotate(_) => false,
         }
     }
 
@@ -206,8 +275,20 @@ impl Event {
             | Event::ImePreedit { .. }
             | Event::ImeCommit(_)
             | Event::KeyDown(_)
-            | Event::KeyUp(_) => false,
+            | Event::KeyUp(_)
+            | Event::ExternalDragOver(_)
+            | Event::ExternalDrop(_)
+            | Event::TouchStart(_)
+            | Event::TouchMove(_)
+            | Event::TouchEnd(_)
+            | Event::Tap(_)
+            | Event::LongPress(_)
+            | Event::Pan(_)
+            | Event::Swipe(_)
+            | Event::Pinch(_)
+            | Event::Rotate(_) => false,
             Event::PointerLeave
+            | Event::TouchCancel(_)
             | Event::PointerMove(_)
             | Event::ThemeChanged(_)
             | Event::WindowClosed
@@ -216,6 +297,7 @@ impl Event {
             | Event::WindowGotFocus
             | Event::WindowMaximizeChanged(_)
             | Event::WindowLostFocus
+            | Event::ExternalDragLeave
             | Event::DroppedFile(_) => true,
         }
     }
@@ -228,7 +310,20 @@ impl Event {
             Event::PointerMove(pointer_event) => Some(pointer_event.pos),
             Event::PointerWheel(pointer_event) => Some(pointer_event.pos),
             Event::DroppedFile(event) => Some(event.pos),
+            Event::ExternalDragOver(event) | Event::ExternalDrop(event) => Some(event.pos),
+            Event::TouchStart(event)
+            | Event::TouchMove(event)
+            | Event::TouchEnd(event)
+            | Event::TouchCancel(event) => Some(event.pos),
+            Event::Tap(event) => Some(event.pos),
+            Event::LongPress(event) => Some(event.pos),
+            // Gestures go to the view where they started.
+            Event::Pan(event) => Some(event.origin),
+            Event::Swipe(event) => Some(event.origin),
+            Event::Pinch(event) => Some(event.origin),
+            Event::Rotate(event) => Some(event.origin),
             Event::PointerLeave
+            | Event::ExternalDragLeave
             | Event::KeyDown(_)
             | Event::KeyUp(_)
             | Event::FocusGained
@@ -247,6 +342,19 @@ impl Event {
         }
     }
 
+    /// The pointer that caused the event, if it's a pointer or touch event.
+    pub fn pointer_id(&self) -> Option<PointerId> {
+        match self {
+            Event::PointerDown(event) | Event::PointerUp(event) => Some(event.pointer_id),
+            Event::PointerMove(event) => Some(event.pointer_id),
+            Event::TouchStart(event)
+            | Event::TouchMove(event)
+            | Event::TouchEnd(event)
+            | Event::TouchCancel(event) => Some(event.pointer_id()),
+            _ => None,
+        }
+    }
+
     pub fn scale(mut self, scale: f64) -> Event {
         match &mut self {
             Event::PointerDown(pointer_event) | Event::PointerUp(pointer_event) => {
@@ -265,7 +373,52 @@ impl Event {
                 event.pos.x /= scale;
                 event.pos.y /= scale;
             }
+            Event::ExternalDragOver(event) | Event::ExternalDrop(event) => {
+                event.pos.x /= scale;
+                event.pos.y /= scale;
+            }
+            Event::TouchStart(event)
+            | Event::TouchMove(event)
+            | Event::TouchEnd(event)
+            | Event::TouchCancel(event) => {
+                event.pos.x /= scale;
+                event.pos.y /= scale;
+            }
+            Event::Tap(event) => {
+                event.pos.x /= scale;
+                event.pos.y /= scale;
+            }
+            Event::LongPress(event) => {
+                event.pos.x /= scale;
+                event.pos.y /= scale;
+            }
+            Event::Pan(event) => {
+                event.origin.x /= scale;
+                event.origin.y /= scale;
+                event.pos.x /= scale;
+                event.pos.y /= scale;
+                event.delta /= scale;
+                event.translation /= scale;
+            }
+            Event::Swipe(event) => {
+                event.origin.x /= scale;
+                event.origin.y /= scale;
+                event.velocity /= scale;
+            }
+            Event::Pinch(event) => {
+                event.origin.x /= scale;
+                event.origin.y /= scale;
+                event.center.x /= scale;
+                event.center.y /= scale;
+            }
+            Event::Rotate(event) => {
+                event.origin.x /= scale;
+                event.origin.y /= scale;
+                event.center.x /= scale;
+                event.center.y /= scale;
+            }
             Event::PointerLeave
+            | Event::ExternalDragLeave
             | Event::KeyDown(_)
             | Event::KeyUp(_)
             | Event::FocusGained
@@ -299,7 +452,38 @@ impl Event {
             Event::DroppedFile(event) => {
                 event.pos -= offset;
             }
+            Event::ExternalDragOver(event) | Event::ExternalDrop(event) => {
+                event.pos -= offset;
+            }
+            Event::TouchStart(event)
+            | Event::TouchMove(event)
+            | Event::TouchEnd(event)
+            | Event::TouchCancel(event) => {
+                event.pos -= offset;
+            }
+            Event::Tap(event) => {
+                event.pos -= offset;
+            }
+            Event::LongPress(event) => {
+                event.pos -= offset;
+            }
+            Event::Pan(event) => {
+                event.origin -= offset;
+                event.pos -= offset;
+            }
+            Event::Swipe(event) => {
+                event.origin -= offset;
+            }
+            Event::Pinch(event) => {
+                event.origin -= offset;
+                event.center -= offset;
+            }
+            Event::Rotate(event) => {
+                event.origin -= offset;
+                event.center -= offset;
+            }
             Event::PointerLeave
+            | Event::ExternalDragLeave
             | Event::KeyDown(_)
             | Event::KeyUp(_)
             | Event::FocusGained
@@ -342,6 +526,19 @@ impl Event {
             Event::FocusGained => Some(EventListener::FocusGained),
             Event::ThemeChanged(_) => Some(EventListener::ThemeChanged),
             Event::DroppedFile(_) => Some(EventListener::DroppedFile),
+            Event::ExternalDragOver(_) => Some(EventListener::ExternalDragOver),
+            Event::ExternalDragLeave => Some(EventListener::ExternalDragLeave),
+            Event::ExternalDrop(_) => Some(EventListener::ExternalDrop),
+            Event::TouchStart(_) => Some(EventListener::TouchStart),
+            Event::TouchMove(_) => Some(EventListener::TouchMove),
+            Event::TouchEnd(_) => Some(EventListener::TouchEnd),
+            Event::TouchCancel(_) => Some(EventListener::TouchCancel),
+            Event::Tap(_) => Some(EventListener::Tap),
+            Event::LongPress(_) => Some(EventListener::LongPress),
+            Event::Pan(_) => Some(EventListener::Pan),
+            Event::Swipe(_) => Some(EventListener::Swipe),
+            Event::Pinch(_) => Some(EventListener::Pinch),
+            Event::Rotate(_) => Some(EventListener::Rotate),
         }
     }
 }
@@ -0,0 +1,171 @@
+//! # Hit testing and view queries
+//!
+//! Functions to find the views under a point in a window and to search the view tree,
+//! for example to find drop targets or to select views in tests.
+
+use std::{cell::Cell, cmp::Reverse};
+
+use floem_winit::window::WindowId;
+use peniko::kurbo::{Affine, Point, Rect, Size};
+
+use crate::{
+    id::ViewId,
+    window_tracking::{root_view_id, root_view_ids},
+};
+
+/// How a view was last painted, which is used to hit test it.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PaintHitInfo {
+    /// Transform from the view's local coordinates to window coordinates.
+    pub(crate) transform: Affine,
+    /// The clip in effect when the view was painted, in the view's local coordinates.
+    pub(crate) clip: Option<Rect>,
+    pub(crate) z_index: i32,
+    pub(crate) size: Size,
+    /// The paint pass that recorded this, to ignore views that are no longer painted.
+    pub(crate) frame: u64,
+}
+
+thread_local! {
+    static PAINT_FRAME: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Starts a new paint pass of a window, returning its number for [`PaintHitInfo::frame`].
+pub(crate) fn next_paint_frame() -> u64 {
+    PAINT_FRAME.with(|frame| {
+        frame.set(frame.get() + 1);
+        frame.get()
+    })
+}
+
+impl PaintHitInfo {
+    fn contains(&self, point: Point) -> bool {
+        let local = self.transform.inverse() * point;
+        self.size.to_rect().contains(local) && self.clip.map_or(true, |clip| clip.contains(local))
+    }
+}
+
+/// Returns the views under `point` in the window, with the topmost view first.
+///
+/// The point is in window coordinates, like the positions of pointer events received by
+/// the root view. This uses the state of the last paint, so clipping, z-index and transforms
+/// like `scale` and `translate` are taken into account, and hidden views are never returned.
+pub fn view_at_point(window: WindowId, point: Point) -> Vec<ViewId> {
+    let Some(root) = root_view_id(&window) else {
+        return Vec::new();
+    };
+    hits_under(root, point)
+}
+
+/// The views under `point` in the tree of `root`, with the topmost view first.
+fn hits_under(root: ViewId, point: Point) -> Vec<ViewId> {
+    // The root is painted in every pass, so views painted in an earlier pass than the root
+    // weren't painted in the last one.
+    let Some(frame) = root.state().borrow().paint_hit.map(|info| info.frame) else {
+        return Vec::new();
+    };
+    let mut hits = Vec::new();
+    collect_hits(root, point, frame, &mut hits);
+    // Views painted later are drawn on top of earlier views with the same z-index.
+    hits.sort_by_key(|&(order, z_index, _)| Reverse((z_index, order)));
+    hits.into_iter().map(|(_, _, id)| id).collect()
+}
+
+fn collect_hits(id: ViewId, point: Point, frame: u64, hits: &mut Vec<(usize, i32, ViewId)>) {
+    if id.style_has_hidden() {
+        return;
+    }
+    let info = id.state().borrow().paint_hit;
+    let Some(info) = info.filter(|info| info.frame == frame) else {
+        // Neither this view nor its children were painted in the last pass
+        return;
+    };
+    if info.contains(point) {
+        hits.push((hits.len(), info.z_index, id));
+    }
+    for child in id.children() {
+        collect_hits(child, point, frame, hits);
+    }
+}
+
+/// Returns the views in all windows for which `predicate` returns `true`, in depth-first order.
+///
+/// Unlike [`view_at_point`], this searches the view tree, so it also returns views that
+/// aren't painted, such as hidden views or views scrolled out of sight.
+///
+/// ```rust,ignore
+/// let buttons = find_views(|id| id.debug_names().iter().any(|name| name == "Submit"));
+/// ```
+pub fn find_views(predicate: impl Fn(ViewId) -> bool) -> Vec<ViewId> {
+    root_view_ids()
+        .into_iter()
+        .flat_map(|root| std::iter::once(root).chain(root.descendants()))
+        .filter(|id| predicate(*id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn painted(id: ViewId, frame: u64, rect: Rect, clip: Option<Rect>, z_index: i32) {
+        id.state().borrow_mut().paint_hit = Some(PaintHitInfo {
+            transform: Affine::translate(rect.origin().to_vec2()),
+            clip: clip.map(|clip| clip - rect.origin().to_vec2()),
+            z_index,
+            size: rect.size(),
+            frame,
+        });
+    }
+
+    #[test]
+    fn topmost_first() {
+        let root = ViewId::new();
+        let below = ViewId::new();
+        let above = ViewId::new();
+        let later = ViewId::new();
+        root.set_children(vec![below, above, later]);
+        let window = Rect::new(0.0, 0.0, 100.0, 100.0);
+        painted(root, 1, window, None, 0);
+        painted(below, 1, Rect::new(0.0, 0.0, 50.0, 50.0), None, 0);
+        painted(above, 1, Rect::new(10.0, 10.0, 60.0, 60.0), None, 5);
+        painted(later, 1, Rect::new(20.0, 20.0, 70.0, 70.0), None, 0);
+
+        // A higher z-index is on top of views painted later
+        assert_eq!(
+            hits_under(root, Point::new(30.0, 30.0)),
+            vec![above, later, below, root]
+        );
+        assert_eq!(hits_under(root, Point::new(65.0, 65.0)), vec![later, root]);
+        assert_eq!(
+            hits_under(root, Point::new(200.0, 5.0)),
+            Vec::<ViewId>::new()
+        );
+    }
+
+    #[test]
+    fn clipped_and_stale() {
+        let root = ViewId::new();
+        let clipped = ViewId::new();
+        let stale = ViewId::new();
+        root.set_children(vec![clipped, stale]);
+        painted(root, 2, Rect::new(0.0, 0.0, 100.0, 100.0), None, 0);
+        // Only the left half of the view is visible in its parent's clip
+        painted(
+            clipped,
+            2,
+            Rect::new(0.0, 0.0, 80.0, 20.0),
+            Some(Rect::new(0.0, 0.0, 40.0, 100.0)),
+            0,
+        );
+        // Painted in an earlier pass, but not the last one
+        painted(stale, 1, Rect::new(0.0, 50.0, 100.0, 100.0), None, 0);
+
+        assert_eq!(
+            hits_under(root, Point::new(10.0, 10.0)),
+            vec![clipped, root]
+        );
+        assert_eq!(hits_under(root, Point::new(60.0, 10.0)), vec![root]);
+        assert_eq!(hits_under(root, Point::new(10.0, 70.0)), vec![root]);
+    }
+}
@@ -6,6 +6,8 @@
 
 use std::{any::Any, cell::RefCell, rc::Rc};
 
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, ActionData, NodeBuilder, Role};
 use floem_winit::window::WindowId;
 use peniko::kurbo::{Insets, Point, Rect, Size};
 use slotmap::new_key_type;
@@ -15,7 +17,10 @@ use crate::{
     animate::{AnimStateCommand, Animation},
     context::{EventCallback, ResizeCallback},
     event::{EventListener, EventPropagation},
+    keyboard::KeyEvent,
     menu::Menu,
+    pointer::PointerId,
+    shortcut::Shortcut,
     style::{DisplayProp, Style, StyleClassRef, StyleSelector},
     unit::PxPct,
     update::{UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES},
@@ -155,6 +160,44 @@ impl ViewId {
         VIEW_STORAGE.with_borrow(|s| s.parent.get(*self).cloned().flatten())
     }
 
+    /// Returns the ancestors of this view, starting with its parent and ending with the root.
+    pub fn ancestors(&self) -> Vec<ViewId> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent();
+        while let Some(id) = current {
+            ancestors.push(id);
+            current = id.parent();
+        }
+        ancestors
+    }
+
+    /// Returns all the descendants of this view in depth-first order.
+    pub fn descendants(&self) -> Vec<ViewId> {
+        let mut descendants = Vec::new();
+        let mut stack = self.children();
+        stack.reverse();
+        while let Some(id) = stack.pop() {
+            descendants.push(id);
+            stack.extend(id.children().into_iter().rev());
+        }
+        descendants
+    }
+
+    /// Returns the debug names set on this view with `debug_name`.
+    pub fn debug_names(&self) -> Vec<String> {
+        self.state().borrow().debug_name.iter().cloned().collect()
+    }
+
+    /// Returns `true` if the view has the style class, either set with `class` or
+    /// as the view's own class.
+    pub fn has_class(&self, class: StyleClassRef) -> bool {
+        self.state().borrow().classes.contains(&class)
+            || self
+                .view()
+                .try_borrow()
+                .is_ok_and(|view| view.view_class() == Some(class))
+    }
+
     pub(crate) fn root(&self) -> Option<ViewId> {
         VIEW_STORAGE.with_borrow_mut(|s| {
             if let Some(root) = s.root.get(*self) {
@@ -324,6 +367,27 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ClearActive(*self));
     }
 
+    /// Sends all events of the pointer to this view, wherever the pointer is, until the
+    /// capture is released with [`release_pointer_capture`](Self::release_pointer_capture)
+    /// or the pointer is lifted.
+    ///
+    /// Unlike [`request_active`](Self::request_active), each pointer can be captured by a
+    /// different view, so concurrent touches can be tracked by different views.
+    pub fn capture_pointer(&self, pointer_id: PointerId) {
+        self.add_update_message(UpdateMessage::CapturePointer {
+            id: *self,
+            pointer_id,
+        });
+    }
+
+    /// Releases the capture of the pointer if it's captured by this view.
+    pub fn release_pointer_capture(&self, pointer_id: PointerId) {
+        self.add_update_message(UpdateMessage::ReleasePointerCapture {
+            id: *self,
+            pointer_id,
+        });
+    }
+
     pub fn inspect(&self) {
         self.add_update_message(UpdateMessage::Inspect);
     }
@@ -332,6 +396,15 @@ impl ViewId {
         self.add_update_message(UpdateMessage::ScrollTo { id: *self, rect });
     }
 
+    /// Sets the accelerators and mnemonics of a menu bar, which are matched while the window has
+    /// focus and sent to the bar as an activation of the item with the id.
+    pub(crate) fn set_menu_bar_shortcuts(&self, shortcuts: Vec<(Shortcut, u64)>) {
+        self.add_update_message(UpdateMessage::MenuBarShortcuts {
+            id: *self,
+            shortcuts,
+        });
+    }
+
     pub(crate) fn transition_anim_complete(&self) {
         self.add_update_message(UpdateMessage::ViewTransitionAnimComplete(*self));
     }
@@ -368,6 +441,118 @@ impl ViewId {
         state.borrow_mut().viewport = Some(viewport);
     }
 
+    /// Adds a scope that activates shortcuts with a matching [`When`](crate::shortcut::When)
+    /// condition while this view or one of its children has focus.
+    pub fn add_shortcut_scope(&self, scope: impl Into<String>) {
+        self.state().borrow_mut().shortcut_scopes.push(scope.into());
+    }
+
+    /// Marks the key down events the view handles itself, like typing in a text input, so
+    /// shortcuts without a condition don't take them while the view has focus.
+    pub(crate) fn set_claims_key(&self, claims_key: impl Fn(&KeyEvent) -> bool + 'static) {
+        self.state().borrow_mut().claims_key = Some(Rc::new(claims_key));
+    }
+
+    /// Sets the position of the view in the tab order. Views with a positive index are visited
+    /// first, in increasing order, followed by the views with index 0 or no index in tree order.
+    /// Views with a negative index are skipped by Tab but can still be focused otherwise.
+    pub fn set_tab_index(&self, index: i32) {
+        self.state().borrow_mut().tab_index = Some(index);
+    }
+
+    pub(crate) fn tab_index(&self) -> Option<i32> {
+        self.state().borrow().tab_index
+    }
+
+    /// Keeps keyboard navigation inside this view while `active` is true. Activating the trap
+    /// moves the focus to the first focusable child, deactivating or removing it returns the
+    /// focus to where it was before.
+    pub fn set_focus_trap(&self, active: bool) {
+        self.add_update_message(UpdateMessage::FocusTrap { id: *self, active });
+    }
+
+    /// Sends `PopoverDismiss` to this view when Escape is pressed and nothing else handles it,
+    /// as long as it's the most recently registered view that is still alive.
+    pub(crate) fn set_dismiss_on_escape(&self) {
+        self.add_update_message(UpdateMessage::DismissOnEscape { id: *self });
+    }
+
+    /// Makes the focusable children of this view a roving focus group, which Tab treats as a
+    /// single stop and inside which the arrow keys, Home and End move the focus.
+    pub fn set_roving_focus(&self) {
+        self.state().borrow_mut().roving_focus = true;
+    }
+
+    /// Sets whether the arrow keys wrap around at the ends of the roving focus group of this
+    /// view, or stop at the first and last member. They wrap by default.
+    pub fn set_roving_focus_wrap(&self, wrap: bool) {
+        self.state().borrow_mut().roving_wrap = wrap;
+    }
+
+    pub(crate) fn is_roving_focus_group(&self) -> bool {
+        self.state().borrow().roving_focus
+    }
+
+    /// Sends `TabsUpdate::Cycle` to this view when Ctrl+Tab is pressed while it contains the
+    /// focus and nothing else handles it.
+    pub(crate) fn set_cycles_tabs(&self) {
+        self.state().borrow_mut().cycles_tabs = true;
+    }
+
+    pub(crate) fn cycles_tabs(&self) -> bool {
+        self.state().borrow().cycles_tabs
+    }
+
+    /// Sets the role of the view in the accessibility tree. Views without a role are only part
+    /// of the tree if they can be focused.
+    #[cfg(feature = "accessibility")]
+    pub fn set_accessibility_role(&self, role: Role) {
+        self.state().borrow_mut().accessibility.role = Some(role);
+    }
+
+    /// Sets the label that assistive technologies announce for the view. Buttons and similar
+    /// roles without a label use the text of their children.
+    #[cfg(feature = "accessibility")]
+    pub fn set_accessibility_label(&self, label: Option<String>) {
+        self.state().borrow_mut().accessibility.label = label;
+        self.request_paint();
+    }
+
+    #[cfg(feature = "accessibility")]
+    pub fn set_accessibility_description(&self, description: Option<String>) {
+        self.state().borrow_mut().accessibility.description = description;
+        self.request_paint();
+    }
+
+    /// Adds properties to the node of the view each time the accessibility tree is built.
+    #[cfg(feature = "accessibility")]
+    pub fn add_accessibility_properties(&self, properties: impl Fn(&mut NodeBuilder) + 'static) {
+        self.state()
+            .borrow_mut()
+            .accessibility
+            .properties
+            .push(Rc::new(properties));
+    }
+
+    /// Handles an action requested by an assistive technology, instead of the default handling
+    /// of focus, click and scroll requests.
+    #[cfg(feature = "accessibility")]
+    pub fn add_accessibility_action(
+        &self,
+        action: Action,
+        handler: impl Fn(Option<&ActionData>) + 'static,
+    ) {
+        self.state()
+            .borrow_mut()
+            .accessibility
+            .actions
+            .push((action, Rc::new(handler)));
+    }
+
+    pub fn update_focus_within_listener(&self, action: Box<dyn Fn(bool)>) {
+        self.state().borrow_mut().focus_within_listener = Some(Rc::from(action));
+    }
+
     pub fn add_event_listener(&self, listener: EventListener, action: Box<EventCallback>) {
         let state = self.state();
         state.borrow_mut().add_event_listener(listener, action);
@@ -412,6 +597,13 @@ impl ViewId {
         self.request_style();
     }
 
+    /// Updates the style overrides set from the inspector and restyles the view.
+    pub(crate) fn update_inspector_style(&self, update: impl FnOnce(&mut Option<Style>)) {
+        let state = self.state();
+        update(&mut state.borrow_mut().inspector_style);
+        self.request_style_recursive();
+    }
+
     pub(crate) fn update_style(&self, offset: StackOffset<Style>, style: Style) {
         let state = self.state();
         let old_any_inherited = state.borrow().style().any_inherited();
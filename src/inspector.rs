@@ -1,10 +1,11 @@
 use crate::app::{add_app_update_event, AppUpdateEvent};
 use crate::app_state::AppState;
-use crate::context::StyleCx;
+use crate::context::{PaintCx, StyleCx};
 use crate::event::{Event, EventListener, EventPropagation};
 use crate::id::ViewId;
 use crate::profiler::profiler;
 use crate::style::{Style, StyleClassRef, StylePropRef, Transition};
+use crate::unit::PxPctAuto;
 use crate::view::{IntoView, View};
 use crate::view_state::ChangeFlags;
 use crate::views::{
@@ -15,15 +16,16 @@ use crate::window::WindowConfig;
 use crate::{new_window, style, Clipboard};
 use floem_reactive::{
     create_effect, create_rw_signal, create_signal, RwSignal, Scope, SignalGet, SignalUpdate,
+    SignalWith,
 };
 use floem_winit::keyboard::{self, NamedKey};
 use floem_winit::window::WindowId;
 use image::DynamicImage;
-use peniko::kurbo::{Point, Rect, Size};
+use peniko::kurbo::{Insets, Point, Rect, Size};
 use peniko::Color;
 use slotmap::Key;
 use std::cell::Cell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
 use std::rc::Rc;
 
@@ -32,6 +34,8 @@ use std::time::{Duration, Instant};
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, Instant};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use taffy::prelude::Layout;
 use taffy::style::{AlignItems, FlexDirection};
 
@@ -43,6 +47,7 @@ pub struct CapturedView {
     custom_name: String,
     layout: Rect,
     taffy: Layout,
+    taffy_style: String,
     clipped: Rect,
     children: Vec<Rc<CapturedView>>,
     direct_style: Style,
@@ -56,6 +61,12 @@ impl CapturedView {
     pub fn capture(id: ViewId, app_state: &mut AppState, clip: Rect) -> Self {
         let layout = id.layout_rect();
         let taffy = id.get_layout().unwrap_or_default();
+        let taffy_style = id
+            .taffy()
+            .borrow()
+            .style(id.taffy_node())
+            .map(|style| format!("{style:?}"))
+            .unwrap_or_default();
         let view_state = id.state();
         let view_state = view_state.borrow();
         let computed_style = view_state.combined_style.clone();
@@ -83,6 +94,7 @@ impl CapturedView {
             custom_name,
             layout,
             taffy,
+            taffy_style,
             clipped,
             direct_style: computed_style,
             requested_changes: view_state.requested_changes,
@@ -179,6 +191,236 @@ impl CaptureState {
     }
 }
 
+impl Capture {
+    /// Creates a plain data snapshot of the captured view tree, which can be serialized
+    /// and compared against other snapshots.
+    pub fn snapshot(&self) -> CaptureSnapshot {
+        CaptureSnapshot {
+            window_size: (self.window_size.width, self.window_size.height),
+            scale: self.scale,
+            root: ViewSnapshot::new(&self.root, &self.state),
+        }
+    }
+
+    /// Serializes a snapshot of the captured view tree to pretty printed JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        self.snapshot().to_json()
+    }
+}
+
+/// A snapshot of a [`Capture`] containing the view tree with layout and style information.
+///
+/// View ids are left out so snapshots of different runs can be compared directly.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CaptureSnapshot {
+    pub window_size: (f64, f64),
+    pub scale: f64,
+    pub root: ViewSnapshot,
+}
+
+#[cfg(feature = "serde")]
+impl CaptureSnapshot {
+    /// Serializes the snapshot to pretty printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// A snapshot of a single view in a [`CaptureSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ViewSnapshot {
+    /// The type name of the view, combined with any custom debug names.
+    pub name: String,
+    /// The custom debug names set with `debug_name`.
+    pub debug_name: String,
+    /// The layout rect of the view in window coordinates, as `[x0, y0, x1, y1]`.
+    pub layout: [f64; 4],
+    /// The taffy layout location and size relative to the parent, as `[x, y, width, height]`.
+    pub taffy_layout: [f64; 4],
+    /// The `Debug` representation of the taffy style of the view.
+    pub taffy_style: String,
+    /// The computed style properties of the view, keyed by property name.
+    pub props: BTreeMap<String, String>,
+    pub children: Vec<ViewSnapshot>,
+}
+
+impl ViewSnapshot {
+    fn new(view: &CapturedView, state: &CaptureState) -> Self {
+        let props = state
+            .styles
+            .get(&view.id)
+            .map(|style| {
+                style
+                    .map
+                    .iter()
+                    .filter(|(key, _)| matches!(key.info, style::StyleKeyInfo::Prop(..)))
+                    .map(|(key, value)| {
+                        let name = format!("{key:?}");
+                        let name = name.strip_prefix("floem::style::").unwrap_or(&name);
+                        (name.to_string(), key.debug_any(&**value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            name: view.name.clone(),
+            debug_name: view.custom_name.clone(),
+            layout: [
+                view.layout.x0,
+                view.layout.y0,
+                view.layout.x1,
+                view.layout.y1,
+            ],
+            taffy_layout: [
+                view.taffy.location.x as f64,
+                view.taffy.location.y as f64,
+                view.taffy.size.width as f64,
+                view.taffy.size.height as f64,
+            ],
+            taffy_style: view.taffy_style.clone(),
+            props,
+            children: view
+                .children
+                .iter()
+                .map(|child| ViewSnapshot::new(child, state))
+                .collect(),
+        }
+    }
+}
+
+/// Copies a snapshot of the capture to the clipboard as JSON and returns a message describing
+/// the result.
+#[cfg(feature = "serde")]
+fn export_json(capture: &Capture) -> String {
+    let json = match capture.to_json() {
+        Ok(json) => json,
+        Err(err) => return format!("Failed to serialize capture: {err}"),
+    };
+    match Clipboard::set_contents(json) {
+        Ok(()) => "Copied to clipboard".to_string(),
+        Err(err) => format!("Failed to copy capture: {err:?}"),
+    }
+}
+
+/// State of the "pick element" mode, where the view under the pointer is highlighted
+/// in the app window and selected in the inspector when clicked.
+#[derive(Default)]
+pub(crate) struct PickState {
+    pub(crate) hovered: Option<ViewId>,
+}
+
+/// Handles an event sent to the app window while an element is being picked.
+/// Returns `true` if the event was consumed by the picker.
+pub(crate) fn pick_event(root: ViewId, app_state: &mut AppState, event: &Event) -> bool {
+    match event {
+        Event::PointerMove(e) => {
+            let hovered = find_view_at(root, e.pos, root.layout_rect());
+            if let Some(picking) = app_state.picking.as_mut() {
+                if picking.hovered != hovered {
+                    picking.hovered = hovered;
+                    app_state.request_paint(root);
+                }
+            }
+            true
+        }
+        Event::PointerDown(e) => {
+            if let Some(id) = find_view_at(root, e.pos, root.layout_rect()) {
+                PICKED.with(|picked| picked.set(Some(id)));
+            }
+            app_state.picking = None;
+            app_state.request_paint(root);
+            true
+        }
+        Event::PointerUp(_) | Event::PointerWheel(_) => true,
+        Event::KeyDown(e) if e.key.logical_key == keyboard::Key::Named(NamedKey::Escape) => {
+            app_state.picking = None;
+            app_state.request_paint(root);
+            true
+        }
+        _ => false,
+    }
+}
+
+fn find_view_at(id: ViewId, pos: Point, clip: Rect) -> Option<ViewId> {
+    if id.style_has_hidden() {
+        return None;
+    }
+    let clipped = id.layout_rect().intersect(clip);
+    id.children()
+        .into_iter()
+        .rev()
+        .find_map(|child| find_view_at(child, pos, clipped))
+        .or_else(|| clipped.contains(pos).then_some(id))
+}
+
+/// Paints the margin, border, padding and content boxes of the view being hovered
+/// while picking an element, on top of the app window.
+pub(crate) fn paint_box_model(cx: &mut PaintCx, id: ViewId) {
+    let view_state = id.state();
+    let origin = view_state.borrow().window_origin;
+    let props = view_state.borrow().layout_props.clone();
+    let size = id.get_size().unwrap_or_default();
+    let parent_width = id.parent_size().map(|size| size.width).unwrap_or_default();
+    let margin = |value| match value {
+        PxPctAuto::Px(px) => px,
+        PxPctAuto::Pct(pct) => parent_width * pct / 100.0,
+        PxPctAuto::Auto => 0.0,
+    };
+
+    let border_box = size.to_rect().with_origin(origin);
+    let margin_box = border_box.inset(Insets {
+        x0: margin(props.margin_left()),
+        y0: margin(props.margin_top()),
+        x1: margin(props.margin_right()),
+        y1: margin(props.margin_bottom()),
+    });
+    let padding_box = border_box.inset(-Insets {
+        x0: props.border_left().0,
+        y0: props.border_top().0,
+        x1: props.border_right().0,
+        y1: props.border_bottom().0,
+    });
+    let content_box = id.get_content_rect() + origin.to_vec2();
+
+    cx.save();
+    cx.set_z_index(i32::MAX);
+    cx.clear_clip();
+    fill_box_ring(cx, margin_box, border_box, Color::rgba8(246, 178, 107, 140));
+    fill_box_ring(
+        cx,
+        border_box,
+        padding_box,
+        Color::rgba8(255, 229, 153, 160),
+    );
+    fill_box_ring(
+        cx,
+        padding_box,
+        content_box,
+        Color::rgba8(147, 196, 125, 140),
+    );
+    cx.fill(&content_box, Color::rgba8(111, 168, 220, 140), 0.0);
+    cx.restore();
+}
+
+/// Fills the area between `outer` and `inner`.
+fn fill_box_ring(cx: &mut PaintCx, outer: Rect, inner: Rect, color: Color) {
+    let inner = inner.intersect(outer);
+    let strips = [
+        Rect::new(outer.x0, outer.y0, outer.x1, inner.y0),
+        Rect::new(outer.x0, inner.y1, outer.x1, outer.y1),
+        Rect::new(outer.x0, inner.y0, inner.x0, inner.y1),
+        Rect::new(inner.x1, inner.y0, outer.x1, inner.y1),
+    ];
+    for strip in strips {
+        if !strip.is_zero_area() {
+            cx.fill(&strip, color, 0.0);
+        }
+    }
+}
+
 fn captured_view_name(view: &CapturedView) -> impl IntoView {
     let name = static_label(view.name.clone());
     let id = text(view.id.data().as_ffi()).style(|s| {
@@ -543,6 +785,39 @@ fn stats(capture: &Capture) -> impl IntoView {
     ))
 }
 
+/// A text field which overrides a style property of a view in the live window when
+/// Enter is pressed. The field turns red if the value can't be parsed.
+fn style_editor(id: ViewId, prop: StylePropRef, value: String) -> impl IntoView {
+    let text = create_rw_signal(value);
+    let invalid = create_rw_signal(false);
+    text_input(text)
+        .on_event_cont(EventListener::KeyDown, move |event| {
+            if let Event::KeyDown(key) = event {
+                if key.key.logical_key == keyboard::Key::Named(NamedKey::Enter) {
+                    let value = text.with_untracked(|text| (prop.info().parse)(text));
+                    invalid.set(value.is_none());
+                    if let Some(value) = value {
+                        id.update_inspector_style(|style| {
+                            style
+                                .get_or_insert_with(Style::new)
+                                .map
+                                .insert(prop.key, value);
+                        });
+                    }
+                }
+            }
+        })
+        .style(move |s| {
+            s.margin_top(3.0)
+                .min_width(150.0)
+                .font_size(12.0)
+                .apply_if(invalid.get(), |s| {
+                    s.border_color(Color::rgb8(220, 60, 60))
+                        .background(Color::rgb8(255, 235, 235))
+                })
+        })
+}
+
 fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> impl IntoView {
     let capture = capture.clone();
     dyn_container(
@@ -640,7 +915,11 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                 let clear = button("Clear selection")
                     .style(|s| s.margin(5.0))
                     .on_click_stop(move |_| selected.set(None));
-                let clear = stack((clear,));
+                let view_id = view.id;
+                let reset = button("Reset style overrides")
+                    .style(|s| s.margin(5.0))
+                    .on_click_stop(move |_| view_id.update_inspector_style(|style| *style = None));
+                let clear = stack((clear, reset));
 
                 let style_header = header("View Style");
                 let class_header = header("Class Header");
@@ -701,6 +980,11 @@ fn selected_view(capture: &Rc<Capture>, selected: RwSignal<Option<ViewId>>) -> i
                         let mut v = (prop.info().debug_view)(&*value).unwrap_or_else(|| {
                             static_label((prop.info().debug_any)(&*value)).into_any()
                         });
+                        v = v_stack((
+                            v,
+                            style_editor(view_id, prop, (prop.info().debug_any)(&*value)),
+                        ))
+                        .into_any();
                         if let Some(transition) = style
                             .map
                             .get(&prop.info().transition_key)
@@ -927,20 +1211,68 @@ fn capture_view(
 
     let image = stack((image, selected_overlay, highlighted_overlay));
 
+    // Select the view picked in the app window. If the view isn't part of this capture,
+    // the window is captured again and the view is selected once the new capture is shown.
+    if let Some(id) = PENDING_PICK.take() {
+        if capture.root.find(id).is_some() {
+            update_select_view_id(id, &capture_view, false);
+        }
+    }
+    let capture_ = capture.clone();
+    let picked = PICKED.with(|picked| *picked);
+    create_effect(move |_| {
+        if let Some(id) = picked.get() {
+            picked.set(None);
+            if capture_.root.find(id).is_some() {
+                update_select_view_id(id, &capture_view, false);
+            } else {
+                PENDING_PICK.set(Some(id));
+                add_app_update_event(AppUpdateEvent::CaptureWindow {
+                    window_id,
+                    capture: capture_s.write_only(),
+                });
+            }
+        }
+    });
+
+    #[cfg(feature = "serde")]
+    let export = {
+        let export_status = create_rw_signal(String::new());
+        let capture = capture.clone();
+        h_stack((
+            button("Export JSON")
+                .style(|s| s.margin(5.0))
+                .on_click_stop(move |_| export_status.set(export_json(&capture))),
+            crate::views::label(move || export_status.get()),
+        ))
+        .style(|s| s.items_center())
+    };
+    #[cfg(not(feature = "serde"))]
+    let export = empty();
+
     let left_scroll = scroll(
         v_stack((
             header("Selected View"),
             selected_view(capture, capture_view.selected),
             header("Stats"),
             stats(capture),
-            button("Recapture")
-                .style(|s| s.margin(5.0))
-                .on_click_stop(move |_| {
-                    add_app_update_event(AppUpdateEvent::CaptureWindow {
-                        window_id,
-                        capture: capture_s.write_only(),
-                    })
-                }),
+            h_stack((
+                button("Recapture")
+                    .style(|s| s.margin(5.0))
+                    .on_click_stop(move |_| {
+                        add_app_update_event(AppUpdateEvent::CaptureWindow {
+                            window_id,
+                            capture: capture_s.write_only(),
+                        })
+                    }),
+                button("Pick Element")
+                    .style(|s| s.margin(5.0))
+                    .on_click_stop(move |_| {
+                        add_app_update_event(AppUpdateEvent::PickElement { window_id })
+                    }),
+                export,
+            ))
+            .style(|s| s.items_center()),
         ))
         .style(|s| s.min_width_full()),
     )
@@ -1077,6 +1409,25 @@ thread_local! {
     pub(crate) static CAPTURE: RwSignal<Option<Rc<Capture>>> = {
         Scope::new().create_rw_signal(None)
     };
+    /// The view last picked in the app window with the "pick element" mode.
+    pub(crate) static PICKED: RwSignal<Option<ViewId>> = {
+        Scope::new().create_rw_signal(None)
+    };
+    static PENDING_PICK: Cell<Option<ViewId>> = const { Cell::new(None) };
+}
+
+/// Captures the window and calls `callback` with a snapshot of its view tree, or `None` if the
+/// window doesn't exist.
+///
+/// The capture happens on the next application update, so the callback isn't called right away.
+pub fn snapshot_window(
+    window_id: WindowId,
+    callback: impl FnOnce(Option<CaptureSnapshot>) + 'static,
+) {
+    add_app_update_event(AppUpdateEvent::SnapshotWindow {
+        window_id,
+        callback: Box::new(callback),
+    })
 }
 
 pub fn capture(window_id: WindowId) {
@@ -1277,3 +1628,38 @@ impl RelativeViewId {
         }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_to_json() {
+        let leaf = ViewSnapshot {
+            name: "Label".to_string(),
+            debug_name: String::new(),
+            layout: [0.0, 0.0, 10.0, 5.5],
+            taffy_layout: [0.0, 0.0, 10.0, 5.5],
+            taffy_style: "Style { .. }".to_string(),
+            props: BTreeMap::from([("TextColor".to_string(), "\"red\"\n".to_string())]),
+            children: Vec::new(),
+        };
+        let snapshot = CaptureSnapshot {
+            window_size: (100.0, 50.0),
+            scale: 1.0,
+            root: ViewSnapshot {
+                name: "Stack".to_string(),
+                debug_name: "root".to_string(),
+                layout: [0.0, 0.0, 100.0, 50.0],
+                taffy_layout: [0.0, 0.0, 100.0, 50.0],
+                taffy_style: "Style { .. }".to_string(),
+                props: BTreeMap::new(),
+                children: vec![leaf],
+            },
+        };
+        let json = snapshot.to_json().unwrap();
+        assert!(json.contains(r#""TextColor": "\"red\"\n""#));
+        let parsed: CaptureSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, snapshot);
+    }
+}
@@ -148,6 +148,8 @@
 //! - [Understanding Ids](crate::id)
 //! - [How the update lifecycle works](crate::renderer)
 //!
+#[cfg(feature = "accessibility")]
+mod accessibility;
 pub mod action;
 pub mod animate;
 mod app;
@@ -155,12 +157,14 @@ mod app_handle;
 pub(crate) mod app_state;
 mod clipboard;
 pub mod context;
+pub mod drag;
 pub mod dropped_file;
 pub mod event;
 pub mod ext_event;
 pub mod file;
 #[cfg(any(feature = "rfd-async-std", feature = "rfd-tokio"))]
 pub mod file_action;
+pub mod hit_test;
 pub(crate) mod id;
 mod inspector;
 pub mod keyboard;
@@ -171,8 +175,10 @@ mod profiler;
 pub mod renderer;
 pub mod responsive;
 mod screen_layout;
+pub mod shortcut;
 pub mod style;
 pub(crate) mod theme;
+pub mod touch;
 pub mod unit;
 mod update;
 pub(crate) mod view;
@@ -185,6 +191,8 @@ mod window_handle;
 mod window_id;
 mod window_tracking;
 
+#[cfg(feature = "accessibility")]
+pub use accesskit;
 pub use app::{launch, quit_app, AppEvent, Application};
 pub use app_state::AppState;
 pub use clipboard::{Clipboard, ClipboardError};
@@ -192,6 +200,7 @@ pub use floem_reactive as reactive;
 pub use floem_renderer::text;
 pub use floem_renderer::Renderer;
 pub use id::ViewId;
+pub use inspector::{snapshot_window, CaptureSnapshot, ViewSnapshot};
 pub use peniko;
 pub use peniko::kurbo;
 pub use screen_layout::ScreenLayout;
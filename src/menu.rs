@@ -1,4 +1,6 @@
-use std::sync::atomic::AtomicU64;
+use std::{collections::HashMap, rc::Rc, sync::atomic::AtomicU64};
+
+use crate::shortcut::{self, Shortcut};
 
 /// An entry in a menu.
 ///
@@ -46,6 +48,56 @@ impl Menu {
         self.entry(MenuEntry::Separator)
     }
 
+    /// Append a group of radio items, one for each `(title, value)` option, where the item
+    /// whose value equals `selected` is checked. `on_select` is called with the value of the
+    /// chosen item.
+    pub fn radio_group<T: PartialEq + Clone + 'static>(
+        mut self,
+        options: impl IntoIterator<Item = (impl Into<String>, T)>,
+        selected: &T,
+        on_select: impl Fn(T) + 'static,
+    ) -> Self {
+        let on_select = Rc::new(on_select);
+        for (title, value) in options {
+            let on_select = on_select.clone();
+            let item = MenuItem::new(title).radio(value == *selected);
+            self = self.entry(item.action(move || on_select(value.clone())));
+        }
+        self
+    }
+
+    /// Moves the actions of the items into `actions` and collects the accelerators of the enabled
+    /// items, except those of commands, which the shortcut registry handles.
+    pub(crate) fn take_actions(
+        &mut self,
+        actions: &mut HashMap<usize, Box<dyn Fn()>>,
+        accelerators: &mut Vec<(Shortcut, usize)>,
+    ) {
+        if let Some(action) = self.item.action.take() {
+            actions.insert(self.item.id as usize, action);
+        }
+        for child in self.children.iter_mut() {
+            match child {
+                MenuEntry::Separator => {}
+                MenuEntry::Item(item) => {
+                    if let Some(action) = item.action.take() {
+                        actions.insert(item.id as usize, action);
+                    }
+                    if let (Some(shortcut), None, true) =
+                        (&item.shortcut, &item.command, item.enabled)
+                    {
+                        accelerators.push((shortcut.clone(), item.id as usize));
+                    }
+                }
+                MenuEntry::SubMenu(m) => {
+                    if m.item.enabled {
+                        m.take_actions(actions, accelerators);
+                    }
+                }
+            }
+        }
+    }
+
     pub(crate) fn platform_menu(&self) -> floem_winit::menu::Menu {
         let mut menu = if self.popup {
             floem_winit::menu::Menu::new_for_popup()
@@ -58,17 +110,19 @@ impl Menu {
                     menu.add_separator();
                 }
                 MenuEntry::Item(item) => {
-                    menu.add_item(
-                        item.id as u32,
-                        &item.title,
-                        // item.key.as_ref(),
-                        item.selected,
-                        item.enabled,
-                    );
+                    // Windows shows the text after a tab as the accelerator, other platforms
+                    // have no way to show it in a native menu.
+                    let title = match &item.shortcut {
+                        Some(shortcut) if cfg!(target_os = "windows") => {
+                            format!("{}\t{shortcut}", item.title)
+                        }
+                        _ => platform_title(&item.title),
+                    };
+                    menu.add_item(item.id as u32, &title, item.selected, item.enabled);
                 }
                 MenuEntry::SubMenu(m) => {
                     let enabled = m.item.enabled;
-                    let title = m.item.title.clone();
+                    let title = platform_title(&m.item.title);
                     menu.add_dropdown(m.platform_menu(), &title, enabled);
                 }
             }
@@ -77,11 +131,50 @@ impl Menu {
     }
 }
 
+/// The title shown in a native menu. Only Windows uses the `&` mnemonic markers, other
+/// platforms would show them.
+fn platform_title(title: &str) -> String {
+    if cfg!(target_os = "windows") {
+        title.to_string()
+    } else {
+        parse_mnemonic(title).0
+    }
+}
+
+/// Removes the `&` markers from a title and returns the mnemonic letter they mark.
+pub(crate) fn parse_mnemonic(title: &str) -> (String, Option<char>) {
+    let mut text = String::with_capacity(title.len());
+    let mut mnemonic = None;
+    let mut chars = title.chars();
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('&') => text.push('&'),
+            Some(c) => {
+                if mnemonic.is_none() {
+                    mnemonic = c.to_lowercase().next();
+                }
+                text.push(c);
+            }
+            None => {}
+        }
+    }
+    (text, mnemonic)
+}
+
 pub struct MenuItem {
     pub(crate) id: u64,
     pub(crate) title: String,
-    // key: Option<HotKey>,
-    selected: Option<bool>,
+    pub(crate) shortcut: Option<Shortcut>,
+    /// The command from the shortcut registry, whose shortcuts trigger the item.
+    pub(crate) command: Option<String>,
+    pub(crate) selected: Option<bool>,
+    pub(crate) radio: bool,
+    /// An SVG shown before the title in menus drawn by Floem.
+    pub(crate) icon: Option<String>,
     pub(crate) enabled: bool,
     pub(crate) action: Option<Box<dyn Fn()>>,
 }
@@ -99,13 +192,59 @@ impl MenuItem {
         Self {
             id,
             title: title.into(),
-            // key: None,
+            shortcut: None,
+            command: None,
             selected: None,
+            radio: false,
+            icon: None,
             enabled: true,
             action: None,
         }
     }
 
+    /// Set the keyboard accelerator, such as `Mod+S` or the chord `Ctrl+K Ctrl+O`, which is shown
+    /// in the menu and runs the item's action while the window has focus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shortcut` can't be parsed.
+    pub fn accelerator(mut self, shortcut: &str) -> Self {
+        self.shortcut = Some(shortcut.parse().unwrap());
+        self
+    }
+
+    /// Run a command of the [shortcut registry](crate::shortcut) when the item is chosen, and
+    /// show the command's shortcut as the accelerator.
+    pub fn command(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        self.shortcut = shortcut::shortcut_for(&id);
+        self.command = Some(id.clone());
+        self.action(move || {
+            shortcut::execute_command(&id);
+        })
+    }
+
+    /// Show a checkmark next to the item when `checked` is true.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.selected = Some(checked);
+        self.radio = false;
+        self
+    }
+
+    /// Make the item part of a radio group, marked when `selected` is true. Native menus show
+    /// radio items with a checkmark. See also [`Menu::radio_group`].
+    pub fn radio(mut self, selected: bool) -> Self {
+        self.selected = Some(selected);
+        self.radio = true;
+        self
+    }
+
+    /// Set an SVG icon that is shown before the title. Native menus don't show icons.
+    pub fn icon(mut self, svg: impl Into<String>) -> Self {
+        self.icon = Some(svg.into());
+        self
+    }
+
     pub fn action(mut self, action: impl Fn() + 'static) -> Self {
         self.action = Some(Box::new(action));
         self
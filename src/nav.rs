@@ -3,6 +3,94 @@ use peniko::kurbo::{Point, Rect};
 
 use crate::{app_state::AppState, id::ViewId, view::view_tab_navigation};
 
+/// Returns `true` if `id` is `scope` or one of its descendants.
+pub(crate) fn is_within(id: ViewId, scope: ViewId) -> bool {
+    id == scope || id.ancestors().contains(&scope)
+}
+
+/// The views inside `scope` that Tab moves the focus through, in order.
+///
+/// Views with a positive tab index come first, ordered by their index, followed by the views
+/// with index 0 or no index in tree order. Views with a negative index are skipped. A roving
+/// focus group is a single stop, at its focused member or else the one that had focus last.
+pub(crate) fn tab_order(app_state: &AppState, scope: ViewId) -> Vec<ViewId> {
+    let mut stops = Vec::new();
+    let mut stack = vec![scope];
+    while let Some(id) = stack.pop() {
+        if id.is_roving_focus_group() {
+            stops.extend(roving_stop(app_state, id));
+            continue;
+        }
+        if app_state.can_focus(id) && id.tab_index().map_or(true, |index| index >= 0) {
+            stops.push(id);
+        }
+        stack.extend(id.children().into_iter().rev());
+    }
+    // The sort is stable, so views without a positive index stay in tree order.
+    stops.sort_by_key(|id| match id.tab_index() {
+        Some(index) if index > 0 => (0, index),
+        _ => (1, 0),
+    });
+    stops
+}
+
+fn roving_members(app_state: &AppState, group: ViewId) -> Vec<ViewId> {
+    group
+        .descendants()
+        .into_iter()
+        .filter(|id| app_state.can_focus(*id))
+        .collect()
+}
+
+fn roving_stop(app_state: &AppState, group: ViewId) -> Option<ViewId> {
+    let members = roving_members(app_state, group);
+    let last_focused = group.state().borrow().roving_last_focused;
+    app_state
+        .focus
+        .filter(|focus| members.contains(focus))
+        .or(last_focused.filter(|last| members.contains(last)))
+        .or(members.first().copied())
+}
+
+/// Moves the focus between the members of the roving focus group that has focus. Returns
+/// `false` if the focus isn't inside a roving focus group.
+pub(crate) fn view_roving_navigation(key: NamedKey, app_state: &mut AppState) -> bool {
+    let Some(focus) = app_state.focus else {
+        return false;
+    };
+    let Some(group) = focus
+        .ancestors()
+        .into_iter()
+        .find(|id| id.is_roving_focus_group())
+    else {
+        return false;
+    };
+    let members = roving_members(app_state, group);
+    let Some(current) = members.iter().position(|id| *id == focus) else {
+        return false;
+    };
+    let len = members.len();
+    let wrap = group.state().borrow().roving_wrap;
+    let new_focus = match key {
+        NamedKey::ArrowUp | NamedKey::ArrowLeft if current > 0 || wrap => {
+            members[(current + len - 1) % len]
+        }
+        NamedKey::ArrowDown | NamedKey::ArrowRight if current + 1 < len || wrap => {
+            members[(current + 1) % len]
+        }
+        // The focus stays at the end of a group that doesn't wrap.
+        NamedKey::ArrowUp | NamedKey::ArrowLeft | NamedKey::ArrowDown | NamedKey::ArrowRight => {
+            return true
+        }
+        NamedKey::Home => members[0],
+        NamedKey::End => members[len - 1],
+        _ => return false,
+    };
+    app_state.clear_focus();
+    app_state.update_focus(new_focus, true);
+    true
+}
+
 pub(crate) fn view_arrow_navigation(key: NamedKey, app_state: &mut AppState, view: ViewId) {
     let focused = match app_state.focus {
         Some(id) => id,
@@ -35,12 +123,14 @@ pub(crate) fn view_arrow_navigation(key: NamedKey, app_state: &mut AppState, vie
         NamedKey::ArrowRight => Rect::new(rect.x1, f64::NEG_INFINITY, f64::INFINITY, f64::INFINITY),
         _ => panic!(),
     };
+    let trap = app_state.focus_traps.last().copied();
     let mut keyboard_navigable: Vec<ViewId> =
         app_state.keyboard_navigable.iter().copied().collect();
     keyboard_navigable.retain(|id| {
         let layout = id.layout_rect();
 
-        !layout.intersect(intersect_target).is_zero_area()
+        trap.map_or(true, |trap| is_within(*id, trap))
+            && !layout.intersect(intersect_target).is_zero_area()
             && center_target.contains(layout.center())
             && app_state.can_focus(*id)
             && *id != focused
@@ -72,3 +162,91 @@ pub(crate) fn view_arrow_navigation(key: NamedKey, app_state: &mut AppState, vie
         app_state.update_focus(id, true);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Makes `children` the children of `parent` and lets them take focus.
+    fn add_children(app_state: &mut AppState, parent: ViewId, children: &[ViewId]) {
+        parent.set_children_ids(children.to_vec());
+        for child in children {
+            child.set_parent(parent);
+            app_state.keyboard_navigable.insert(*child);
+        }
+    }
+
+    fn tab(app_state: &mut AppState, root: ViewId, backwards: bool) -> Option<ViewId> {
+        view_tab_navigation(root, app_state, backwards);
+        app_state.focus
+    }
+
+    #[test]
+    fn tab_order_by_index_then_tree() {
+        let root = ViewId::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| ViewId::new());
+        let mut app_state = AppState::new(root);
+        add_children(&mut app_state, root, &[a, b, c, d, e]);
+        b.set_tab_index(2);
+        c.set_tab_index(-1);
+        d.set_tab_index(1);
+        e.set_tab_index(0);
+
+        assert_eq!(tab_order(&app_state, root), vec![d, b, a, e]);
+
+        for id in [root, a, b, c, d, e] {
+            id.remove();
+        }
+    }
+
+    #[test]
+    fn focus_trap_keeps_tab_inside() {
+        let root = ViewId::new();
+        let [outside, trap, x, y] = [(); 4].map(|_| ViewId::new());
+        let mut app_state = AppState::new(root);
+        add_children(&mut app_state, root, &[outside, trap]);
+        add_children(&mut app_state, trap, &[x, y]);
+        app_state.keyboard_navigable.remove(&trap);
+        app_state.focus_traps.push(trap);
+        app_state.focus = Some(x);
+
+        assert_eq!(tab(&mut app_state, root, false), Some(y));
+        assert_eq!(tab(&mut app_state, root, false), Some(x));
+        assert_eq!(tab(&mut app_state, root, true), Some(y));
+        assert_eq!(tab(&mut app_state, root, true), Some(x));
+
+        for id in [root, outside, trap, x, y] {
+            id.remove();
+        }
+    }
+
+    #[test]
+    fn roving_arrows_wrap_or_stop() {
+        let root = ViewId::new();
+        let [group, a, b, c] = [(); 4].map(|_| ViewId::new());
+        let mut app_state = AppState::new(root);
+        add_children(&mut app_state, root, &[group]);
+        add_children(&mut app_state, group, &[a, b, c]);
+        app_state.keyboard_navigable.remove(&group);
+        group.set_roving_focus();
+        app_state.focus = Some(c);
+
+        let mut arrow = |key| {
+            assert!(view_roving_navigation(key, &mut app_state));
+            app_state.focus
+        };
+        assert_eq!(arrow(NamedKey::ArrowRight), Some(a));
+        assert_eq!(arrow(NamedKey::ArrowLeft), Some(c));
+        assert_eq!(arrow(NamedKey::Home), Some(a));
+
+        group.set_roving_focus_wrap(false);
+        assert_eq!(arrow(NamedKey::ArrowUp), Some(a));
+        assert_eq!(arrow(NamedKey::End), Some(c));
+        assert_eq!(arrow(NamedKey::ArrowDown), Some(c));
+        assert_eq!(arrow(NamedKey::ArrowLeft), Some(b));
+
+        for id in [root, group, a, b, c] {
+            id.remove();
+        }
+    }
+}
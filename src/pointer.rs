@@ -55,16 +55,70 @@ impl PointerButton {
     }
 }
 
+/// Identifies a pointer, so events from concurrent pointers can be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerId {
+    /// The mouse, or a touchpad or pen that the system reports as a mouse.
+    Mouse,
+    /// A touch or pen on a touchscreen, with the id of its [`TouchEvent`](crate::touch::TouchEvent)s.
+    Touch(u64),
+}
+
+impl PointerId {
+    pub fn is_touch(self) -> bool {
+        matches!(self, PointerId::Touch(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerType {
+    Mouse,
+    Pen,
+    Touch,
+}
+
 #[derive(Debug, Clone)]
 pub struct PointerInputEvent {
     pub pos: Point,
     pub button: PointerButton,
     pub modifiers: Modifiers,
     pub count: u8,
+    pub pointer_id: PointerId,
+    pub pointer_type: PointerType,
+    /// The pressure from `0.0` to `1.0`. Pointers that don't report pressure have a pressure
+    /// of `0.5` while a button is pressed, and `0.0` otherwise.
+    pub pressure: f64,
+    /// The angle between a pen and the surface in radians, where `PI / 2` is perpendicular,
+    /// if the device reports it.
+    pub tilt: Option<f64>,
+}
+
+impl PointerInputEvent {
+    /// Whether the event was sent for a touch, right after its touch event. It isn't sent if a
+    /// view handled the start of the touch, so views only handle a touch once.
+    pub fn is_from_touch(&self) -> bool {
+        self.pointer_id.is_touch()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PointerMoveEvent {
     pub pos: Point,
     pub modifiers: Modifiers,
+    pub pointer_id: PointerId,
+    pub pointer_type: PointerType,
+    /// The pressure from `0.0` to `1.0`. Pointers that don't report pressure have a pressure
+    /// of `0.5` while a button is pressed, and `0.0` otherwise.
+    pub pressure: f64,
+    /// The angle between a pen and the surface in radians, where `PI / 2` is perpendicular,
+    /// if the device reports it.
+    pub tilt: Option<f64>,
+}
+
+impl PointerMoveEvent {
+    /// Whether the event was sent for a touch, right after its touch event. See
+    /// [`PointerInputEvent::is_from_touch`].
+    pub fn is_from_touch(&self) -> bool {
+        self.pointer_id.is_touch()
+    }
 }
@@ -1,14 +1,18 @@
 use crate::app::{add_app_update_event, AppUpdateEvent};
+use crate::app_state::AppState;
 use crate::event::{Event, EventListener, EventPropagation};
+use crate::id::ViewId;
 use crate::inspector::header;
 use crate::view::IntoView;
 use crate::views::{
     button, clip, container, dyn_container, empty, h_stack, label, scroll, stack, static_label,
     text, v_stack, v_stack_from_iter, Decorators,
 };
-use floem_reactive::{create_rw_signal, RwSignal, Scope, SignalGet, SignalUpdate};
+use floem_reactive::{create_rw_signal, RwSignal, Scope, SignalGet, SignalUpdate, SignalWith};
 use floem_winit::window::WindowId;
 use peniko::Color;
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::mem;
 use std::rc::Rc;
@@ -24,6 +28,8 @@ pub struct ProfileEvent {
     pub start: Instant,
     pub end: Instant,
     pub name: &'static str,
+    /// The view and its type name, if this event is a style, layout or paint pass of a single view.
+    pub view: Option<(ViewId, Cow<'static, str>)>,
 }
 
 #[derive(Default)]
@@ -31,15 +37,136 @@ pub struct ProfileFrame {
     pub events: Vec<ProfileEvent>,
 }
 
-#[derive(Default)]
+impl ProfileFrame {
+    fn start(&self) -> Option<Instant> {
+        self.events.iter().map(|event| event.start).min()
+    }
+
+    fn end(&self) -> Option<Instant> {
+        self.events.iter().map(|event| event.end).max()
+    }
+}
+
+/// The number of frames kept by a [`Profile`] before the oldest frames are dropped.
+pub const DEFAULT_PROFILE_FRAMES: usize = 1000;
+
 pub struct Profile {
     pub current: ProfileFrame,
-    frames: Vec<ProfileFrame>,
+    frames: VecDeque<ProfileFrame>,
+    max_frames: usize,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::with_max_frames(DEFAULT_PROFILE_FRAMES)
+    }
 }
 
 impl Profile {
+    /// Creates a profile which keeps at most `max_frames` frames, dropping the oldest
+    /// frames once it's full.
+    pub fn with_max_frames(max_frames: usize) -> Self {
+        Self {
+            current: ProfileFrame::default(),
+            frames: VecDeque::new(),
+            max_frames: max_frames.max(1),
+        }
+    }
+
     pub fn next_frame(&mut self) {
-        self.frames.push(mem::take(&mut self.current));
+        if self.frames.len() == self.max_frames {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(mem::take(&mut self.current));
+    }
+
+    /// Serializes the recorded frames to the Chrome Trace Event JSON format, which can be
+    /// opened in Perfetto or `chrome://tracing`.
+    #[cfg(feature = "serde")]
+    pub fn to_chrome_trace(&self) -> String {
+        use serde_json::json;
+
+        let origin = self.frames.iter().filter_map(|frame| frame.start()).min();
+        let mut events = Vec::new();
+        for (index, frame) in self.frames.iter().enumerate() {
+            for event in &frame.events {
+                let ts = origin
+                    .map(|origin| event.start.saturating_duration_since(origin))
+                    .unwrap_or_default();
+                let dur = event.end.saturating_duration_since(event.start);
+                let (name, cat, args) = match &event.view {
+                    Some((id, view_name)) => (
+                        format!("{} {}", event.name, view_name),
+                        "view",
+                        json!({
+                            "frame": index,
+                            "view": view_name,
+                            "view_id": format!("{id:?}"),
+                        }),
+                    ),
+                    None => (event.name.to_string(), "window", json!({ "frame": index })),
+                };
+                events.push(json!({
+                    "name": name,
+                    "cat": cat,
+                    "ph": "X",
+                    "ts": ts.as_secs_f64() * 1_000_000.0,
+                    "dur": dur.as_secs_f64() * 1_000_000.0,
+                    "pid": 1,
+                    "tid": 1,
+                    "args": args,
+                }));
+            }
+        }
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" }).to_string()
+    }
+}
+
+/// Times a style, layout or paint pass of a single view.
+///
+/// The pass is recorded in the window profile if the window is being profiled, and
+/// a `tracing` span is entered for it if the `tracing` feature is enabled.
+pub(crate) struct ViewTiming {
+    name: &'static str,
+    view: Option<(ViewId, Cow<'static, str>)>,
+    start: Option<Instant>,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl ViewTiming {
+    pub(crate) fn start(
+        name: &'static str,
+        id: ViewId,
+        app_state: &AppState,
+        view_name: impl FnOnce() -> Cow<'static, str>,
+    ) -> Self {
+        let profiling = app_state.profile.is_some();
+        let view = (profiling || cfg!(feature = "tracing")).then(|| (id, view_name()));
+        Self {
+            name,
+            #[cfg(feature = "tracing")]
+            _span: tracing::trace_span!(
+                "view",
+                pass = name,
+                view = view.as_ref().map(|(_, name)| &**name),
+                id = ?id
+            )
+            .entered(),
+            view,
+            start: profiling.then(Instant::now),
+        }
+    }
+
+    pub(crate) fn finish(self, app_state: &mut AppState) {
+        if let (Some(start), Some(profile)) = (self.start, app_state.profile.as_mut()) {
+            profile.current.events.push(ProfileEvent {
+                start,
+                end: Instant::now(),
+                name: self.name,
+                view: self.view,
+            });
+        }
     }
 }
 
@@ -48,6 +175,8 @@ struct ProfileFrameData {
     duration: Duration,
     sum: Duration,
     events: Vec<ProfileEvent>,
+    /// The slowest style, layout and paint passes of single views in the frame.
+    slowest_views: Vec<ProfileEvent>,
 }
 
 fn info(name: impl Display, value: String) -> impl IntoView {
@@ -74,11 +203,12 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
         .frames
         .iter()
         .map(|frame| {
-            let start = frame.events.first().map(|event| event.start);
-            let end = frame.events.last().map(|event| event.end);
+            let start = frame.start();
+            let end = frame.end();
             let sum = frame
                 .events
                 .iter()
+                .filter(|event| event.view.is_none())
                 .map(|event| event.end.saturating_duration_since(event.start))
                 .sum();
             let duration = start
@@ -89,6 +219,19 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
                 duration,
                 sum,
                 events: frame.events.clone(),
+                slowest_views: {
+                    let mut views: Vec<_> = frame
+                        .events
+                        .iter()
+                        .filter(|event| event.view.is_some())
+                        .cloned()
+                        .collect();
+                    views.sort_by_key(|event| {
+                        std::cmp::Reverse(event.end.saturating_duration_since(event.start))
+                    });
+                    views.truncate(20);
+                    views
+                },
             })
         })
         .collect();
@@ -138,8 +281,14 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
                     .end
                     .saturating_duration_since(event.start)
                     .as_secs_f64();
+                let view = event
+                    .view
+                    .as_ref()
+                    .map(|(id, name)| format!("{name} ({id:?})"))
+                    .unwrap_or_default();
                 v_stack((
                     info("Name", event.name.to_string()),
+                    info("View", view),
                     info("Time", format!("{:.4} ms", len * 1000.0)),
                 ))
                 .into_any()
@@ -175,40 +324,44 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
         move || selected_frame.get(),
         move |selected_frame| {
             if let Some(frame) = selected_frame {
-                let list = frame.events.iter().map(|event| {
-                    let len = event
-                        .end
-                        .saturating_duration_since(event.start)
-                        .as_secs_f64();
-                    let left = event
-                        .start
-                        .saturating_duration_since(frame.start.unwrap())
-                        .as_secs_f64()
-                        / frame.duration.as_secs_f64();
-                    let width = len / frame.duration.as_secs_f64();
-                    let event_ = event.clone();
-                    clip(
-                        static_label(format!("{} ({:.4} ms)", event.name, len * 1000.0))
-                            .style(|s| s.padding(5.0)),
-                    )
-                    .style(move |s| {
-                        s.min_width(0)
-                            .width_pct(width * 100.0)
-                            .absolute()
-                            .inset_left_pct(left * 100.0)
-                            .border(0.3)
-                            .border_color(Color::rgb8(129, 164, 192))
-                            .background(Color::rgb8(209, 222, 233).with_alpha_factor(0.6))
-                            .text_clip()
-                            .hover(|s| {
-                                s.color(Color::WHITE)
-                                    .background(Color::BLACK.with_alpha_factor(0.6))
-                            })
-                    })
-                    .on_event_cont(EventListener::PointerEnter, move |_| {
-                        hovered_event.set(Some(event_.clone()))
-                    })
-                });
+                let list = frame
+                    .events
+                    .iter()
+                    .filter(|event| event.view.is_none())
+                    .map(|event| {
+                        let len = event
+                            .end
+                            .saturating_duration_since(event.start)
+                            .as_secs_f64();
+                        let left = event
+                            .start
+                            .saturating_duration_since(frame.start.unwrap())
+                            .as_secs_f64()
+                            / frame.duration.as_secs_f64();
+                        let width = len / frame.duration.as_secs_f64();
+                        let event_ = event.clone();
+                        clip(
+                            static_label(format!("{} ({:.4} ms)", event.name, len * 1000.0))
+                                .style(|s| s.padding(5.0)),
+                        )
+                        .style(move |s| {
+                            s.min_width(0)
+                                .width_pct(width * 100.0)
+                                .absolute()
+                                .inset_left_pct(left * 100.0)
+                                .border(0.3)
+                                .border_color(Color::rgb8(129, 164, 192))
+                                .background(Color::rgb8(209, 222, 233).with_alpha_factor(0.6))
+                                .text_clip()
+                                .hover(|s| {
+                                    s.color(Color::WHITE)
+                                        .background(Color::BLACK.with_alpha_factor(0.6))
+                                })
+                        })
+                        .on_event_cont(EventListener::PointerEnter, move |_| {
+                            hovered_event.set(Some(event_.clone()))
+                        })
+                    });
                 scroll(
                     v_stack_from_iter(list)
                         .style(move |s| s.min_width_pct(zoom.get() * 100.0).height_full()),
@@ -238,12 +391,79 @@ fn profile_view(profile: &Rc<Profile>) -> impl IntoView {
             .background(Color::WHITE)
     });
 
-    let timeline = v_stack((header("Timeline"), timeline))
-        .style(|s| s.min_width(0).flex_basis(0).flex_grow(1.0));
+    let slowest_views = dyn_container(
+        move || selected_frame.get(),
+        move |selected_frame| {
+            if let Some(frame) = selected_frame {
+                let list = frame.slowest_views.iter().map(|event| {
+                    let len = event.end.saturating_duration_since(event.start);
+                    let name = event
+                        .view
+                        .as_ref()
+                        .map(|(_, name)| name.to_string())
+                        .unwrap_or_default();
+                    let event_ = event.clone();
+                    h_stack((
+                        static_label(format!("{} {name}", event.name)).style(|s| s.flex_grow(1.0)),
+                        static_label(format!("{:.4} ms", len.as_secs_f64() * 1000.0))
+                            .style(|s| s.margin_right(16)),
+                    ))
+                    .style(|s| {
+                        s.padding(5.0)
+                            .hover(|s| s.background(Color::rgba8(228, 237, 216, 160)))
+                    })
+                    .on_event_cont(EventListener::PointerEnter, move |_| {
+                        hovered_event.set(Some(event_.clone()))
+                    })
+                });
+                scroll(v_stack_from_iter(list).style(|s| s.width_full()))
+                    .style(|s| s.width_full().height_full())
+                    .into_any()
+            } else {
+                text("No selected frame")
+                    .style(|s| s.padding(5.0))
+                    .into_any()
+            }
+        },
+    )
+    .style(|s| s.width_full().height(200.0).background(Color::WHITE));
+
+    let timeline = v_stack((
+        header("Timeline"),
+        timeline,
+        header("Slowest Views"),
+        slowest_views,
+    ))
+    .style(|s| s.min_width(0).flex_basis(0).flex_grow(1.0));
 
     h_stack((frames, separator, timeline)).style(|s| s.height_full().width_full().max_width_full())
 }
 
+/// Writes the profile as a Chrome trace to the temporary directory and returns a message
+/// describing where it was written.
+#[cfg(feature = "serde")]
+fn export_chrome_trace(profile: &Profile) -> String {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = std::env::temp_dir().join(format!("floem-trace-{timestamp}.json"));
+        match std::fs::write(&path, profile.to_chrome_trace()) {
+            Ok(()) => format!("Saved to {}", path.display()),
+            Err(err) => format!("Failed to save trace: {err}"),
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        match crate::Clipboard::set_contents(profile.to_chrome_trace()) {
+            Ok(()) => "Copied to clipboard".to_string(),
+            Err(err) => format!("Failed to copy trace: {err:?}"),
+        }
+    }
+}
+
 thread_local! {
     pub(crate) static PROFILE: RwSignal<Option<Rc<Profile>>> = {
         Scope::new().create_rw_signal(None)
@@ -254,6 +474,25 @@ pub fn profiler(window_id: WindowId) -> impl IntoView {
     let profiling = create_rw_signal(false);
     let profile = PROFILE.with(|c| *c);
 
+    #[cfg(feature = "serde")]
+    let export = {
+        let export_status = create_rw_signal(String::new());
+        h_stack((
+            button("Export Chrome Trace")
+                .on_click_stop(move |_| {
+                    if let Some(profile) = profile.get_untracked() {
+                        export_status.set(export_chrome_trace(&profile));
+                    }
+                })
+                .disabled(move || profile.with(|profile| profile.is_none()))
+                .style(|s| s.margin(5.0)),
+            label(move || export_status.get()),
+        ))
+        .style(|s| s.items_center())
+    };
+    #[cfg(not(feature = "serde"))]
+    let export = empty();
+
     let button = h_stack((
         button(label(move || {
             if profiling.get() {
@@ -275,6 +514,7 @@ pub fn profiler(window_id: WindowId) -> impl IntoView {
         })
         .style(|s| s.margin(5.0)),
         label(move || if profiling.get() { "Profiling..." } else { "" }),
+        export,
     ))
     .style(|s| s.items_center());
 
@@ -308,3 +548,73 @@ pub fn profiler(window_id: WindowId) -> impl IntoView {
             }
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(start: Instant, events: &[(&'static str, u64, u64)]) -> ProfileFrame {
+        ProfileFrame {
+            events: events
+                .iter()
+                .map(|&(name, from, to)| ProfileEvent {
+                    start: start + Duration::from_micros(from),
+                    end: start + Duration::from_micros(to),
+                    name,
+                    view: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn drop_oldest_frames() {
+        let start = Instant::now();
+        let mut profile = Profile::default();
+        for i in 0..DEFAULT_PROFILE_FRAMES as u64 + 5 {
+            profile.current = frame(start, &[("Layout", i, i + 1)]);
+            profile.next_frame();
+        }
+        assert_eq!(profile.frames.len(), DEFAULT_PROFILE_FRAMES);
+        // The first five frames were dropped
+        assert_eq!(
+            profile.frames[0].start(),
+            Some(start + Duration::from_micros(5))
+        );
+        assert!(profile.current.events.is_empty());
+
+        let mut profile = Profile::with_max_frames(0);
+        profile.next_frame();
+        profile.next_frame();
+        assert_eq!(profile.frames.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn chrome_trace() {
+        let start = Instant::now();
+        let mut profile = Profile::default();
+        profile.current = frame(start, &[("Layout", 10, 40), ("Paint", 40, 60)]);
+        profile.next_frame();
+        profile.current = frame(start, &[("Style \"quoted\"", 100, 101)]);
+        profile.next_frame();
+
+        let trace: serde_json::Value = serde_json::from_str(&profile.to_chrome_trace()).unwrap();
+        assert_eq!(trace["displayTimeUnit"], "ms");
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0]["name"], "Layout");
+        assert_eq!(events[0]["ph"], "X");
+        assert_eq!(events[1]["args"]["frame"], 0);
+        assert_eq!(events[2]["name"], "Style \"quoted\"");
+        assert_eq!(events[2]["args"]["frame"], 1);
+        let micros = |value: &serde_json::Value| value.as_f64().unwrap().round();
+        assert_eq!(micros(&events[1]["ts"]), 30.0);
+        assert_eq!(micros(&events[1]["dur"]), 20.0);
+        assert_eq!(micros(&events[2]["ts"]), 90.0);
+
+        let empty: serde_json::Value =
+            serde_json::from_str(&Profile::default().to_chrome_trace()).unwrap();
+        assert_eq!(empty["traceEvents"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,1080 @@
+//! An application-wide registry of commands and their keyboard shortcuts.
+//!
+//! Commands are registered with [`register_command`] together with their default bindings.
+//! A binding maps a [`Shortcut`], which is a sequence of one or more [`KeyStroke`]s such as
+//! `Ctrl+K Ctrl+C`, to a command, optionally guarded by a [`When`] condition.
+//!
+//! Conditions are made of names that are true while a view with that
+//! [shortcut scope](crate::views::Decorators::shortcut_scope) or one of its children has focus, or
+//! while a context with that name is enabled with [`set_context`].
+//!
+//! User overrides are loaded with [`load_keymap`] or [`load_keymap_file`]. A keymap has one
+//! binding per line in the form `<shortcut> = <command> [when <condition>]`. Prefixing the command
+//! with `-` removes the binding instead. Lines starting with `#` are comments.
+//!
+//! ```text
+//! # comment out lines
+//! ctrl+k ctrl+c = editor.comment when editor && !readOnly
+//! # disable the default save shortcut
+//! mod+s = -file.save
+//! ```
+//!
+//! Key down events are matched against the bindings before they reach the focused view. Keys a
+//! focused text input, text area or editor uses itself only match bindings with a condition.
+//! Use [`shortcut_for`] to display a command's shortcut, for example in a menu, and [`conflicts`]
+//! to find bindings that shadow each other.
+
+use std::{cell::RefCell, collections::HashSet, fmt::Display, path::Path, rc::Rc, str::FromStr};
+
+use indexmap::IndexMap;
+use smallvec::SmallVec;
+
+use crate::{
+    id::ViewId,
+    keyboard::{Key, KeyEvent, Modifiers, NamedKey},
+};
+
+/// A key together with the modifiers that have to be held.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyStroke {
+    pub key: Key,
+    pub mods: Modifiers,
+}
+
+impl KeyStroke {
+    pub fn new(key: Key, mods: Modifiers) -> Self {
+        Self {
+            key: normalize_key(key),
+            mods,
+        }
+    }
+
+    /// The key stroke of a key down event, or `None` if only a modifier was pressed.
+    pub fn from_event(event: &KeyEvent) -> Option<Self> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let key = {
+            use crate::keyboard::KeyEventExtModifierSupplement;
+            // Matching uses the key without shift or option applied, so `Shift+1` is not `!`.
+            event.key.key_without_modifiers()
+        };
+        #[cfg(target_arch = "wasm32")]
+        let key = event.key.logical_key.clone();
+
+        if matches!(
+            key,
+            Key::Named(
+                NamedKey::Shift
+                    | NamedKey::Control
+                    | NamedKey::Alt
+                    | NamedKey::AltGraph
+                    | NamedKey::Meta
+                    | NamedKey::Super
+            )
+        ) {
+            return None;
+        }
+        let mut mods = event.modifiers;
+        mods.remove(Modifiers::ALTGR);
+        Some(Self::new(key, mods))
+    }
+}
+
+fn normalize_key(key: Key) -> Key {
+    match key {
+        Key::Character(c) if c == " " => Key::Named(NamedKey::Space),
+        Key::Character(c) => Key::Character(c.to_lowercase().into()),
+        key => key,
+    }
+}
+
+/// The modifier used for most shortcuts on the current platform: command on macOS and control
+/// elsewhere.
+pub fn primary_modifier() -> Modifiers {
+    if cfg!(target_os = "macos") {
+        Modifiers::META
+    } else {
+        Modifiers::CONTROL
+    }
+}
+
+const NAMED_KEYS: &[(&str, NamedKey)] = &[
+    ("enter", NamedKey::Enter),
+    ("tab", NamedKey::Tab),
+    ("space", NamedKey::Space),
+    ("backspace", NamedKey::Backspace),
+    ("delete", NamedKey::Delete),
+    ("insert", NamedKey::Insert),
+    ("escape", NamedKey::Escape),
+    ("esc", NamedKey::Escape),
+    ("up", NamedKey::ArrowUp),
+    ("down", NamedKey::ArrowDown),
+    ("left", NamedKey::ArrowLeft),
+    ("right", NamedKey::ArrowRight),
+    ("home", NamedKey::Home),
+    ("end", NamedKey::End),
+    ("pageup", NamedKey::PageUp),
+    ("pagedown", NamedKey::PageDown),
+    ("f1", NamedKey::F1),
+    ("f2", NamedKey::F2),
+    ("f3", NamedKey::F3),
+    ("f4", NamedKey::F4),
+    ("f5", NamedKey::F5),
+    ("f6", NamedKey::F6),
+    ("f7", NamedKey::F7),
+    ("f8", NamedKey::F8),
+    ("f9", NamedKey::F9),
+    ("f10", NamedKey::F10),
+    ("f11", NamedKey::F11),
+    ("f12", NamedKey::F12),
+];
+
+impl FromStr for KeyStroke {
+    type Err = ShortcutParseError;
+
+    /// Parses strokes such as `Ctrl+Shift+P`. `Mod` and `CmdOrCtrl` stand for
+    /// [`primary_modifier`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // `+` itself can be the key, as in `Ctrl++`
+        let (mods_part, key) = match s.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None => match s.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", s),
+            },
+        };
+
+        let mut mods = Modifiers::empty();
+        for part in mods_part.split('+').filter(|part| !part.is_empty()) {
+            mods |= match part.to_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "shift" => Modifiers::SHIFT,
+                "alt" | "option" => Modifiers::ALT,
+                "meta" | "cmd" | "command" | "super" | "win" => Modifiers::META,
+                "mod" | "cmdorctrl" => primary_modifier(),
+                _ => return Err(ShortcutParseError::UnknownModifier(part.to_string())),
+            };
+        }
+
+        let lower = key.to_lowercase();
+        let key = if let Some((_, named)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+            Key::Named(*named)
+        } else if key.chars().count() == 1 {
+            Key::Character(lower.into())
+        } else {
+            return Err(ShortcutParseError::UnknownKey(key.to_string()));
+        };
+        Ok(Self::new(key, mods))
+    }
+}
+
+impl Display for KeyStroke {
+    /// Formats the stroke with the platform's modifier names, e.g. `Cmd+Shift+P` on macOS.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.mods.control() {
+            f.write_str("Ctrl+")?;
+        }
+        if self.mods.alt() {
+            f.write_str(if cfg!(target_os = "macos") {
+                "Option+"
+            } else {
+                "Alt+"
+            })?;
+        }
+        if self.mods.meta() {
+            f.write_str(match std::env::consts::OS {
+                "macos" => "Cmd+",
+                "windows" => "Win+",
+                _ => "Meta+",
+            })?;
+        }
+        if self.mods.shift() {
+            f.write_str("Shift+")?;
+        }
+        match &self.key {
+            Key::Character(c) => f.write_str(&c.to_uppercase()),
+            Key::Named(named) => match NAMED_KEYS.iter().find(|(_, key)| key == named) {
+                Some((name, _)) => match *name {
+                    "pageup" => f.write_str("PageUp"),
+                    "pagedown" => f.write_str("PageDown"),
+                    name => {
+                        let mut chars = name.chars();
+                        let first = chars.next().unwrap_or_default().to_ascii_uppercase();
+                        write!(f, "{first}{}", chars.as_str())
+                    }
+                },
+                None => write!(f, "{named:?}"),
+            },
+            key => write!(f, "{key:?}"),
+        }
+    }
+}
+
+/// A sequence of key strokes that triggers a command, such as `Ctrl+K Ctrl+C`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    strokes: SmallVec<[KeyStroke; 2]>,
+}
+
+impl Shortcut {
+    pub fn strokes(&self) -> &[KeyStroke] {
+        &self.strokes
+    }
+
+    /// Returns `true` if `self` is a shorter sequence that `other` starts with.
+    pub fn is_prefix_of(&self, other: &Shortcut) -> bool {
+        self.strokes.len() < other.strokes.len() && other.strokes.starts_with(&self.strokes)
+    }
+}
+
+impl From<KeyStroke> for Shortcut {
+    fn from(stroke: KeyStroke) -> Self {
+        Self {
+            strokes: SmallVec::from_elem(stroke, 1),
+        }
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ShortcutParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let strokes = s
+            .split_whitespace()
+            .map(KeyStroke::from_str)
+            .collect::<Result<SmallVec<_>, _>>()?;
+        if strokes.is_empty() {
+            return Err(ShortcutParseError::Empty);
+        }
+        Ok(Self { strokes })
+    }
+}
+
+impl Display for Shortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, stroke) in self.strokes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{stroke}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A condition for a binding to be active.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum When {
+    /// True while a focused view is in the scope, or the context is set, with this name.
+    Name(String),
+    Not(Box<When>),
+    And(Box<When>, Box<When>),
+    Or(Box<When>, Box<When>),
+}
+
+impl When {
+    pub fn eval(&self, is_active: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            When::Name(name) => is_active(name),
+            When::Not(when) => !when.eval(is_active),
+            When::And(a, b) => a.eval(is_active) && b.eval(is_active),
+            When::Or(a, b) => a.eval(is_active) || b.eval(is_active),
+        }
+    }
+}
+
+impl FromStr for When {
+    type Err = ShortcutParseError;
+
+    /// Parses conditions made of names, `!`, `&&`, `||` and parentheses.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = WhenParser {
+            tokens: tokenize_when(s)?,
+            pos: 0,
+        };
+        let when = parser.or()?;
+        if parser.pos != parser.tokens.len() {
+            return Err(ShortcutParseError::InvalidCondition(s.to_string()));
+        }
+        Ok(when)
+    }
+}
+
+impl Display for When {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            When::Name(name) => f.write_str(name),
+            When::Not(when) => match when.as_ref() {
+                When::Name(_) | When::Not(_) => write!(f, "!{when}"),
+                _ => write!(f, "!({when})"),
+            },
+            When::And(a, b) => {
+                let operand = |when: &When| match when {
+                    When::Or(..) => format!("({when})"),
+                    _ => when.to_string(),
+                };
+                write!(f, "{} && {}", operand(a), operand(b))
+            }
+            When::Or(a, b) => write!(f, "{a} || {b}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum WhenToken {
+    Name(String),
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+fn tokenize_when(s: &str) -> Result<Vec<WhenToken>, ShortcutParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '!' => tokens.push(WhenToken::Not),
+            '(' => tokens.push(WhenToken::Open),
+            ')' => tokens.push(WhenToken::Close),
+            '&' if chars.next_if_eq(&'&').is_some() => tokens.push(WhenToken::And),
+            '|' if chars.next_if_eq(&'|').is_some() => tokens.push(WhenToken::Or),
+            c if c.is_alphanumeric() || matches!(c, '_' | '.' | '-') => {
+                let mut name = String::from(c);
+                while let Some(c) =
+                    chars.next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '-'))
+                {
+                    name.push(c);
+                }
+                tokens.push(WhenToken::Name(name));
+            }
+            _ => return Err(ShortcutParseError::InvalidCondition(s.to_string())),
+        }
+    }
+    Ok(tokens)
+}
+
+struct WhenParser {
+    tokens: Vec<WhenToken>,
+    pos: usize,
+}
+
+impl WhenParser {
+    fn eat(&mut self, token: &WhenToken) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self) -> ShortcutParseError {
+        ShortcutParseError::InvalidCondition(format!("unexpected token at {}", self.pos))
+    }
+
+    fn or(&mut self) -> Result<When, ShortcutParseError> {
+        let mut when = self.and()?;
+        while self.eat(&WhenToken::Or) {
+            when = When::Or(Box::new(when), Box::new(self.and()?));
+        }
+        Ok(when)
+    }
+
+    fn and(&mut self) -> Result<When, ShortcutParseError> {
+        let mut when = self.unary()?;
+        while self.eat(&WhenToken::And) {
+            when = When::And(Box::new(when), Box::new(self.unary()?));
+        }
+        Ok(when)
+    }
+
+    fn unary(&mut self) -> Result<When, ShortcutParseError> {
+        if self.eat(&WhenToken::Not) {
+            return Ok(When::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&WhenToken::Open) {
+            let when = self.or()?;
+            if !self.eat(&WhenToken::Close) {
+                return Err(self.error());
+            }
+            return Ok(when);
+        }
+        match self.tokens.get(self.pos) {
+            Some(WhenToken::Name(name)) => {
+                self.pos += 1;
+                Ok(When::Name(name.clone()))
+            }
+            _ => Err(self.error()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutParseError {
+    Empty,
+    UnknownKey(String),
+    UnknownModifier(String),
+    InvalidCondition(String),
+}
+
+impl Display for ShortcutParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShortcutParseError::Empty => f.write_str("empty shortcut"),
+            ShortcutParseError::UnknownKey(key) => write!(f, "unknown key `{key}`"),
+            ShortcutParseError::UnknownModifier(m) => write!(f, "unknown modifier `{m}`"),
+            ShortcutParseError::InvalidCondition(c) => write!(f, "invalid condition `{c}`"),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(std::io::Error),
+    /// A line of the keymap couldn't be parsed. Lines are counted from 1.
+    Parse {
+        line: usize,
+        error: ShortcutParseError,
+    },
+    /// A line isn't of the form `<shortcut> = <command> [when <condition>]`.
+    Syntax {
+        line: usize,
+    },
+}
+
+impl Display for KeymapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeymapError::Io(err) => write!(f, "failed to read keymap: {err}"),
+            KeymapError::Parse { line, error } => write!(f, "line {line}: {error}"),
+            KeymapError::Syntax { line } => {
+                write!(f, "line {line}: expected `<shortcut> = <command>`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+/// A command that can be bound to shortcuts.
+pub struct Command {
+    id: String,
+    title: String,
+    bindings: Vec<(Shortcut, Option<When>)>,
+    action: Rc<dyn Fn()>,
+}
+
+impl Command {
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        action: impl Fn() + 'static,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            bindings: Vec::new(),
+            action: Rc::new(action),
+        }
+    }
+
+    /// Adds a default binding that is always active.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shortcut` can't be parsed.
+    pub fn binding(mut self, shortcut: &str) -> Self {
+        self.bindings.push((shortcut.parse().unwrap(), None));
+        self
+    }
+
+    /// Adds a default binding that is active while `when` is true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shortcut` or `when` can't be parsed.
+    pub fn binding_when(mut self, shortcut: &str, when: &str) -> Self {
+        self.bindings
+            .push((shortcut.parse().unwrap(), Some(when.parse().unwrap())));
+        self
+    }
+
+    /// Adds a default binding from an already built shortcut and condition.
+    pub fn shortcut_binding(mut self, shortcut: Shortcut, when: Option<When>) -> Self {
+        self.bindings.push((shortcut, when));
+        self
+    }
+}
+
+/// Two bindings where one hides the other, because their shortcuts are equal or one is the start
+/// of the other, and both can be active at the same time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    pub shortcut: Shortcut,
+    pub command: String,
+    pub other_shortcut: Shortcut,
+    pub other_command: String,
+}
+
+#[derive(Clone)]
+struct Binding {
+    shortcut: Shortcut,
+    command: String,
+    when: Option<When>,
+}
+
+struct CommandEntry {
+    title: String,
+    action: Rc<dyn Fn()>,
+}
+
+#[derive(Default)]
+struct Registry {
+    commands: IndexMap<String, CommandEntry>,
+    defaults: Vec<Binding>,
+    /// Bindings from the user's keymap, which take precedence over the defaults.
+    user: Vec<Binding>,
+    /// Default bindings the user's keymap removed.
+    removed: Vec<(Shortcut, String)>,
+    contexts: HashSet<String>,
+    /// The strokes of a chord typed so far.
+    pending: Vec<KeyStroke>,
+}
+
+enum Match {
+    Command(String),
+    /// The index of a matching accelerator.
+    Accelerator(usize),
+    Pending,
+    None,
+}
+
+/// What a key down event was used for.
+pub(crate) enum KeyDown {
+    /// It ran a command or is part of a chord.
+    Handled,
+    /// It completed the accelerator with this index.
+    Accelerator(usize),
+    Unhandled,
+}
+
+impl Registry {
+    /// The effective bindings, with the highest precedence first.
+    fn bindings(&self) -> impl Iterator<Item = &Binding> {
+        self.user
+            .iter()
+            .rev()
+            .chain(self.defaults.iter().rev().filter(|binding| {
+                !self.removed.iter().any(|(shortcut, command)| {
+                    *shortcut == binding.shortcut && *command == binding.command
+                })
+            }))
+    }
+
+    /// Matches the next stroke. `claimed` is whether the focused view uses the stroke itself, in
+    /// which case only bindings with a condition can start a shortcut with it.
+    ///
+    /// `accelerators` are the shortcuts of menu items, which share the chord state with the
+    /// bindings but have lower precedence.
+    fn stroke(
+        &mut self,
+        stroke: KeyStroke,
+        claimed: bool,
+        is_active: &impl Fn(&str) -> bool,
+        accelerators: &[&Shortcut],
+    ) -> Match {
+        let had_pending = !self.pending.is_empty();
+        let skip_unscoped = claimed && !had_pending;
+        self.pending.push(stroke);
+        let mut exact = None;
+        let mut is_prefix = false;
+        for binding in self.bindings() {
+            if skip_unscoped && binding.when.is_none() {
+                continue;
+            }
+            if !binding
+                .when
+                .as_ref()
+                .map_or(true, |when| when.eval(is_active))
+            {
+                continue;
+            }
+            let strokes = binding.shortcut.strokes();
+            if strokes == self.pending.as_slice() {
+                exact = exact.or_else(|| Some(Match::Command(binding.command.clone())));
+            } else if strokes.starts_with(&self.pending) {
+                is_prefix = true;
+            }
+        }
+        if !skip_unscoped {
+            for (i, accelerator) in accelerators.iter().enumerate() {
+                let strokes = accelerator.strokes();
+                if strokes == self.pending.as_slice() {
+                    exact = exact.or(Some(Match::Accelerator(i)));
+                } else if strokes.starts_with(&self.pending) {
+                    is_prefix = true;
+                }
+            }
+        }
+        if is_prefix {
+            return Match::Pending;
+        }
+        self.pending.clear();
+        match exact {
+            Some(matched) => matched,
+            // the rest of an unknown chord is swallowed
+            None if had_pending => Match::Pending,
+            None => Match::None,
+        }
+    }
+
+    fn load_keymap(&mut self, keymap: &str) -> Result<(), KeymapError> {
+        let mut user = Vec::new();
+        let mut removed = Vec::new();
+        for (i, line) in keymap.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = |error| KeymapError::Parse {
+                line: line_number,
+                error,
+            };
+            // `=` can be the key of the shortcut, so split at the last one before the command
+            let (shortcut, rest) = line
+                .rsplit_once(" = ")
+                .or_else(|| line.rsplit_once('='))
+                .ok_or(KeymapError::Syntax { line: line_number })?;
+            let shortcut: Shortcut = shortcut.parse().map_err(parse_error)?;
+            let (command, when) = match rest.split_once(" when ") {
+                Some((command, when)) => (command.trim(), Some(when.parse().map_err(parse_error)?)),
+                None => (rest.trim(), None),
+            };
+            if command.is_empty() {
+                return Err(KeymapError::Syntax { line: line_number });
+            }
+            match command.strip_prefix('-') {
+                Some(command) => removed.push((shortcut, command.to_string())),
+                None => user.push(Binding {
+                    shortcut,
+                    command: command.to_string(),
+                    when,
+                }),
+            }
+        }
+        self.user = user;
+        self.removed = removed;
+        Ok(())
+    }
+
+    fn shortcuts_for(&self, command: &str) -> Vec<Shortcut> {
+        self.bindings()
+            .filter(|binding| binding.command == command)
+            .map(|binding| binding.shortcut.clone())
+            .collect()
+    }
+
+    fn conflicts(&self) -> Vec<ShortcutConflict> {
+        let bindings: Vec<&Binding> = self.bindings().collect();
+        let mut conflicts = Vec::new();
+        for (i, a) in bindings.iter().enumerate() {
+            for b in &bindings[i + 1..] {
+                let overlaps = a.shortcut == b.shortcut
+                    || a.shortcut.is_prefix_of(&b.shortcut)
+                    || b.shortcut.is_prefix_of(&a.shortcut);
+                // conditions can only be compared syntactically
+                let both_active = a.when.is_none() || b.when.is_none() || a.when == b.when;
+                if overlaps && both_active && a.command != b.command {
+                    conflicts.push(ShortcutConflict {
+                        shortcut: a.shortcut.clone(),
+                        command: a.command.clone(),
+                        other_shortcut: b.shortcut.clone(),
+                        other_command: b.command.clone(),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+/// Registers a command and its default bindings, replacing a command with the same id.
+pub fn register_command(command: Command) {
+    REGISTRY.with_borrow_mut(|registry| {
+        registry
+            .defaults
+            .retain(|binding| binding.command != command.id);
+        registry.defaults.extend(
+            command
+                .bindings
+                .into_iter()
+                .map(|(shortcut, when)| Binding {
+                    shortcut,
+                    command: command.id.clone(),
+                    when,
+                }),
+        );
+        registry.commands.insert(
+            command.id,
+            CommandEntry {
+                title: command.title,
+                action: command.action,
+            },
+        );
+    });
+}
+
+pub fn unregister_command(id: &str) {
+    REGISTRY.with_borrow_mut(|registry| {
+        registry.commands.shift_remove(id);
+        registry.defaults.retain(|binding| binding.command != id);
+    });
+}
+
+/// Runs the command with the given id. Returns `false` if there is no such command.
+pub fn execute_command(id: &str) -> bool {
+    let action =
+        REGISTRY.with_borrow(|registry| registry.commands.get(id).map(|c| c.action.clone()));
+    match action {
+        Some(action) => {
+            action();
+            true
+        }
+        None => false,
+    }
+}
+
+/// The title of a registered command.
+pub fn command_title(id: &str) -> Option<String> {
+    REGISTRY.with_borrow(|registry| registry.commands.get(id).map(|c| c.title.clone()))
+}
+
+/// The ids of all registered commands, in registration order.
+pub fn commands() -> Vec<String> {
+    REGISTRY.with_borrow(|registry| registry.commands.keys().cloned().collect())
+}
+
+/// Enables or disables a context name used in [`When`] conditions.
+pub fn set_context(name: impl Into<String>, enabled: bool) {
+    let name = name.into();
+    REGISTRY.with_borrow_mut(|registry| {
+        if enabled {
+            registry.contexts.insert(name);
+        } else {
+            registry.contexts.remove(&name);
+        }
+    });
+}
+
+/// Whether `stroke` starts an effective binding of a command whose id starts with `prefix`.
+pub(crate) fn starts_binding(stroke: &KeyStroke, prefix: &str) -> bool {
+    REGISTRY.with_borrow(|registry| {
+        registry.bindings().any(|binding| {
+            binding.command.starts_with(prefix)
+                && binding.shortcut.strokes().first() == Some(stroke)
+        })
+    })
+}
+
+/// Adds a user binding that takes precedence over the default bindings.
+pub fn bind(shortcut: Shortcut, command: impl Into<String>, when: Option<When>) {
+    REGISTRY.with_borrow_mut(|registry| {
+        registry.user.push(Binding {
+            shortcut,
+            command: command.into(),
+            when,
+        })
+    });
+}
+
+/// Replaces the user bindings with the ones in `keymap`. See the [module docs](self) for the
+/// format. Nothing is changed if the keymap has an error.
+pub fn load_keymap(keymap: &str) -> Result<(), KeymapError> {
+    REGISTRY.with_borrow_mut(|registry| registry.load_keymap(keymap))
+}
+
+/// Reads a keymap file and loads it with [`load_keymap`].
+pub fn load_keymap_file(path: impl AsRef<Path>) -> Result<(), KeymapError> {
+    let keymap = std::fs::read_to_string(path).map_err(KeymapError::Io)?;
+    load_keymap(&keymap)
+}
+
+/// The shortcut that runs a command, for displaying it in a menu or tooltip.
+///
+/// This is the binding with the highest precedence, regardless of its condition.
+pub fn shortcut_for(command: &str) -> Option<Shortcut> {
+    shortcuts_for(command).into_iter().next()
+}
+
+/// All shortcuts bound to a command, with the highest precedence first.
+pub fn shortcuts_for(command: &str) -> Vec<Shortcut> {
+    REGISTRY.with_borrow(|registry| registry.shortcuts_for(command))
+}
+
+/// All pairs of bindings that hide each other.
+pub fn conflicts() -> Vec<ShortcutConflict> {
+    REGISTRY.with_borrow(|registry| registry.conflicts())
+}
+
+/// Matches a key down event against the bindings and then the menu `accelerators`, running the
+/// matching command. Chords work for both.
+///
+/// Keys the focused view claims, like typing in a text input, only start shortcuts with a
+/// condition, so a keymap can still override them with e.g. `when editor`.
+pub(crate) fn handle_key_down(
+    event: &KeyEvent,
+    focus: Option<ViewId>,
+    accelerators: &[&Shortcut],
+) -> KeyDown {
+    let Some(stroke) = KeyStroke::from_event(event) else {
+        return KeyDown::Unhandled;
+    };
+    let claimed = focus
+        .and_then(|focus| focus.state().borrow().claims_key.clone())
+        .is_some_and(|claims_key| claims_key(event));
+    let mut scopes: HashSet<String> = HashSet::new();
+    if let Some(focus) = focus {
+        for id in std::iter::once(focus).chain(focus.ancestors()) {
+            scopes.extend(id.state().borrow().shortcut_scopes.iter().cloned());
+        }
+    }
+    let matched = REGISTRY.with_borrow_mut(|registry| {
+        let contexts = std::mem::take(&mut registry.contexts);
+        let matched = registry.stroke(
+            stroke,
+            claimed,
+            &|name: &str| scopes.contains(name) || contexts.contains(name),
+            accelerators,
+        );
+        registry.contexts = contexts;
+        matched
+    });
+    match matched {
+        Match::Command(command) => {
+            execute_command(&command);
+            KeyDown::Handled
+        }
+        Match::Accelerator(i) => KeyDown::Accelerator(i),
+        Match::Pending => KeyDown::Handled,
+        Match::None => KeyDown::Unhandled,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke(s: &str) -> KeyStroke {
+        s.parse().unwrap()
+    }
+
+    fn registry(bindings: &[(&str, &str, Option<&str>)]) -> Registry {
+        let mut registry = Registry::default();
+        for (shortcut, command, when) in bindings {
+            registry.defaults.push(Binding {
+                shortcut: shortcut.parse().unwrap(),
+                command: command.to_string(),
+                when: when.map(|when| when.parse().unwrap()),
+            });
+        }
+        registry
+    }
+
+    #[test]
+    fn parse_shortcut() {
+        let shortcut: Shortcut = "Ctrl+K ctrl+shift+c".parse().unwrap();
+        assert_eq!(shortcut.strokes().len(), 2);
+        assert_eq!(
+            shortcut.strokes()[1],
+            KeyStroke::new(
+                Key::Character("c".into()),
+                Modifiers::CONTROL | Modifiers::SHIFT
+            )
+        );
+        assert_eq!(
+            stroke("ctrl++"),
+            KeyStroke::new(Key::Character("+".into()), Modifiers::CONTROL)
+        );
+        assert_eq!(
+            stroke("PageDown"),
+            KeyStroke::new(Key::Named(NamedKey::PageDown), Modifiers::empty())
+        );
+        assert!("ctrl+nope".parse::<Shortcut>().is_err());
+        assert!("hyper+a".parse::<Shortcut>().is_err());
+    }
+
+    #[test]
+    fn parse_when() {
+        let when: When = "editor && !(readOnly || empty)".parse().unwrap();
+        assert!(when.eval(&|name| name == "editor"));
+        assert!(!when.eval(&|name| name == "editor" || name == "empty"));
+        assert!("editor &&".parse::<When>().is_err());
+    }
+
+    #[test]
+    fn chords() {
+        let mut registry =
+            registry(&[("ctrl+k ctrl+c", "comment", None), ("ctrl+s", "save", None)]);
+        let no_scope = |_: &str| false;
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+k"), false, &no_scope, &[]),
+            Match::Pending
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+c"), false, &no_scope, &[]),
+            Match::Command(command) if command == "comment"
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+s"), false, &no_scope, &[]),
+            Match::Command(command) if command == "save"
+        ));
+        // an unknown second stroke ends the chord
+        registry.stroke(stroke("ctrl+k"), false, &no_scope, &[]);
+        assert!(matches!(
+            registry.stroke(stroke("x"), false, &no_scope, &[]),
+            Match::Pending
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("x"), false, &no_scope, &[]),
+            Match::None
+        ));
+    }
+
+    #[test]
+    fn scopes_and_overrides() {
+        let mut registry = registry(&[
+            ("ctrl+d", "delete", Some("editor")),
+            ("ctrl+d", "bookmark", None),
+        ]);
+        let editor = |name: &str| name == "editor";
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+d"), false, &editor, &[]),
+            Match::Command(command) if command == "bookmark"
+        ));
+        registry
+            .removed
+            .push(("ctrl+d".parse().unwrap(), "bookmark".into()));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+d"), false, &editor, &[]),
+            Match::Command(command) if command == "delete"
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+d"), false, &|_| false, &[]),
+            Match::None
+        ));
+    }
+
+    #[test]
+    fn detect_conflicts() {
+        let registry = registry(&[
+            ("ctrl+k", "kill", None),
+            ("ctrl+k ctrl+c", "comment", Some("editor")),
+            ("ctrl+s", "save", Some("editor")),
+            ("ctrl+s", "submit", Some("form")),
+        ]);
+        let conflicts = registry.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].command, "comment");
+        assert_eq!(conflicts[0].other_command, "kill");
+    }
+
+    #[test]
+    fn claimed_keys() {
+        let mut registry = registry(&[
+            ("a", "archive", None),
+            ("ctrl+k a", "align", None),
+            ("ctrl+d", "duplicate", Some("editor")),
+        ]);
+        let editor = |name: &str| name == "editor";
+        assert!(matches!(
+            registry.stroke(stroke("a"), true, &editor, &[]),
+            Match::None
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("a"), false, &editor, &[]),
+            Match::Command(command) if command == "archive"
+        ));
+        // a claimed key still finishes a chord
+        registry.stroke(stroke("ctrl+k"), false, &editor, &[]);
+        assert!(matches!(
+            registry.stroke(stroke("a"), true, &editor, &[]),
+            Match::Command(command) if command == "align"
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+d"), true, &editor, &[]),
+            Match::Command(command) if command == "duplicate"
+        ));
+    }
+
+    #[test]
+    fn accelerators() {
+        let mut registry = registry(&[("ctrl+s", "save", None)]);
+        let chord: Shortcut = "ctrl+k ctrl+o".parse().unwrap();
+        let save: Shortcut = "ctrl+s".parse().unwrap();
+        let print: Shortcut = "ctrl+p".parse().unwrap();
+        let accelerators = [&chord, &save, &print];
+        let no_scope = |_: &str| false;
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+k"), false, &no_scope, &accelerators),
+            Match::Pending
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+o"), false, &no_scope, &accelerators),
+            Match::Accelerator(0)
+        ));
+        // bindings take precedence
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+s"), false, &no_scope, &accelerators),
+            Match::Command(command) if command == "save"
+        ));
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+p"), false, &no_scope, &accelerators),
+            Match::Accelerator(2)
+        ));
+    }
+
+    #[test]
+    fn keymap() {
+        let mut registry = registry(&[("ctrl+s", "save", None), ("ctrl+o", "open", None)]);
+        assert!(registry
+            .load_keymap("ctrl+b = sidebar.toggle when !editor\nctrl+s = -save")
+            .is_ok());
+        assert!(registry.shortcuts_for("save").is_empty());
+        assert_eq!(
+            registry.shortcuts_for("open"),
+            ["ctrl+o".parse::<Shortcut>().unwrap()]
+        );
+        assert!(matches!(
+            registry.stroke(stroke("ctrl+b"), false, &|name| name == "editor", &[]),
+            Match::None
+        ));
+        assert!(matches!(
+            registry.load_keymap("# comment\nctrl+b sidebar.toggle"),
+            Err(KeymapError::Syntax { line: 2 })
+        ));
+        // a failed load keeps the previous keymap
+        assert_eq!(
+            registry.shortcuts_for("sidebar.toggle"),
+            ["ctrl+b".parse::<Shortcut>().unwrap()]
+        );
+        assert!(matches!(registry.load_keymap("ctrl+= = zoom.in"), Ok(())));
+        assert_eq!(
+            registry.shortcuts_for("zoom.in"),
+            ["ctrl+=".parse::<Shortcut>().unwrap()]
+        );
+        assert_eq!(
+            registry.shortcuts_for("save"),
+            ["ctrl+s".parse::<Shortcut>().unwrap()]
+        );
+    }
+}
@@ -46,33 +46,58 @@ pub trait StylePropValue: Clone + PartialEq + Debug {
     fn interpolate(&self, _other: &Self, _value: f64) -> Option<Self> {
         None
     }
+
+    /// Parses a value from user input, such as a value edited in the inspector.
+    /// Returns `None` if the text isn't valid or the type doesn't support parsing.
+    fn parse(_text: &str) -> Option<Self> {
+        None
+    }
 }
 
 impl StylePropValue for i32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as i32)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+impl StylePropValue for bool {
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
-impl StylePropValue for bool {}
 impl StylePropValue for f32 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value as f32) + *other * value as f32)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for u16 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as u16)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for usize {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some((*self as f64 + (*other as f64 - *self as f64) * value).round() as usize)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for f64 {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         Some(*self * (1.0 - value) + *other * value)
     }
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
 }
 impl StylePropValue for Display {}
 impl StylePropValue for Position {}
@@ -101,7 +126,11 @@ impl StylePropValue for BoxShadow {
         })
     }
 }
-impl StylePropValue for String {}
+impl StylePropValue for String {
+    fn parse(text: &str) -> Option<Self> {
+        Some(text.to_string())
+    }
+}
 impl StylePropValue for Weight {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Weight)
@@ -136,6 +165,13 @@ impl<T: StylePropValue> StylePropValue for Option<T> {
                 .and_then(|other| this.interpolate(other, value).map(Some))
         })
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim() {
+            "" | "None" | "none" => Some(None),
+            text => T::parse(text).map(Some),
+        }
+    }
 }
 impl<T: StylePropValue> StylePropValue for Vec<T> {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -163,6 +199,9 @@ impl StylePropValue for Px {
     fn interpolate(&self, other: &Self, value: f64) -> Option<Self> {
         self.0.interpolate(&other.0, value).map(Px)
     }
+    fn parse(text: &str) -> Option<Self> {
+        parse_px(text).map(Px)
+    }
 }
 impl StylePropValue for PxPctAuto {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -182,6 +221,16 @@ impl StylePropValue for PxPctAuto {
             _ => None,
         }
     }
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("auto") {
+            Some(Self::Auto)
+        } else if let Some(pct) = text.strip_suffix('%') {
+            pct.trim().parse().ok().map(Self::Pct)
+        } else {
+            parse_px(text).map(Self::Px)
+        }
+    }
 }
 impl StylePropValue for PxPct {
     fn debug_view(&self) -> Option<Box<dyn View>> {
@@ -200,7 +249,49 @@ impl StylePropValue for PxPct {
             _ => None,
         }
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some(pct) = text.strip_suffix('%') {
+            pct.trim().parse().ok().map(Self::Pct)
+        } else {
+            parse_px(text).map(Self::Px)
+        }
+    }
+}
+
+/// Parses a pixel value such as `10`, `10.5` or `10px`.
+fn parse_px(text: &str) -> Option<f64> {
+    let text = text.trim();
+    text.strip_suffix("px").unwrap_or(text).trim().parse().ok()
+}
+
+/// Parses a CSS style hex color in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` formats.
+fn parse_hex_color(text: &str) -> Option<Color> {
+    let hex = text.trim().strip_prefix('#')?;
+    // `from_str_radix` would accept a leading sign
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+    let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    match hex.len() {
+        3 | 4 => Some(Color::rgba8(
+            digit(0)? * 17,
+            digit(1)? * 17,
+            digit(2)? * 17,
+            if hex.len() == 4 { digit(3)? * 17 } else { 255 },
+        )),
+        6 | 8 => Some(Color::rgba8(
+            byte(0)?,
+            byte(2)?,
+            byte(4)?,
+            if hex.len() == 8 { byte(6)? } else { 255 },
+        )),
+        _ => None,
+    }
 }
+
 impl StylePropValue for Color {
     fn debug_view(&self) -> Option<Box<dyn View>> {
         let color = *self;
@@ -240,6 +331,10 @@ impl StylePropValue for Color {
             .round() as u8;
         Some(Color { r, g, b, a })
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        parse_hex_color(text)
+    }
 }
 
 impl StylePropValue for Gradient {
@@ -424,6 +519,10 @@ impl StylePropValue for Brush {
             _ => None,
         }
     }
+
+    fn parse(text: &str) -> Option<Self> {
+        <Color as StylePropValue>::parse(text).map(Brush::Solid)
+    }
 }
 
 pub trait StyleClass: Default + Copy + 'static {
@@ -497,6 +596,7 @@ pub struct StylePropInfo {
     pub(crate) interpolate: InterpolateFn,
     pub(crate) debug_any: fn(val: &dyn Any) -> String,
     pub(crate) debug_view: fn(val: &dyn Any) -> Option<Box<dyn View>>,
+    pub(crate) parse: fn(text: &str) -> Option<Rc<dyn Any>>,
     pub(crate) transition_key: StyleKey,
 }
 
@@ -564,6 +664,7 @@ impl StylePropInfo {
                     )
                 }
             },
+            parse: |text| T::parse(text).map(|v| Rc::new(StyleMapValue::Val(v)) as Rc<dyn Any>),
             transition_key,
         }
     }
@@ -2335,7 +2436,7 @@ pub trait CustomStylable<S: Default + Into<Style> + 'static>:
 
 #[cfg(test)]
 mod tests {
-    use super::{Style, StyleValue};
+    use super::{parse_hex_color, Style, StylePropValue, StyleValue};
     use crate::{
         style::{PaddingBottom, PaddingLeft},
         unit::PxPct,
@@ -2414,4 +2515,55 @@ mod tests {
             StyleValue::Val(PxPct::Px(100.0))
         );
     }
+
+    #[test]
+    fn parse_hex_colors() {
+        use peniko::Color;
+
+        assert_eq!(
+            parse_hex_color("#f80"),
+            Some(Color::rgba8(255, 136, 0, 255))
+        );
+        assert_eq!(
+            parse_hex_color("#f808"),
+            Some(Color::rgba8(255, 136, 0, 136))
+        );
+        assert_eq!(
+            parse_hex_color("#12ab3c"),
+            Some(Color::rgba8(0x12, 0xab, 0x3c, 255))
+        );
+        assert_eq!(
+            parse_hex_color(" #12AB3C80 "),
+            Some(Color::rgba8(0x12, 0xab, 0x3c, 0x80))
+        );
+
+        assert_eq!(parse_hex_color("12ab3c"), None);
+        assert_eq!(parse_hex_color("#12ab3"), None);
+        assert_eq!(parse_hex_color("#12ab3g"), None);
+        assert_eq!(parse_hex_color("#+f+f+f"), None);
+        assert_eq!(parse_hex_color("#"), None);
+        assert_eq!(parse_hex_color("#éé"), None);
+    }
+
+    #[test]
+    fn parse_lengths() {
+        use crate::unit::{Px, PxPctAuto};
+
+        assert_eq!(Px::parse("10"), Some(Px(10.0)));
+        assert_eq!(Px::parse(" 10.5px "), Some(Px(10.5)));
+        assert_eq!(Px::parse("10%"), None);
+
+        assert_eq!(PxPct::parse("12px"), Some(PxPct::Px(12.0)));
+        assert_eq!(PxPct::parse("50 %"), Some(PxPct::Pct(50.0)));
+        assert_eq!(PxPct::parse("auto"), None);
+
+        assert_eq!(PxPctAuto::parse("Auto"), Some(PxPctAuto::Auto));
+        assert_eq!(PxPctAuto::parse("25%"), Some(PxPctAuto::Pct(25.0)));
+        assert_eq!(PxPctAuto::parse("3"), Some(PxPctAuto::Px(3.0)));
+        assert_eq!(PxPctAuto::parse("wide"), None);
+
+        assert_eq!(<Option<f64>>::parse("none"), Some(None));
+        assert_eq!(<Option<f64>>::parse("1.5"), Some(Some(1.5)));
+        assert_eq!(i32::parse("x"), None);
+    }
 }
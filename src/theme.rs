@@ -5,10 +5,17 @@ use crate::{
         dropdown::{self},
         scroll,
         slider::{self, SliderClass},
-        ButtonClass, CheckboxClass, LabelClass, LabelCustomStyle, LabeledCheckboxClass,
-        LabeledRadioButtonClass, ListClass, ListItemClass, PlaceholderTextClass, RadioButtonClass,
-        RadioButtonDotClass, TextInputClass, ToggleButtonCircleRad, ToggleButtonClass,
-        ToggleButtonInset, TooltipClass,
+        ButtonClass, CheckboxClass, FieldErrorClass, FormErrorClass, LabelClass, LabelCustomStyle,
+        LabeledCheckboxClass, LabeledRadioButtonClass, ListClass, ListItemClass,
+        MenuAcceleratorClass, MenuBarClass, MenuBarItemClass, MenuClass, MenuItemClass,
+        MenuSeparatorClass, NumberInputButtonClass, NumberInputInvalidClass, PlaceholderTextClass,
+        PopoverBackdropClass, PopoverClass, RadioButtonClass, RadioButtonDotClass,
+        RevealToggleClass, SubMenuClass, TabBarClass, TabCloseClass, TabHeaderClass,
+        TabOverflowClass, TabOverflowItemClass, TableCellClass, TableCellEditorClass, TableClass,
+        TableFrozenClass, TableHeaderCellClass, TableHeaderClass, TableResizeHandleClass,
+        TableRowClass, TextAreaClass, TextInputClass, ToggleButtonCircleRad, ToggleButtonClass,
+        ToggleButtonInset, TooltipClass, TreeExpanderClass, TreeGuideColor, TreeRowClass,
+        TreeViewClass,
     },
 };
 use peniko::{Brush, Color};
@@ -203,6 +210,10 @@ pub(crate) fn default_theme() -> Theme {
                 .color(Color::GRAY)
         });
 
+    let invalid_style = Style::new()
+        .border_color(Color::RED)
+        .focus(|s| s.border_color(Color::RED));
+
     let item_focused_style = Style::new().selected(|s| {
         s.background(selected_bg_color)
             .hover(|s| s.background(selected_hover_bg_color))
@@ -225,7 +236,26 @@ pub(crate) fn default_theme() -> Theme {
         .class(RadioButtonClass, |_| radio_button_style)
         .class(RadioButtonDotClass, |_| radio_button_dot_style)
         .class(LabeledRadioButtonClass, |_| labeled_radio_button_style)
+        .class(TextAreaClass, |_| input_style.clone())
         .class(TextInputClass, |_| input_style)
+        .class(FormErrorClass, |s| {
+            s.color(Color::RED).font_size(FONT_SIZE * 0.9)
+        })
+        .class(NumberInputButtonClass, |s| {
+            s.size(16.0, 12.0)
+                .border_radius(3.0)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(RevealToggleClass, |s| {
+            s.size(18.0, 18.0)
+                .padding(1.0)
+                .border_radius(3.0)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(NumberInputInvalidClass, |_| invalid_style.clone())
+        .class(FieldErrorClass, |_| invalid_style)
         .class(ButtonClass, |_| button_style)
         .apply_custom(
             scroll::ScrollCustomStyle::new()
@@ -273,6 +303,87 @@ pub(crate) fn default_theme() -> Theme {
                 .box_shadow_v_offset(2.0)
                 .box_shadow_color(Color::BLACK.with_alpha_factor(0.2))
         })
+        .class(PopoverClass, |s| {
+            s.border(1.0)
+                .border_color(border)
+                .border_radius(border_radius)
+                .padding(padding * 2.0)
+                .background(Color::WHITE_SMOKE)
+                .box_shadow_blur(4.0)
+                .box_shadow_v_offset(2.0)
+                .box_shadow_color(Color::BLACK.with_alpha_factor(0.2))
+        })
+        .class(PopoverBackdropClass, |s| {
+            s.background(Color::BLACK.with_alpha_factor(0.3))
+        })
+        .class(TabBarClass, |s| s.border_bottom(1.0).border_color(border))
+        .class(TabHeaderClass, |s| {
+            s.items_center()
+                .column_gap(padding)
+                .padding_horiz(padding * 2.0)
+                .height(30.0)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+                .selected(|s| s.background(selected_bg_color))
+                .apply(focus_style.clone())
+        })
+        .class(TabCloseClass, |s| {
+            s.size(14.0, 14.0)
+                .border_radius(3.0)
+                .hover(|s| s.background(Color::BLACK.with_alpha_factor(0.1)))
+        })
+        .class(TabOverflowClass, |s| {
+            s.size(20.0, 20.0)
+                .padding(2.0)
+                .border_radius(3.0)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(TabOverflowItemClass, |s| {
+            s.padding(padding)
+                .border_radius(3.0)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(TableClass, |s| s.apply(border_style.clone()))
+        .class(TableHeaderClass, |s| {
+            s.border_bottom(1.0)
+                .border_color(border)
+                .background(Color::WHITE_SMOKE)
+        })
+        .class(TableHeaderCellClass, |s| {
+            s.items_center()
+                .column_gap(padding)
+                .padding_horiz(padding)
+                .height(28.0)
+                .background(Color::WHITE_SMOKE)
+                .border_right(1.0)
+                .border_color(border)
+                .cursor(CursorStyle::Pointer)
+                .hover(|s| s.background(hover_bg_color))
+        })
+        .class(TableResizeHandleClass, |s| {
+            s.width(5.0).cursor(CursorStyle::ColResize)
+        })
+        .class(TableRowClass, |s| s.border_bottom(1.0).border_color(border))
+        .class(TableCellClass, |s| {
+            s.padding_horiz(padding)
+                .border_right(1.0)
+                .border_color(border)
+                .selected(|s| s.background(selected_bg_color))
+        })
+        .class(TableCellEditorClass, |s| s.padding_horiz(2.0).border(0.0))
+        .class(TableFrozenClass, |s| s.background(Color::WHITE))
+        .class(TreeViewClass, |s| {
+            s.set(TreeGuideColor, Some(Color::BLACK.with_alpha_factor(0.1)))
+        })
+        .class(TreeRowClass, |s| {
+            s.column_gap(2.0)
+                .padding_right(padding)
+                .hover(|s| s.background(hover_bg_color))
+                .selected(|s| s.background(selected_bg_color))
+        })
+        .class(TreeExpanderClass, |s| s.size(16.0, 16.0).flex_shrink(0.0))
         .class(dropdown::DropdownClass, |s| {
             s.width(75)
                 .padding(3)
@@ -293,6 +404,44 @@ pub(crate) fn default_theme() -> Theme {
                         })
                 })
         })
+        .class(MenuClass, |s| {
+            s.min_width(200.0)
+                .border_radius(10.0)
+                .background(Color::rgb8(44, 44, 44))
+                .color(Color::rgb8(201, 201, 201))
+                .line_height(2.0)
+                .padding(5.0)
+                .box_shadow_blur(5.0)
+                .box_shadow_color(Color::BLACK)
+        })
+        // A submenu opens next to its item, so move it past the padding of the menu and line up
+        // its first item with the item it belongs to.
+        .class(SubMenuClass, |s| s.margin_left(5.0).margin_top(-5.0))
+        .class(MenuItemClass, |s| {
+            s.padding_horiz(12.0)
+                .border_radius(10.0)
+                .selected(|s| s.background(Color::rgb8(65, 65, 65)))
+                .active(|s| s.background(Color::rgb8(92, 92, 92)))
+                .disabled(|s| s.color(Color::rgb8(92, 92, 92)))
+        })
+        .class(MenuSeparatorClass, |s| {
+            s.height(1.0)
+                .margin_vert(5.0)
+                .margin_horiz(12.0)
+                .background(Color::rgb8(92, 92, 92))
+        })
+        .class(MenuAcceleratorClass, |s| {
+            s.margin_left(30.0).color(Color::rgb8(140, 140, 140))
+        })
+        .class(MenuBarClass, |s| s.padding_horiz(padding))
+        .class(MenuBarItemClass, |s| {
+            s.padding_horiz(8.0)
+                .padding_vert(3.0)
+                .border_radius(border_radius)
+                .hover(|s| s.background(hover_bg_color))
+                .selected(|s| s.background(selected_bg_color))
+                .disabled(|s| s.color(Color::GRAY))
+        })
         .font_size(FONT_SIZE)
         .color(Color::BLACK);
 
@@ -0,0 +1,739 @@
+//! # Touch
+//!
+//! Touch events with per-touch ids, and the gesture events recognized from them.
+//!
+//! Views receive the raw touches as [`Event::TouchStart`], [`Event::TouchMove`],
+//! [`Event::TouchEnd`] and [`Event::TouchCancel`]. From those, tap, long press, pan, swipe,
+//! pinch and rotate gestures are recognized and sent to the view under the point where the
+//! gesture started. Pinch and rotate gestures are also produced by touchpads.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use peniko::kurbo::{Point, Vec2};
+
+use crate::{
+    event::Event,
+    keyboard::Modifiers,
+    pointer::{PointerId, PointerType},
+};
+
+/// How far a touch can move, in logical pixels, before it's no longer a tap or long press.
+const TOUCH_SLOP: f64 = 10.0;
+/// How long a touch has to be held without moving to be a long press.
+pub(crate) const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// The maximum time between two taps for them to be counted as a multi-tap.
+const MULTI_TAP_INTERVAL: Duration = Duration::from_millis(300);
+/// The minimum speed, in logical pixels per second, at the end of a pan for it to be a swipe.
+const SWIPE_VELOCITY: f64 = 600.0;
+/// How long after a touch mouse events are taken to be emulated from it by the platform.
+const EMULATED_MOUSE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone)]
+pub struct TouchEvent {
+    /// Identifies the touch, and is the same for all events of one finger until it's lifted.
+    pub id: u64,
+    pub pos: Point,
+    /// The pressure of the touch from `0.0` to `1.0`, if the device reports it.
+    pub force: Option<f64>,
+    /// This is [`PointerType::Pen`] if the touch is known to be from a pen,
+    /// and [`PointerType::Touch`] otherwise.
+    pub pointer_type: PointerType,
+    /// The angle between a pen and the surface in radians, where `PI / 2` is perpendicular,
+    /// if the device reports it.
+    pub tilt: Option<f64>,
+    pub modifiers: Modifiers,
+}
+
+impl TouchEvent {
+    pub fn pointer_id(&self) -> PointerId {
+        PointerId::Touch(self.id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+    /// The gesture was interrupted, for example because the touches were cancelled by the system.
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub struct TapEvent {
+    pub pos: Point,
+    /// The number of taps in quick succession, which is `2` for a double tap.
+    pub count: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct LongPressEvent {
+    pub pos: Point,
+}
+
+#[derive(Debug, Clone)]
+pub struct PanEvent {
+    /// Where the gesture started.
+    pub origin: Point,
+    pub pos: Point,
+    /// The movement since the last pan event.
+    pub delta: Vec2,
+    /// The movement since the gesture started.
+    pub translation: Vec2,
+    pub phase: GesturePhase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone)]
+pub struct SwipeEvent {
+    /// Where the gesture started.
+    pub origin: Point,
+    pub direction: SwipeDirection,
+    /// The velocity when the touch was lifted, in logical pixels per second.
+    pub velocity: Vec2,
+}
+
+#[derive(Debug, Clone)]
+pub struct PinchEvent {
+    /// Where the gesture started.
+    pub origin: Point,
+    /// The point between the touches.
+    pub center: Point,
+    /// The scale since the gesture started, where `1.0` is the initial size.
+    pub scale: f64,
+    /// The change in scale since the last pinch event, as a factor.
+    pub scale_delta: f64,
+    pub phase: GesturePhase,
+}
+
+#[derive(Debug, Clone)]
+pub struct RotateEvent {
+    /// Where the gesture started.
+    pub origin: Point,
+    /// The point between the touches.
+    pub center: Point,
+    /// The rotation since the gesture started, in radians.
+    pub rotation: f64,
+    /// The rotation since the last rotate event, in radians.
+    pub delta: f64,
+    pub phase: GesturePhase,
+}
+
+struct TrackedTouch {
+    id: u64,
+    start: Point,
+    pos: Point,
+    last_time: Instant,
+    velocity: Vec2,
+}
+
+enum GestureState {
+    /// No touches are down.
+    Idle,
+    /// A single touch is down and hasn't moved, so it can still be a tap or a long press.
+    Pressed {
+        started: Instant,
+    },
+    LongPressed,
+    Panning {
+        origin: Point,
+        last: Point,
+    },
+    Transforming {
+        origin: Point,
+        distance: f64,
+        angle: f64,
+        scale: f64,
+        rotation: f64,
+    },
+    /// The gesture ended but some touches are still down.
+    Finished,
+}
+
+/// Turns the touches of a window into gesture events.
+pub(crate) struct GestureRecognizer {
+    touches: Vec<TrackedTouch>,
+    state: GestureState,
+    /// Incremented for every new press, so an outdated long press timer can be ignored.
+    press_generation: u64,
+    last_tap: Option<(Point, Instant, u8)>,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self {
+            touches: Vec::new(),
+            state: GestureState::Idle,
+            press_generation: 0,
+            last_tap: None,
+        }
+    }
+}
+
+impl GestureRecognizer {
+    /// Handles a new touch at `now`. Returns the gestures it caused, and the press generation if
+    /// a long press timer should be started.
+    pub(crate) fn touch_start(
+        &mut self,
+        id: u64,
+        pos: Point,
+        now: Instant,
+    ) -> (Vec<Event>, Option<u64>) {
+        self.touches.push(TrackedTouch {
+            id,
+            start: pos,
+            pos,
+            last_time: now,
+            velocity: Vec2::ZERO,
+        });
+        let mut events = Vec::new();
+        match self.touches.len() {
+            1 => {
+                self.state = GestureState::Pressed { started: now };
+                self.press_generation += 1;
+                return (events, Some(self.press_generation));
+            }
+            2 => {
+                if let GestureState::Panning { origin, last } = self.state {
+                    events.push(pan_end(origin, last, GesturePhase::Ended));
+                }
+                if !matches!(self.state, GestureState::Finished) {
+                    let origin = self.touches[0].start;
+                    let (center, distance, angle) = self.pair();
+                    self.state = GestureState::Transforming {
+                        origin,
+                        distance,
+                        angle,
+                        scale: 1.0,
+                        rotation: 0.0,
+                    };
+                    events.extend(transform_events(
+                        origin,
+                        center,
+                        (1.0, 1.0),
+                        (0.0, 0.0),
+                        GesturePhase::Began,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        (events, None)
+    }
+
+    pub(crate) fn touch_move(&mut self, id: u64, pos: Point, now: Instant) -> Vec<Event> {
+        let Some(touch) = self.touches.iter_mut().find(|touch| touch.id == id) else {
+            return Vec::new();
+        };
+        let elapsed = now.saturating_duration_since(touch.last_time).as_secs_f64();
+        if elapsed > 0.0 {
+            let velocity = (pos - touch.pos) / elapsed;
+            // Smooth the velocity a bit, since touch events can arrive unevenly.
+            touch.velocity = touch.velocity * 0.2 + velocity * 0.8;
+        }
+        touch.pos = pos;
+        touch.last_time = now;
+        let start = touch.start;
+
+        match self.state {
+            GestureState::Pressed { .. } if start.distance(pos) > TOUCH_SLOP => {
+                self.state = GestureState::Panning {
+                    origin: start,
+                    last: pos,
+                };
+                vec![Event::Pan(PanEvent {
+                    origin: start,
+                    pos,
+                    delta: pos - start,
+                    translation: pos - start,
+                    phase: GesturePhase::Began,
+                })]
+            }
+            GestureState::Panning { origin, last } => {
+                self.state = GestureState::Panning { origin, last: pos };
+                vec![Event::Pan(PanEvent {
+                    origin,
+                    pos,
+                    delta: pos - last,
+                    translation: pos - origin,
+                    phase: GesturePhase::Changed,
+                })]
+            }
+            GestureState::Transforming {
+                origin,
+                distance,
+                angle,
+                scale: last_scale,
+                rotation: last_rotation,
+            } if self.touches.len() >= 2 => {
+                let (center, new_distance, new_angle) = self.pair();
+                let scale = if distance > 0.0 {
+                    new_distance / distance
+                } else {
+                    1.0
+                };
+                let rotation = normalize_angle(new_angle - angle);
+                self.state = GestureState::Transforming {
+                    origin,
+                    distance,
+                    angle,
+                    scale,
+                    rotation,
+                };
+                let scale_delta = if last_scale > 0.0 {
+                    scale / last_scale
+                } else {
+                    1.0
+                };
+                transform_events(
+                    origin,
+                    center,
+                    (scale, scale_delta),
+                    (rotation, normalize_angle(rotation - last_rotation)),
+                    GesturePhase::Changed,
+                )
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn touch_end(
+        &mut self,
+        id: u64,
+        pos: Point,
+        cancelled: bool,
+        now: Instant,
+    ) -> Vec<Event> {
+        let Some(index) = self.touches.iter().position(|touch| touch.id == id) else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        match self.state {
+            GestureState::Pressed { started } if !cancelled => {
+                if now.saturating_duration_since(started) < LONG_PRESS_DURATION {
+                    let count = match self.last_tap {
+                        Some((last_pos, last_time, count))
+                            if now.saturating_duration_since(last_time) < MULTI_TAP_INTERVAL
+                                && last_pos.distance(pos) < TOUCH_SLOP * 2.0 =>
+                        {
+                            count.saturating_add(1)
+                        }
+                        _ => 1,
+                    };
+                    self.last_tap = Some((pos, now, count));
+                    events.push(Event::Tap(TapEvent { pos, count }));
+                }
+            }
+            GestureState::Panning { origin, last } => {
+                let phase = if cancelled {
+                    GesturePhase::Cancelled
+                } else {
+                    GesturePhase::Ended
+                };
+                events.push(pan_end(origin, last, phase));
+                let velocity = self.touches[index].velocity;
+                if !cancelled && velocity.hypot() > SWIPE_VELOCITY {
+                    let direction = if velocity.x.abs() > velocity.y.abs() {
+                        if velocity.x > 0.0 {
+                            SwipeDirection::Right
+                        } else {
+                            SwipeDirection::Left
+                        }
+                    } else if velocity.y > 0.0 {
+                        SwipeDirection::Down
+                    } else {
+                        SwipeDirection::Up
+                    };
+                    events.push(Event::Swipe(SwipeEvent {
+                        origin,
+                        direction,
+                        velocity,
+                    }));
+                }
+            }
+            GestureState::Transforming {
+                origin,
+                scale,
+                rotation,
+                ..
+            } if index < 2 => {
+                let (center, ..) = self.pair();
+                let phase = if cancelled {
+                    GesturePhase::Cancelled
+                } else {
+                    GesturePhase::Ended
+                };
+                events.extend(transform_events(
+                    origin,
+                    center,
+                    (scale, 1.0),
+                    (rotation, 0.0),
+                    phase,
+                ));
+            }
+            _ => {}
+        }
+
+        self.touches.remove(index);
+        self.state = if self.touches.is_empty() {
+            GestureState::Idle
+        } else if matches!(self.state, GestureState::Transforming { .. }) && index >= 2 {
+            // A touch beyond the first two doesn't affect the gesture.
+            std::mem::replace(&mut self.state, GestureState::Idle)
+        } else {
+            GestureState::Finished
+        };
+        events
+    }
+
+    /// Called when the long press timer for a press finishes.
+    pub(crate) fn long_press_timeout(&mut self, generation: u64) -> Option<Event> {
+        if generation != self.press_generation {
+            return None;
+        }
+        if let GestureState::Pressed { .. } = self.state {
+            let pos = self.touches.first()?.pos;
+            self.state = GestureState::LongPressed;
+            Some(Event::LongPress(LongPressEvent { pos }))
+        } else {
+            None
+        }
+    }
+
+    /// The center, distance and angle between the first two touches.
+    fn pair(&self) -> (Point, f64, f64) {
+        let a = self.touches[0].pos;
+        let b = self.touches[1].pos;
+        let d = b - a;
+        (a.midpoint(b), d.hypot(), d.y.atan2(d.x))
+    }
+}
+
+fn pan_end(origin: Point, last: Point, phase: GesturePhase) -> Event {
+    Event::Pan(PanEvent {
+        origin,
+        pos: last,
+        delta: Vec2::ZERO,
+        translation: last - origin,
+        phase,
+    })
+}
+
+fn transform_events(
+    origin: Point,
+    center: Point,
+    (scale, scale_delta): (f64, f64),
+    (rotation, delta): (f64, f64),
+    phase: GesturePhase,
+) -> Vec<Event> {
+    vec![
+        Event::Pinch(PinchEvent {
+            origin,
+            center,
+            scale,
+            scale_delta,
+            phase,
+        }),
+        Event::Rotate(RotateEvent {
+            origin,
+            center,
+            rotation,
+            delta,
+            phase,
+        }),
+    ]
+}
+
+/// Decides which touches are also sent as pointer events, and which mouse events are ignored
+/// because the platform emulated them from a touch, so a tap isn't handled twice.
+#[derive(Default)]
+pub(crate) struct TouchPointerFilter {
+    /// The touches whose start a view handled, which get no pointer events.
+    handled: Vec<u64>,
+    touches: usize,
+    last_touch: Option<Instant>,
+    /// The number of ignored mouse presses, whose releases are ignored too.
+    ignored_presses: usize,
+}
+
+impl TouchPointerFilter {
+    /// Handles the start of a touch, which `handled` says a view handled. Returns whether it's
+    /// sent as a pointer down.
+    pub(crate) fn touch_start(&mut self, id: u64, handled: bool, now: Instant) -> bool {
+        self.touches += 1;
+        self.last_touch = Some(now);
+        if handled {
+            self.handled.push(id);
+        }
+        !handled
+    }
+
+    /// Handles a move of a touch. Returns whether it's sent as a pointer move.
+    pub(crate) fn touch_move(&mut self, id: u64, now: Instant) -> bool {
+        self.last_touch = Some(now);
+        !self.handled.contains(&id)
+    }
+
+    /// Handles the end or cancellation of a touch. Returns whether it's sent as a pointer up.
+    pub(crate) fn touch_end(&mut self, id: u64, now: Instant) -> bool {
+        self.touches = self.touches.saturating_sub(1);
+        self.last_touch = Some(now);
+        let handled = self.handled.iter().position(|handled| *handled == id);
+        if let Some(i) = handled {
+            self.handled.swap_remove(i);
+        }
+        handled.is_none()
+    }
+
+    fn is_emulating_mouse(&self, now: Instant) -> bool {
+        self.touches > 0
+            || self.last_touch.is_some_and(|last_touch| {
+                now.saturating_duration_since(last_touch) < EMULATED_MOUSE_TIMEOUT
+            })
+    }
+
+    /// Returns whether a mouse button press or release is sent, rather than ignored as emulated
+    /// from a touch.
+    pub(crate) fn mouse_input(&mut self, pressed: bool, now: Instant) -> bool {
+        if pressed && self.is_emulating_mouse(now) {
+            self.ignored_presses += 1;
+            false
+        } else if !pressed && self.ignored_presses > 0 {
+            self.ignored_presses -= 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Returns whether a mouse move is sent, rather than ignored as emulated from a touch.
+    pub(crate) fn mouse_move(&self, now: Instant) -> bool {
+        !self.is_emulating_mouse(now)
+    }
+}
+
+/// Wraps an angle to the range `-PI..=PI`.
+fn normalize_angle(angle: f64) -> f64 {
+    use std::f64::consts::{PI, TAU};
+    let angle = angle.rem_euclid(TAU);
+    if angle > PI {
+        angle - TAU
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let t0 = Instant::now();
+        let mut gestures = GestureRecognizer::default();
+        let pos = Point::new(10.0, 10.0);
+
+        let (events, long_press) = gestures.touch_start(1, pos, t0);
+        assert!(events.is_empty());
+        assert_eq!(long_press, Some(1));
+        match &gestures.touch_end(1, pos, false, t0 + ms(50))[..] {
+            [Event::Tap(tap)] => assert_eq!(tap.count, 1),
+            events => panic!("expected a tap, got {events:?}"),
+        }
+
+        gestures.touch_start(2, Point::new(12.0, 11.0), t0 + ms(150));
+        match &gestures.touch_end(2, Point::new(12.0, 11.0), false, t0 + ms(200))[..] {
+            [Event::Tap(tap)] => assert_eq!(tap.count, 2),
+            events => panic!("expected a double tap, got {events:?}"),
+        }
+
+        // Too long after the last tap to count with it
+        gestures.touch_start(3, pos, t0 + ms(1000));
+        match &gestures.touch_end(3, pos, false, t0 + ms(1050))[..] {
+            [Event::Tap(tap)] => assert_eq!(tap.count, 1),
+            events => panic!("expected a tap, got {events:?}"),
+        }
+    }
+
+    #[test]
+    fn long_press_generation() {
+        let t0 = Instant::now();
+        let mut gestures = GestureRecognizer::default();
+        let pos = Point::new(10.0, 10.0);
+
+        let (_, first) = gestures.touch_start(1, pos, t0);
+        gestures.touch_end(1, pos, false, t0 + ms(50));
+        let (_, second) = gestures.touch_start(2, pos, t0 + ms(100));
+        assert_ne!(first, second);
+
+        // The timer of the first press is outdated
+        assert!(gestures.long_press_timeout(first.unwrap()).is_none());
+        assert!(matches!(
+            gestures.long_press_timeout(second.unwrap()),
+            Some(Event::LongPress(_))
+        ));
+        // A long press isn't also a tap
+        assert!(gestures.touch_end(2, pos, false, t0 + ms(700)).is_empty());
+
+        // Moving past the slop cancels the long press
+        let (_, third) = gestures.touch_start(3, pos, t0 + ms(1000));
+        gestures.touch_move(3, Point::new(40.0, 10.0), t0 + ms(1100));
+        assert!(gestures.long_press_timeout(third.unwrap()).is_none());
+    }
+
+    #[test]
+    fn pan_and_swipe() {
+        let t0 = Instant::now();
+        let mut gestures = GestureRecognizer::default();
+
+        gestures.touch_start(1, Point::ZERO, t0);
+        // Within the slop, so it's not a pan yet
+        assert!(gestures
+            .touch_move(1, Point::new(5.0, 0.0), t0 + ms(10))
+            .is_empty());
+        match &gestures.touch_move(1, Point::new(50.0, 0.0), t0 + ms(20))[..] {
+            [Event::Pan(pan)] => {
+                assert_eq!(pan.phase, GesturePhase::Began);
+                assert_eq!(pan.translation, Vec2::new(50.0, 0.0));
+            }
+            events => panic!("expected a pan, got {events:?}"),
+        }
+        match &gestures.touch_move(1, Point::new(100.0, 0.0), t0 + ms(30))[..] {
+            [Event::Pan(pan)] => {
+                assert_eq!(pan.phase, GesturePhase::Changed);
+                assert_eq!(pan.delta, Vec2::new(50.0, 0.0));
+            }
+            events => panic!("expected a pan, got {events:?}"),
+        }
+        match &gestures.touch_end(1, Point::new(100.0, 0.0), false, t0 + ms(40))[..] {
+            [Event::Pan(pan), Event::Swipe(swipe)] => {
+                assert_eq!(pan.phase, GesturePhase::Ended);
+                assert_eq!(swipe.direction, SwipeDirection::Right);
+            }
+            events => panic!("expected a pan and swipe, got {events:?}"),
+        }
+
+        // A slow pan isn't a swipe
+        gestures.touch_start(2, Point::ZERO, t0 + ms(100));
+        gestures.touch_move(2, Point::new(0.0, 50.0), t0 + ms(1100));
+        match &gestures.touch_end(2, Point::new(0.0, 50.0), false, t0 + ms(1200))[..] {
+            [Event::Pan(pan)] => assert_eq!(pan.phase, GesturePhase::Ended),
+            events => panic!("expected a pan, got {events:?}"),
+        }
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let t0 = Instant::now();
+        let mut gestures = GestureRecognizer::default();
+
+        gestures.touch_start(1, Point::ZERO, t0);
+        match &gestures
+            .touch_start(2, Point::new(100.0, 0.0), t0 + ms(10))
+            .0[..]
+        {
+            [Event::Pinch(pinch), Event::Rotate(rotate)] => {
+                assert_eq!(pinch.phase, GesturePhase::Began);
+                assert_eq!(rotate.phase, GesturePhase::Began);
+                assert_eq!(pinch.center, Point::new(50.0, 0.0));
+            }
+            events => panic!("expected a pinch and rotate, got {events:?}"),
+        }
+
+        // Twice as far apart, and turned a quarter turn
+        match &gestures.touch_move(2, Point::new(0.0, 200.0), t0 + ms(20))[..] {
+            [Event::Pinch(pinch), Event::Rotate(rotate)] => {
+                assert_eq!(pinch.phase, GesturePhase::Changed);
+                assert!((pinch.scale - 2.0).abs() < 1e-9);
+                assert!((rotate.rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+            }
+            events => panic!("expected a pinch and rotate, got {events:?}"),
+        }
+
+        match &gestures.touch_end(2, Point::new(0.0, 200.0), false, t0 + ms(30))[..] {
+            [Event::Pinch(pinch), Event::Rotate(rotate)] => {
+                assert_eq!(pinch.phase, GesturePhase::Ended);
+                assert!((pinch.scale - 2.0).abs() < 1e-9);
+                assert_eq!(rotate.phase, GesturePhase::Ended);
+            }
+            events => panic!("expected a pinch and rotate, got {events:?}"),
+        }
+        // The remaining touch doesn't start a new gesture
+        assert!(gestures
+            .touch_move(1, Point::new(80.0, 0.0), t0 + ms(40))
+            .is_empty());
+        assert!(gestures
+            .touch_end(1, Point::new(80.0, 0.0), false, t0 + ms(50))
+            .is_empty());
+    }
+
+    #[test]
+    fn cancel() {
+        let t0 = Instant::now();
+        let mut gestures = GestureRecognizer::default();
+
+        // A cancelled press isn't a tap
+        gestures.touch_start(1, Point::ZERO, t0);
+        assert!(gestures
+            .touch_end(1, Point::ZERO, true, t0 + ms(50))
+            .is_empty());
+
+        // A cancelled pan isn't a swipe, however fast it was
+        gestures.touch_start(2, Point::ZERO, t0 + ms(100));
+        gestures.touch_move(2, Point::new(100.0, 0.0), t0 + ms(110));
+        match &gestures.touch_end(2, Point::new(100.0, 0.0), true, t0 + ms(120))[..] {
+            [Event::Pan(pan)] => assert_eq!(pan.phase, GesturePhase::Cancelled),
+            events => panic!("expected a cancelled pan, got {events:?}"),
+        }
+
+        gestures.touch_start(3, Point::ZERO, t0 + ms(200));
+        gestures.touch_start(4, Point::new(100.0, 0.0), t0 + ms(210));
+        match &gestures.touch_end(3, Point::ZERO, true, t0 + ms(220))[..] {
+            [Event::Pinch(pinch), Event::Rotate(rotate)] => {
+                assert_eq!(pinch.phase, GesturePhase::Cancelled);
+                assert_eq!(rotate.phase, GesturePhase::Cancelled);
+            }
+            events => panic!("expected a cancelled pinch and rotate, got {events:?}"),
+        }
+    }
+
+    #[test]
+    fn single_tap_is_handled_once() {
+        let t0 = Instant::now();
+        let mut filter = TouchPointerFilter::default();
+
+        // A tap nobody handles as a touch is sent as pointer events, and the mouse events the
+        // platform emulates from it are ignored.
+        assert!(filter.touch_start(1, false, t0));
+        assert!(filter.touch_end(1, t0 + ms(80)));
+        assert!(!filter.mouse_move(t0 + ms(90)));
+        assert!(!filter.mouse_input(true, t0 + ms(90)));
+        assert!(!filter.mouse_input(false, t0 + ms(100)));
+
+        // A tap handled as a touch gets no pointer events at all.
+        assert!(!filter.touch_start(2, true, t0 + ms(1000)));
+        assert!(!filter.touch_move(2, t0 + ms(1020)));
+        assert!(!filter.touch_end(2, t0 + ms(1050)));
+        assert!(!filter.mouse_input(true, t0 + ms(1060)));
+        assert!(!filter.mouse_input(false, t0 + ms(1070)));
+
+        // The mouse works again a while after the last touch.
+        assert!(filter.mouse_move(t0 + ms(2000)));
+        assert!(filter.mouse_input(true, t0 + ms(2000)));
+        assert!(filter.mouse_input(false, t0 + ms(2100)));
+    }
+}
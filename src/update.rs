@@ -3,7 +3,7 @@ use std::{any::Any, cell::RefCell, collections::HashMap};
 use floem_winit::window::ResizeDirection;
 use peniko::kurbo::{Point, Rect, Size, Vec2};
 
-use crate::{id::ViewId, menu::Menu, view::View};
+use crate::{id::ViewId, menu::Menu, pointer::PointerId, shortcut::Shortcut, view::View};
 
 thread_local! {
     /// Stores all the update message with their original `ViewId`
@@ -31,6 +31,14 @@ pub(crate) enum UpdateMessage {
     ClearFocus(ViewId),
     Active(ViewId),
     ClearActive(ViewId),
+    CapturePointer {
+        id: ViewId,
+        pointer_id: PointerId,
+    },
+    ReleasePointerCapture {
+        id: ViewId,
+        pointer_id: PointerId,
+    },
     WindowScale(f64),
     Disabled {
         id: ViewId,
@@ -60,12 +68,23 @@ pub(crate) enum UpdateMessage {
     WindowMenu {
         menu: Menu,
     },
+    FocusTrap {
+        id: ViewId,
+        active: bool,
+    },
+    DismissOnEscape {
+        id: ViewId,
+    },
+    MenuBarShortcuts {
+        id: ViewId,
+        shortcuts: Vec<(Shortcut, u64)>,
+    },
     SetWindowTitle {
         title: String,
     },
     AddOverlay {
         id: ViewId,
-        position: Point,
+        position: Option<Point>,
         view: Box<dyn FnOnce() -> Box<dyn View>>,
     },
     RemoveOverlay {
@@ -54,6 +54,7 @@ use crate::{
     context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx, UpdateCx},
     event::{Event, EventPropagation},
     id::ViewId,
+    nav::tab_order,
     style::{BoxShadowProp, LayoutProps, Style, StyleClassRef},
     view_state::ViewStyleProps,
     views::{dyn_view, DynamicView},
@@ -298,6 +299,11 @@ pub trait View {
         }
         found
     }
+
+    /// Adds the state of the view, such as its value, to its node in the accessibility tree.
+    /// This is only called for views that have a role or can be focused.
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, _node: &mut accesskit::NodeBuilder) {}
 }
 
 impl View for Box<dyn View> {
@@ -348,6 +354,11 @@ impl View for Box<dyn View> {
     fn scroll_to(&mut self, cx: &mut AppState, target: ViewId, rect: Option<Rect>) -> bool {
         (**self).scroll_to(cx, target, rect)
     }
+
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut accesskit::NodeBuilder) {
+        (**self).accessibility(node)
+    }
 }
 
 /// Computes the layout of the view's children, if any.
@@ -528,101 +539,30 @@ pub(crate) fn paint_border(
     }
 }
 
-/// Tab navigation finds the next or previous view with the `keyboard_navigatable` status in the tree.
-#[allow(dead_code)]
+/// Tab navigation moves the focus to the next or previous view in the [tab order](tab_order) of
+/// the active focus trap, or of the whole window if there is none.
 pub(crate) fn view_tab_navigation(root_view: ViewId, app_state: &mut AppState, backwards: bool) {
-    let start = app_state.focus.unwrap_or(root_view);
+    let scope = app_state.focus_traps.last().copied().unwrap_or(root_view);
+    let order = tab_order(app_state, scope);
+    if order.is_empty() {
+        return;
+    }
 
-    let tree_iter = |id: ViewId| {
-        if backwards {
-            view_tree_previous(root_view, id).unwrap_or_else(|| view_nested_last_child(root_view))
-        } else {
-            view_tree_next(id).unwrap_or(root_view)
-        }
+    let len = order.len();
+    let current = app_state
+        .focus
+        .and_then(|focus| order.iter().position(|id| *id == focus));
+    let new_focus = match (current, backwards) {
+        (Some(i), false) => order[(i + 1) % len],
+        (Some(i), true) => order[(i + len - 1) % len],
+        (None, false) => order[0],
+        (None, true) => order[len - 1],
     };
 
-    let mut new_focus = tree_iter(start);
-    while new_focus != start && !app_state.can_focus(new_focus) {
-        new_focus = tree_iter(new_focus);
-    }
-
     app_state.clear_focus();
     app_state.update_focus(new_focus, true);
 }
 
-/// Get the next item in the tree, either the first child or the next sibling of this view or of the first parent view
-fn view_tree_next(id: ViewId) -> Option<ViewId> {
-    if let Some(child) = id.children().into_iter().next() {
-        return Some(child);
-    }
-
-    let mut ancestor = id;
-    loop {
-        if let Some(next_sibling) = view_next_sibling(ancestor) {
-            return Some(next_sibling);
-        }
-        ancestor = ancestor.parent()?;
-    }
-}
-
-/// Get the id of the view after this one (but with the same parent and level of nesting)
-fn view_next_sibling(id: ViewId) -> Option<ViewId> {
-    let parent = id.parent();
-
-    let Some(parent) = parent else {
-        // We're the root, which has no sibling
-        return None;
-    };
-
-    let children = parent.children();
-    let pos = children.iter().position(|v| v == &id).unwrap();
-
-    if pos + 1 < children.len() {
-        Some(children[pos + 1])
-    } else {
-        None
-    }
-}
-
-/// Get the next item in the tree, the deepest last child of the previous sibling of this view or the parent
-fn view_tree_previous(root_view: ViewId, id: ViewId) -> Option<ViewId> {
-    view_previous_sibling(id)
-        .map(view_nested_last_child)
-        .or_else(|| {
-            (root_view != id).then_some(
-                id.parent()
-                    .unwrap_or_else(|| view_nested_last_child(root_view)),
-            )
-        })
-}
-
-/// Get the id of the view before this one (but with the same parent and level of nesting)
-fn view_previous_sibling(id: ViewId) -> Option<ViewId> {
-    let parent = id.parent();
-
-    let Some(parent) = parent else {
-        // We're the root, which has no sibling
-        return None;
-    };
-
-    let children = parent.children();
-    let pos = children.iter().position(|v| v == &id).unwrap();
-
-    if pos > 0 {
-        Some(children[pos - 1])
-    } else {
-        None
-    }
-}
-
-fn view_nested_last_child(view: ViewId) -> ViewId {
-    let mut last_child = view;
-    while let Some(new_last_child) = last_child.children().pop() {
-        last_child = new_last_child;
-    }
-    last_child
-}
-
 /// Produces an ascii art debug display of all of the views.
 #[allow(dead_code)]
 pub(crate) fn view_debug_tree(root_view: ViewId) {
@@ -1,9 +1,15 @@
+#[cfg(feature = "accessibility")]
+use crate::accessibility::ViewAccessibility;
 use crate::{
     animate::Animation,
     context::{
-        EventCallback, InteractionState, MenuCallback, MoveListener, ResizeCallback, ResizeListener,
+        EventCallback, InteractionState, MenuCallback, MoveListener, ResizeCallback,
+        ResizeListener, VisibilityListener,
     },
     event::EventListener,
+    hit_test::PaintHitInfo,
+    id::ViewId,
+    keyboard::KeyEvent,
     pointer::PointerInputEvent,
     prop_extractor,
     responsive::ScreenSizeBp,
@@ -166,10 +172,34 @@ pub struct ViewState {
     pub(crate) window_origin: Point,
     pub(crate) move_listener: Option<Rc<RefCell<MoveListener>>>,
     pub(crate) cleanup_listener: Option<Rc<dyn Fn()>>,
+    pub(crate) visibility_listener: Option<Rc<RefCell<VisibilityListener>>>,
+    pub(crate) mount_listener: Option<Box<dyn FnOnce()>>,
     pub(crate) last_pointer_down: Option<PointerInputEvent>,
     pub(crate) is_hidden_state: IsHiddenState,
     pub(crate) num_waiting_animations: u16,
     pub(crate) debug_name: SmallVec<[String; 1]>,
+    /// Style overrides applied on top of the view's own style by the inspector.
+    pub(crate) inspector_style: Option<Style>,
+    /// How the view was last painted, or `None` if it wasn't visible.
+    pub(crate) paint_hit: Option<PaintHitInfo>,
+    /// Names that activate shortcuts while the view or one of its children has focus.
+    pub(crate) shortcut_scopes: SmallVec<[String; 1]>,
+    /// Whether the view uses a key down event itself while it has focus, in which case shortcuts
+    /// without a condition don't take the key from it.
+    pub(crate) claims_key: Option<Rc<dyn Fn(&KeyEvent) -> bool>>,
+    /// The position of the view in the tab order, see `Decorators::tab_index`.
+    pub(crate) tab_index: Option<i32>,
+    /// Whether the focusable children of the view form a roving focus group.
+    pub(crate) roving_focus: bool,
+    /// Whether the arrow keys wrap around at the ends of the roving focus group.
+    pub(crate) roving_wrap: bool,
+    /// Whether Ctrl+Tab switches between the tabs of the view while it contains the focus.
+    pub(crate) cycles_tabs: bool,
+    /// The member of the roving focus group that had focus last.
+    pub(crate) roving_last_focused: Option<ViewId>,
+    pub(crate) focus_within_listener: Option<Rc<dyn Fn(bool)>>,
+    #[cfg(feature = "accessibility")]
+    pub(crate) accessibility: ViewAccessibility,
 }
 
 impl ViewState {
@@ -195,11 +225,25 @@ impl ViewState {
             resize_listener: None,
             move_listener: None,
             cleanup_listener: None,
+            visibility_listener: None,
+            mount_listener: None,
             last_pointer_down: None,
             window_origin: Point::ZERO,
             is_hidden_state: IsHiddenState::None,
             num_waiting_animations: 0,
             debug_name: Default::default(),
+            inspector_style: None,
+            paint_hit: None,
+            shortcut_scopes: SmallVec::new(),
+            claims_key: None,
+            tab_index: None,
+            roving_focus: false,
+            roving_wrap: true,
+            cycles_tabs: false,
+            roving_last_focused: None,
+            focus_within_listener: None,
+            #[cfg(feature = "accessibility")]
+            accessibility: ViewAccessibility::default(),
         }
     }
 
@@ -224,6 +268,9 @@ impl ViewState {
         computed_style = computed_style
             .apply_classes_from_context(&self.classes, context)
             .apply(self.style());
+        if let Some(inspector_style) = &self.inspector_style {
+            computed_style.apply_mut(inspector_style.clone());
+        }
 
         for animation in self
             .animations
@@ -293,4 +340,15 @@ impl ViewState {
     pub(crate) fn update_cleanup_listener(&mut self, action: impl Fn() + 'static) {
         self.cleanup_listener = Some(Rc::new(action));
     }
+
+    pub(crate) fn update_visibility_listener(&mut self, action: Box<dyn Fn(f64)>) {
+        self.visibility_listener = Some(Rc::new(RefCell::new(VisibilityListener {
+            ratio: None,
+            callback: action,
+        })));
+    }
+
+    pub(crate) fn update_mount_listener(&mut self, action: impl FnOnce() + 'static) {
+        self.mount_listener = Some(Box::new(action));
+    }
 }
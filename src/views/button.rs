@@ -1,3 +1,6 @@
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
+
 use crate::{style_class, views::Decorators, IntoView, View, ViewId};
 use core::ops::FnMut;
 
@@ -19,6 +22,8 @@ impl Button {
     pub fn new(child: impl IntoView) -> Self {
         let id = ViewId::new();
         id.add_child(Box::new(child.into_view()));
+        #[cfg(feature = "accessibility")]
+        id.set_accessibility_role(Role::Button);
         Button { id }.keyboard_navigatable().class(ButtonClass)
     }
 
@@ -1,12 +1,16 @@
+#[cfg(feature = "accessibility")]
+use crate::{accessibility::node_id, view::View};
 use crate::{
     style_class,
     view::IntoView,
     views::{
-        self, create_value_container_signals, h_stack, svg, value_container, Decorators,
+        self, create_value_container_signals, h_stack, svg, value_container, Decorators, Stack,
         ValueContainer,
     },
 };
-use floem_reactive::{SignalGet, SignalUpdate};
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, Role, Toggled};
+use floem_reactive::{create_memo, SignalGet, SignalUpdate};
 use std::fmt::Display;
 
 style_class!(pub CheckboxClass);
@@ -15,11 +19,49 @@ style_class!(pub LabeledCheckboxClass);
 
 fn checkbox_svg(checked: impl SignalGet<bool> + 'static) -> impl IntoView {
     const CHECKBOX_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><polygon points="5.19,11.83 0.18,7.44 1.82,5.56 4.81,8.17 10,1.25 12,2.75" /></svg>"#;
+    let checked = create_memo(move |_| checked.get());
     let svg_str = move || if checked.get() { CHECKBOX_SVG } else { "" }.to_string();
-    svg(CHECKBOX_SVG)
+    let checkbox = svg(CHECKBOX_SVG)
         .update_value(svg_str)
         .class(CheckboxClass)
-        .keyboard_navigatable()
+        .keyboard_navigatable();
+    #[cfg(feature = "accessibility")]
+    let checkbox = checkbox
+        .accessibility_role(Role::CheckBox)
+        .accessibility(move |node| {
+            node.set_toggled(if checked.get_untracked() {
+                Toggled::True
+            } else {
+                Toggled::False
+            })
+        });
+    checkbox
+}
+
+/// A checkbox next to a label, which is also the label of the checkbox for assistive
+/// technologies. As the stack handles clicks, `toggle` handles clicks requested on the checkbox
+/// by an assistive technology.
+fn labeled_checkbox_stack<S: Display + 'static>(
+    checked: impl SignalGet<bool> + 'static,
+    label: impl Fn() -> S + 'static,
+    toggle: Option<impl Fn() + 'static>,
+) -> Stack {
+    let label = views::label(label);
+    let checkbox = checkbox_svg(checked).into_any();
+    #[cfg(feature = "accessibility")]
+    let checkbox = {
+        let label_id = label.id();
+        let checkbox = checkbox.accessibility(move |node| node.push_labelled_by(node_id(label_id)));
+        match toggle {
+            Some(toggle) => checkbox.on_accessibility_action(Action::Click, move |_| toggle()),
+            None => checkbox,
+        }
+    };
+    #[cfg(not(feature = "accessibility"))]
+    let _ = toggle;
+    h_stack((checkbox, label))
+        .class(LabeledCheckboxClass)
+        .style(|s| s.items_center().justify_center())
 }
 
 /// The `Checkbox` struct provides various methods to create and manage checkboxes.
@@ -79,17 +121,14 @@ impl Checkbox {
     ) -> ValueContainer<bool> {
         let (inbound_signal, outbound_signal) = create_value_container_signals(checked);
 
+        let toggle = move || {
+            let checked = inbound_signal.get_untracked();
+            outbound_signal.set(!checked);
+        };
+
         value_container(
-            h_stack((
-                checkbox_svg(inbound_signal.read_only()),
-                views::label(label),
-            ))
-            .class(LabeledCheckboxClass)
-            .on_click_stop(move |_| {
-                let checked = inbound_signal.get_untracked();
-                outbound_signal.set(!checked);
-            })
-            .style(|s| s.items_center().justify_center()),
+            labeled_checkbox_stack(inbound_signal.read_only(), label, Some(toggle))
+                .on_click_stop(move |_| toggle()),
             move || outbound_signal.get(),
         )
     }
@@ -102,9 +141,7 @@ impl Checkbox {
         checked: impl SignalGet<bool> + 'static,
         label: impl Fn() -> S + 'static,
     ) -> impl IntoView {
-        h_stack((checkbox_svg(checked), views::label(label)))
-            .class(LabeledCheckboxClass)
-            .style(|s| s.items_center().justify_center())
+        labeled_checkbox_stack(checked, label, None::<fn()>)
     }
 
     /// Creates a new labeled checkbox with a signal that provides and updates its checked state.
@@ -115,12 +152,8 @@ impl Checkbox {
         checked: impl SignalGet<bool> + SignalUpdate<bool> + Copy + 'static,
         label: impl Fn() -> S + 'static,
     ) -> impl IntoView {
-        h_stack((checkbox_svg(checked), views::label(label)))
-            .class(LabeledCheckboxClass)
-            .style(|s| s.items_center().justify_center())
-            .on_click_stop(move |_| {
-                checked.update(|val| *val = !*val);
-            })
+        let toggle = move || checked.update(|val| *val = !*val);
+        labeled_checkbox_stack(checked, label, Some(toggle)).on_click_stop(move |_| toggle())
     }
 }
 
@@ -2,6 +2,8 @@
 //!
 //! The decorator trait is the primary interface for extending the appearance and functionality of ['View']s.
 
+use std::rc::Rc;
+
 use floem_reactive::{create_effect, create_updater, SignalUpdate};
 use floem_winit::keyboard::Key;
 use peniko::kurbo::{Point, Rect};
@@ -9,6 +11,7 @@ use peniko::kurbo::{Point, Rect};
 use crate::{
     action::{set_window_menu, set_window_title, update_window_scale},
     animate::Animation,
+    drag::{self, DropEffect},
     event::{Event, EventListener, EventPropagation},
     keyboard::Modifiers,
     menu::Menu,
@@ -136,6 +139,169 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Makes the view draggable and sets the payload of drags started from it.
+    ///
+    /// See the [`drag`](crate::drag) module for reading the payload in drop targets.
+    fn drag_data<T: 'static>(self, data: impl Fn() -> T + 'static) -> Self::DV {
+        let view = self.draggable();
+        view.id().add_event_listener(
+            EventListener::DragStart,
+            Box::new(move |_| {
+                drag::set_drag_data(data());
+                EventPropagation::Continue
+            }),
+        );
+        view
+    }
+
+    /// Sets the [`DropEffect`] while a drag carrying a `T` is over the view.
+    fn on_drag_over_data<T: 'static>(
+        self,
+        effect: impl Fn(&T) -> DropEffect + 'static,
+    ) -> Self::DV {
+        let effect = Rc::new(effect);
+        let view = self.into_view();
+        for listener in [EventListener::DragEnter, EventListener::DragOver] {
+            let effect = effect.clone();
+            view.id().add_event_listener(
+                listener,
+                Box::new(move |_| {
+                    if let Some(data) = drag::drag_data::<T>() {
+                        drag::set_drop_effect(effect(&data));
+                    }
+                    EventPropagation::Continue
+                }),
+            );
+        }
+        view
+    }
+
+    /// Add a handler for drops of a `T` on the view. The handler gets the payload and the
+    /// position of the drop relative to the view.
+    fn on_drop_data<T: 'static>(self, action: impl Fn(Rc<T>, Point) + 'static) -> Self::DV {
+        self.on_event(EventListener::Drop, move |e| {
+            match (drag::drag_data::<T>(), e.point()) {
+                (Some(data), Some(pos)) => {
+                    action(data, pos);
+                    EventPropagation::Stop
+                }
+                _ => EventPropagation::Continue,
+            }
+        })
+    }
+
+    /// Activates shortcuts whose condition names `scope` while this view or one of its children
+    /// has focus. See the [`shortcut`](crate::shortcut) module.
+    fn shortcut_scope(self, scope: impl Into<String>) -> Self::DV {
+        let view = self.into_view();
+        view.id().add_shortcut_scope(scope);
+        view
+    }
+
+    /// Sets the position of the view in the tab order. See [`ViewId::set_tab_index`].
+    ///
+    /// [`ViewId::set_tab_index`]: crate::id::ViewId::set_tab_index
+    fn tab_index(self, index: i32) -> Self::DV {
+        let view = self.into_view();
+        view.id().set_tab_index(index);
+        view
+    }
+
+    /// Traps the keyboard focus inside the view while `active` returns true, as needed for modal
+    /// dialogs. Tab and arrow navigation stay inside the view, the first focusable child gets
+    /// focus when the trap activates and the previous focus is restored when it deactivates or
+    /// the view is removed.
+    fn trap_focus(self, active: impl Fn() -> bool + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            id.set_focus_trap(active());
+        });
+        view
+    }
+
+    /// Makes the focusable children of the view a roving focus group, such as the buttons of a
+    /// toolbar. Tab moves into the group at the child that had focus last and then out of it,
+    /// while the arrow keys, Home and End move the focus between the children.
+    fn roving_focus(self) -> Self::DV {
+        let view = self.into_view();
+        view.id().set_roving_focus();
+        view
+    }
+
+    /// Sets whether the arrow keys wrap around at the ends of a [roving focus](Self::roving_focus)
+    /// group, or stop at the first and last child. They wrap by default.
+    fn roving_focus_wrap(self, wrap: bool) -> Self::DV {
+        let view = self.into_view();
+        view.id().set_roving_focus_wrap(wrap);
+        view
+    }
+
+    /// Add a handler that's called with `true` when the view or one of its children gains focus
+    /// while none of them had it, and with `false` when the focus leaves all of them.
+    fn on_focus_within(self, action: impl Fn(bool) + 'static) -> Self::DV {
+        let view = self.into_view();
+        view.id().update_focus_within_listener(Box::new(action));
+        view
+    }
+
+    /// Sets the role of the view in the accessibility tree, such as
+    /// [`Role::Button`](accesskit::Role::Button). Built-in widgets set a role by default.
+    #[cfg(feature = "accessibility")]
+    fn accessibility_role(self, role: accesskit::Role) -> Self::DV {
+        let view = self.into_view();
+        view.id().set_accessibility_role(role);
+        view
+    }
+
+    /// Sets the label that assistive technologies announce for the view.
+    #[cfg(feature = "accessibility")]
+    fn accessibility_label<S: Into<String>>(self, label: impl Fn() -> S + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            id.set_accessibility_label(Some(label().into()));
+        });
+        view
+    }
+
+    /// Sets a description of the view for assistive technologies, in addition to its label.
+    #[cfg(feature = "accessibility")]
+    fn accessibility_description<S: Into<String>>(
+        self,
+        description: impl Fn() -> S + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        create_effect(move |_| {
+            id.set_accessibility_description(Some(description().into()));
+        });
+        view
+    }
+
+    /// Adds properties to the node of the view in the accessibility tree, such as its value or
+    /// whether it's expanded. `properties` is called each time the tree is built.
+    #[cfg(feature = "accessibility")]
+    fn accessibility(self, properties: impl Fn(&mut accesskit::NodeBuilder) + 'static) -> Self::DV {
+        let view = self.into_view();
+        view.id().add_accessibility_properties(properties);
+        view
+    }
+
+    /// Handles an action that an assistive technology requests on the view, such as
+    /// [`Action::SetValue`](accesskit::Action::SetValue). The handler replaces the default
+    /// handling of focus, click and scroll requests.
+    #[cfg(feature = "accessibility")]
+    fn on_accessibility_action(
+        self,
+        action: accesskit::Action,
+        handler: impl Fn(Option<&accesskit::ActionData>) + 'static,
+    ) -> Self::DV {
+        let view = self.into_view();
+        view.id().add_accessibility_action(action, handler);
+        view
+    }
+
     fn disabled(self, disabled_fn: impl Fn() -> bool + 'static) -> Self::DV {
         let view = self.into_view();
         let id = view.id();
@@ -310,6 +476,30 @@ pub trait Decorators: IntoView<V = Self::DV> + Sized {
         view
     }
 
+    /// Add a handler that's called with the ratio of the view's area that's visible,
+    /// from `0.0` to `1.0`, whenever it changes.
+    ///
+    /// The visible area is the part of the view inside the window and the viewports of
+    /// any `scroll` views it's in. Hidden views have a ratio of `0.0`.
+    fn on_visibility_change(self, action: impl Fn(f64) + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        let state = id.state();
+        state
+            .borrow_mut()
+            .update_visibility_listener(Box::new(action));
+        view
+    }
+
+    /// Add a handler that's called once, the first time the view is laid out in a window.
+    fn on_mount(self, action: impl FnOnce() + 'static) -> Self::DV {
+        let view = self.into_view();
+        let id = view.id();
+        let state = id.state();
+        state.borrow_mut().update_mount_listener(action);
+        view
+    }
+
     fn animation(self, animation: impl Fn(Animation) -> Animation + 'static) -> Self::DV {
         let view = self.into_view();
         let view_id = view.id();
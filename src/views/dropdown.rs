@@ -1,21 +1,22 @@
 use std::{any::Any, rc::Rc};
 
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, NodeBuilder, Role};
 use floem_reactive::{
     as_child_of_current_scope, create_effect, create_updater, Scope, SignalGet, SignalUpdate,
 };
 use floem_winit::keyboard::{Key, NamedKey};
-use peniko::kurbo::{Point, Rect};
 
 use crate::{
-    action::{add_overlay, remove_overlay},
+    action::{add_layer, remove_overlay},
     event::{Event, EventListener, EventPropagation},
     id::ViewId,
     prop, prop_extractor,
     style::{CustomStylable, Style, StyleClass, Width},
     style_class,
     unit::PxPctAuto,
-    view::{default_compute_layout, IntoView, View},
-    views::{scroll, Decorators},
+    view::{IntoView, View},
+    views::{scroll, Decorators, PopoverConfig, PopoverLayer},
 };
 
 use super::list;
@@ -74,7 +75,6 @@ pub struct Dropdown<T: 'static> {
     list_view: Rc<dyn Fn() -> Box<dyn View>>,
     list_style: Style,
     overlay_id: Option<ViewId>,
-    window_origin: Option<Point>,
     on_accept: Option<Box<dyn Fn(T)>>,
     on_open: Option<Box<dyn Fn(bool)>>,
     style: DropdownStyle,
@@ -110,12 +110,6 @@ impl<T: 'static> View for Dropdown<T> {
         }
     }
 
-    fn compute_layout(&mut self, cx: &mut crate::context::ComputeLayoutCx) -> Option<Rect> {
-        self.window_origin = Some(cx.window_origin);
-
-        default_compute_layout(self.id, cx)
-    }
-
     fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
         if let Ok(state) = state.downcast::<Message>() {
             match *state {
@@ -171,6 +165,11 @@ impl<T: 'static> View for Dropdown<T> {
 
         EventPropagation::Continue
     }
+
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_expanded(self.overlay_id.is_some());
+    }
 }
 
 impl<T> Dropdown<T> {
@@ -227,6 +226,16 @@ impl<T> Dropdown<T> {
         let main_view = child.id();
 
         dropdown_id.set_children(vec![child]);
+        #[cfg(feature = "accessibility")]
+        {
+            dropdown_id.set_accessibility_role(Role::ComboBox);
+            dropdown_id.add_accessibility_action(Action::Expand, move |_| {
+                dropdown_id.update_state(Message::OpenState(true));
+            });
+            dropdown_id.add_accessibility_action(Action::Collapse, move |_| {
+                dropdown_id.update_state(Message::OpenState(false));
+            });
+        }
 
         Self {
             id: dropdown_id,
@@ -236,7 +245,6 @@ impl<T> Dropdown<T> {
             list_view,
             list_style: Style::new(),
             overlay_id: None,
-            window_origin: None,
             on_accept: None,
             on_open: None,
             style: Default::default(),
@@ -309,9 +317,7 @@ impl<T> Dropdown<T> {
             cx.app_state.compute_layout();
             if let Some(layout) = self.id.get_layout() {
                 self.update_list_style(layout.size.width as f64);
-                let point =
-                    self.window_origin.unwrap_or_default() + (0., layout.size.height as f64);
-                self.create_overlay(point);
+                self.create_overlay();
 
                 if let Some(on_open) = &self.on_open {
                     on_open(true);
@@ -336,16 +342,24 @@ impl<T> Dropdown<T> {
         }
     }
 
-    fn create_overlay(&mut self, point: Point) {
+    fn create_overlay(&mut self) {
+        let id = self.id;
         let list = self.list_view.clone();
         let list_style = self.list_style.clone();
-        self.overlay_id = Some(add_overlay(point, move |_| {
+        // The list opens below the dropdown, or above it when there's no room left below.
+        let config = PopoverConfig {
+            offset: 0.0,
+            ..PopoverConfig::new(Some(id))
+        };
+        self.overlay_id = Some(add_layer(move |_| {
             let list = list()
                 .style(move |s| s.apply(list_style.clone()))
                 .into_view();
             let list_id = list.id();
             list_id.request_focus();
-            list
+            PopoverLayer::new(list, config, move || {
+                id.update_state(Message::OpenState(false));
+            })
         }));
     }
 
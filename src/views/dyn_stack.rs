@@ -7,10 +7,16 @@ use floem_reactive::{as_child_of_current_scope, create_effect, Scope};
 use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 
+use peniko::kurbo::Point;
+use taffy::style::FlexDirection;
+
 use crate::{
     app_state::AppState,
-    context::UpdateCx,
+    context::{EventCx, UpdateCx},
+    drag::{self, DropEffect},
+    event::{Event, EventListener, EventPropagation},
     id::ViewId,
+    style::FlexDirectionProp,
     view::{IntoView, View},
 };
 
@@ -29,6 +35,7 @@ where
     id: ViewId,
     children: Vec<Option<(ViewId, Scope)>>,
     view_fn: ViewFn<T>,
+    on_reorder: Option<Box<dyn Fn(usize, usize)>>,
     phantom: PhantomData<T>,
 }
 
@@ -121,10 +128,74 @@ where
         id,
         children: Vec::new(),
         view_fn,
+        on_reorder: None,
         phantom: PhantomData,
     }
 }
 
+impl<T> DynStack<T> {
+    /// Lets the items be reordered by dragging them within the stack.
+    ///
+    /// When an item is dropped, `on_reorder` is called with its current index and the index it
+    /// should be moved to, counted after removing it from the list. Update the items with
+    /// `items.insert(to, items.remove(from))` to apply the move.
+    pub fn reorderable(mut self, on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        self.on_reorder = Some(Box::new(on_reorder));
+        self
+    }
+}
+
+/// The index a dragged child of `stack` at `pos` would be moved to, counted without the dragged
+/// child.
+fn reorder_target(stack: ViewId, dragged: ViewId, pos: Point) -> usize {
+    let is_row = matches!(
+        stack.state().borrow().combined_style.get(FlexDirectionProp),
+        FlexDirection::Row | FlexDirection::RowReverse
+    );
+    stack
+        .children()
+        .into_iter()
+        .filter(|child| *child != dragged)
+        .filter(|child| {
+            let layout = child.get_layout().unwrap_or_default();
+            if is_row {
+                pos.x > (layout.location.x + layout.size.width / 2.0) as f64
+            } else {
+                pos.y > (layout.location.y + layout.size.height / 2.0) as f64
+            }
+        })
+        .count()
+}
+
+/// Handles the drag of a child of `stack` to reorder it. Returns the index of the child and the
+/// index to move it to when it's dropped in the stack, which is then handled.
+pub(crate) fn reorder_drop(
+    cx: &mut EventCx,
+    stack: ViewId,
+    event: &Event,
+) -> Option<(usize, usize)> {
+    let dragged = cx.app_state.dragging.as_ref()?.id;
+    let from = stack.children().iter().position(|id| *id == dragged)?;
+    match event {
+        Event::PointerMove(_) if cx.app_state.is_dragging() => {
+            drag::set_drop_effect(DropEffect::Move);
+        }
+        Event::PointerUp(pointer_event) if cx.app_state.is_dragging() => {
+            let rect = stack.get_size().unwrap_or_default().to_rect();
+            if rect.contains(pointer_event.pos) {
+                let to = reorder_target(stack, dragged, pointer_event.pos);
+                // the item is moved to its new place, so it shouldn't animate back
+                cx.app_state.dragging = None;
+                cx.app_state.request_paint(dragged);
+                dragged.apply_event(&EventListener::DragEnd, event);
+                return Some((from, to));
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
 impl<T> View for DynStack<T> {
     fn id(&self) -> ViewId {
         self.id
@@ -143,9 +214,29 @@ impl<T> View for DynStack<T> {
                 &mut self.children,
                 &self.view_fn,
             );
+            if self.on_reorder.is_some() {
+                for child in self.id.children() {
+                    cx.app_state.draggable.insert(child);
+                }
+            }
             self.id.request_all();
         }
     }
+
+    fn event_after_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let Some(on_reorder) = &self.on_reorder else {
+            return EventPropagation::Continue;
+        };
+        match reorder_drop(cx, self.id, event) {
+            Some((from, to)) => {
+                if from != to {
+                    on_reorder(from, to);
+                }
+                EventPropagation::Stop
+            }
+            None => EventPropagation::Continue,
+        }
+    }
 }
 
 #[derive(Debug)]
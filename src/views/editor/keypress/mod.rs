@@ -1,14 +1,19 @@
 pub mod key;
 pub mod press;
 
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
 
-use crate::{keyboard::Modifiers, reactive::RwSignal};
-use floem_editor_core::{
-    command::{EditCommand, MoveCommand, MultiSelectionCommand, ScrollCommand},
-    mode::Mode,
+use crate::{
+    keyboard::Modifiers,
+    reactive::RwSignal,
+    shortcut::{self, KeyStroke, Shortcut, When},
 };
-use floem_reactive::{SignalGet, SignalWith};
+use floem_editor_core::command::{EditCommand, MoveCommand, MultiSelectionCommand, ScrollCommand};
+use floem_reactive::SignalWith;
 
 use super::{
     command::{Command, CommandExecuted},
@@ -374,30 +379,134 @@ fn add_default_nonmacos(c: &mut HashMap<KeyPress, Command>) {
     );
 }
 
+thread_local! {
+    /// The editor with focus, which the editor commands in the shortcut registry run on.
+    static FOCUSED_EDITOR: Cell<Option<RwSignal<Editor>>> = const { Cell::new(None) };
+    static COMMANDS_REGISTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+pub(crate) fn set_focused_editor(editor: RwSignal<Editor>, focused: bool) {
+    FOCUSED_EDITOR.with(|focused_editor| {
+        if focused {
+            focused_editor.set(Some(editor));
+        } else if focused_editor.get() == Some(editor) {
+            focused_editor.set(None);
+        }
+    });
+}
+
+/// Registers the commands of the default keymap in the [shortcut registry](crate::shortcut),
+/// bound while an editor has focus (`when editor`). They run on the focused editor.
+///
+/// The command ids are `editor.` followed by the command name, such as `editor.undo`. In insert
+/// mode a key press with shift runs the command bound without it, so those shortcuts are bound
+/// too, and for moves and scrolls they go to a `_select` variant, such as `editor.down_select`,
+/// that extends the selection.
+///
+/// Editors register the commands when they are created. Registering again does nothing.
+pub fn register_editor_commands() {
+    if COMMANDS_REGISTERED.replace(true) {
+        return;
+    }
+
+    let keymap = KeypressMap::default();
+    let mut commands: BTreeMap<String, (String, Command, Modifiers, Vec<Shortcut>)> =
+        BTreeMap::new();
+    for (keypress, command) in &keymap.keymaps {
+        let KeyInput::Keyboard(key, _) = &keypress.key else {
+            continue;
+        };
+        let mut add = |shifted: bool, mods: Modifiers| {
+            let title = command.desc().unwrap_or(command.str());
+            let (id, title, modifiers) =
+                if shifted && matches!(command, Command::Move(_) | Command::Scroll(_)) {
+                    (
+                        format!("editor.{}_select", command.str()),
+                        format!("{title} (Extend Selection)"),
+                        Modifiers::SHIFT,
+                    )
+                } else {
+                    (
+                        format!("editor.{}", command.str()),
+                        title.to_string(),
+                        Modifiers::empty(),
+                    )
+                };
+            commands
+                .entry(id)
+                .or_insert_with(|| (title, command.clone(), modifiers, Vec::new()))
+                .3
+                .push(KeyStroke::new(key.clone(), mods).into());
+        };
+        add(false, keypress.mods);
+        let shifted = KeyPress::new(keypress.key.clone(), keypress.mods | Modifiers::SHIFT);
+        if !keypress.mods.contains(Modifiers::SHIFT) && !keymap.keymaps.contains_key(&shifted) {
+            add(true, shifted.mods);
+        }
+    }
+
+    for (id, (title, command, modifiers, shortcuts)) in commands {
+        let registered = shortcuts.into_iter().fold(
+            shortcut::Command::new(id, title, move || {
+                let Some(editor) = FOCUSED_EDITOR.get() else {
+                    return;
+                };
+                editor.try_with_untracked(|editor| {
+                    if let Some(editor) = editor {
+                        editor
+                            .doc()
+                            .run_command(editor, &command, Some(1), modifiers);
+                    }
+                });
+            }),
+            |registered, shortcut| {
+                registered.shortcut_binding(shortcut, Some(When::Name("editor".to_string())))
+            },
+        );
+        shortcut::register_command(registered);
+    }
+}
+
+/// The key handler of editors that use the default keymap. The keymap is bound in the
+/// [shortcut registry](crate::shortcut) by [`register_editor_commands`], so the registry runs
+/// those commands before the editor sees the key press and this leaves every key press unhandled.
 pub fn default_key_handler(
-    editor: RwSignal<Editor>,
+    _editor: RwSignal<Editor>,
 ) -> impl Fn(&KeyPress, Modifiers) -> CommandExecuted + 'static {
-    let keypress_map = KeypressMap::default();
-    move |keypress, modifiers| {
-        let command = keypress_map.keymaps.get(keypress).or_else(|| {
-            let mode = editor.get_untracked().cursor.get_untracked().get_mode();
-            if mode == Mode::Insert {
-                let mut keypress = keypress.clone();
-                keypress.mods.set(Modifiers::SHIFT, false);
-                keypress_map.keymaps.get(&keypress)
-            } else {
-                None
-            }
-        });
-
-        let Some(command) = command else {
-            return CommandExecuted::No;
-        };
+    |_, _| CommandExecuted::No
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(s: &str) -> Shortcut {
+        s.parse().unwrap()
+    }
 
-        editor.with_untracked(|editor| {
-            editor
-                .doc()
-                .run_command(editor, command, Some(1), modifiers)
-        })
+    #[test]
+    fn editor_commands_in_registry() {
+        register_editor_commands();
+        assert_eq!(
+            shortcut::shortcuts_for("editor.down"),
+            vec![shortcut("down")]
+        );
+        assert_eq!(
+            shortcut::shortcuts_for("editor.down_select"),
+            vec![shortcut("shift+down")]
+        );
+        assert_eq!(
+            shortcut::command_title("editor.down_select").as_deref(),
+            Some("Down (Extend Selection)")
+        );
+        assert!(!shortcut::shortcuts_for("editor.undo").is_empty());
+        assert!(shortcut::starts_binding(
+            &"down".parse().unwrap(),
+            "editor."
+        ));
+        assert!(!shortcut::starts_binding(
+            &"f12".parse().unwrap(),
+            "editor."
+        ));
     }
 }
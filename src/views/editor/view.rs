@@ -9,6 +9,7 @@ use crate::{
     kurbo::{BezPath, Line, Point, Rect, Size, Vec2},
     peniko::Color,
     reactive::{batch, create_effect, create_memo, create_rw_signal, Memo, RwSignal, Scope},
+    shortcut::{self, KeyStroke},
     style::{CursorStyle, Style},
     style_class,
     taffy::tree::NodeId,
@@ -26,7 +27,7 @@ use floem_reactive::{SignalGet, SignalTrack, SignalUpdate, SignalWith};
 use crate::views::editor::{
     command::CommandExecuted,
     gutter::editor_gutter_view,
-    keypress::{key::KeyInput, press::KeyPress},
+    keypress::{key::KeyInput, press::KeyPress, register_editor_commands, set_focused_editor},
     layout::LineExtraStyle,
     visual_line::{RVLine, VLineInfo},
 };
@@ -804,6 +805,11 @@ impl View for EditorView {
         self.id
     }
 
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut accesskit::NodeBuilder) {
+        node.set_value(self.editor.with_untracked(|ed| ed.text().to_string()));
+    }
+
     fn style_pass(&mut self, cx: &mut crate::context::StyleCx<'_>) {
         self.editor.with_untracked(|ed| {
             ed.es.update(|s| {
@@ -912,6 +918,8 @@ pub fn editor_view(
     is_active: impl Fn(bool) -> bool + 'static + Copy,
 ) -> EditorView {
     let id = ViewId::new();
+    #[cfg(feature = "accessibility")]
+    id.set_accessibility_role(accesskit::Role::MultilineTextInput);
     let is_active = create_memo(move |_| is_active(true));
 
     let ed = editor.get_untracked();
@@ -967,6 +975,7 @@ pub fn editor_view(
         inner_node: None,
     }
     .keyboard_navigatable()
+    .shortcut_scope("editor")
     .on_event(EventListener::ImePreedit, move |event| {
         if !is_active.get_untracked() {
             return EventPropagation::Continue;
@@ -1135,11 +1144,29 @@ fn editor_content(
         let id = editor_content_view.id();
         ed.editor_view_id.set(Some(id));
 
+        // Typed characters and the keys of the editor commands stay with the editor
+        register_editor_commands();
+        id.set_claims_key(move |key_event| {
+            let Ok(keypress) = KeyPress::try_from(key_event) else {
+                return false;
+            };
+            keypress.is_char()
+                || (keypress.mods.is_empty()
+                    && matches!(
+                        keypress.key,
+                        KeyInput::Keyboard(Key::Named(NamedKey::Space), _)
+                    ))
+                || KeyStroke::from_event(key_event)
+                    .is_some_and(|stroke| shortcut::starts_binding(&stroke, "editor."))
+        });
+
         editor_content_view
             .on_event_cont(EventListener::FocusGained, move |_| {
+                set_focused_editor(editor, true);
                 editor.with_untracked(|ed| ed.editor_view_focused.notify())
             })
             .on_event_cont(EventListener::FocusLost, move |_| {
+                set_focused_editor(editor, false);
                 editor.with_untracked(|ed| ed.editor_view_focus_lost.notify())
             })
             .on_event_cont(EventListener::PointerDown, move |event| {
@@ -0,0 +1,351 @@
+//! Forms that validate their fields as they're edited.
+//!
+//! A [`Form`] holds the values of its fields in signals that are bound to the inputs editing
+//! them, such as a [`text_input`](super::text_input), [checkbox](super::Checkbox),
+//! [dropdown](super::dropdown::Dropdown) or [`slider`](super::slider::slider). Each field
+//! is checked by its validators whenever its value changes, and errors are shown once the field
+//! has been left or the form submitted.
+
+use std::{cell::Cell, rc::Rc};
+
+#[cfg(feature = "accessibility")]
+use accesskit::Invalid;
+use floem_reactive::{
+    create_effect, create_memo, create_rw_signal, untrack, Memo, ReadSignal, RwSignal, Scope,
+    SignalGet, SignalUpdate, SignalWith,
+};
+
+use crate::{
+    event::EventListener,
+    ext_event::create_ext_action,
+    id::ViewId,
+    style_class,
+    view::IntoView,
+    views::{button, label, Button, Decorators, Label},
+};
+
+style_class!(pub FormErrorClass);
+/// Added to the view [bound](Field::bind) to a field while it shows an error.
+style_class!(pub FieldErrorClass);
+
+/// Called by an [asynchronous validator](Field::validate_async) with its result. It can be
+/// sent to and called from another thread.
+pub type ValidationDone = Box<dyn FnOnce(Result<(), String>) + Send>;
+
+type Validator<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+type AsyncValidator<T> = Rc<dyn Fn(T, ValidationDone)>;
+
+/// The state of a field that the form checks without knowing its type.
+#[derive(Clone, Copy)]
+struct FieldState {
+    touched: RwSignal<bool>,
+    error: RwSignal<Option<String>>,
+    pending: RwSignal<bool>,
+    view: RwSignal<Option<ViewId>>,
+}
+
+impl FieldState {
+    fn is_valid(&self) -> bool {
+        self.error.with(Option::is_none) && !self.pending.get()
+    }
+
+    fn shows_error(&self) -> bool {
+        self.touched.get() && self.error.with(Option::is_some)
+    }
+}
+
+/// A group of fields validated together. See [`form`].
+#[derive(Clone, Copy)]
+pub struct Form {
+    fields: RwSignal<Vec<FieldState>>,
+    is_valid: Memo<bool>,
+    submitting: RwSignal<bool>,
+}
+
+/// Creates a form, whose fields are added with [`Form::field`].
+///
+/// ```rust
+/// # use floem::reactive::SignalWith;
+/// # use floem::views::{form, text_input, v_stack, Decorators};
+/// let form = form();
+/// let name = form
+///     .field(String::new())
+///     .validate(|name| match name.trim().is_empty() {
+///         true => Err("Enter a name".to_string()),
+///         false => Ok(()),
+///     });
+/// v_stack((
+///     name.bind(text_input(name.value())),
+///     name.error_view(),
+///     form.submit_button("Save", move || name.value().with(|name| println!("saved {name}"))),
+/// ));
+/// ```
+pub fn form() -> Form {
+    let fields = create_rw_signal(Vec::<FieldState>::new());
+    Form {
+        fields,
+        is_valid: create_memo(move |_| fields.with(|fields| fields.iter().all(|f| f.is_valid()))),
+        submitting: create_rw_signal(false),
+    }
+}
+
+impl Form {
+    /// Adds a field starting at `initial`, which it's compared to for whether it's dirty.
+    pub fn field<T: Clone + PartialEq + 'static>(&self, initial: T) -> Field<T> {
+        let value = create_rw_signal(initial.clone());
+        let state = FieldState {
+            touched: create_rw_signal(false),
+            error: create_rw_signal(None),
+            pending: create_rw_signal(false),
+            view: create_rw_signal(None),
+        };
+        let validators = create_rw_signal(Vec::<Validator<T>>::new());
+        let async_validator = create_rw_signal(None::<AsyncValidator<T>>);
+        self.fields.update(|fields| fields.push(state));
+
+        // Results of asynchronous validations of earlier values are dropped
+        let generation = Rc::new(Cell::new(0u64));
+        create_effect(move |_| {
+            let value = value.get();
+            generation.set(generation.get() + 1);
+            // Validators can read other signals, such as another field to match, and are run
+            // again when those change
+            let result = validators
+                .with(|validators| validators.iter().try_for_each(|validate| validate(&value)));
+            let (error, pending) = match (result, async_validator.get()) {
+                (Err(error), _) => (Some(error), false),
+                (Ok(()), None) => (None, false),
+                (Ok(()), Some(validate)) => {
+                    let current = generation.get();
+                    let generation = generation.clone();
+                    let done =
+                        create_ext_action(Scope::current(), move |result: Result<(), String>| {
+                            if generation.get() == current {
+                                state.error.set(result.err());
+                                state.pending.set(false);
+                            }
+                        });
+                    untrack(|| validate(value, Box::new(done)));
+                    (None, true)
+                }
+            };
+            if state.error.with_untracked(|e| *e != error) {
+                state.error.set(error);
+            }
+            if state.pending.get_untracked() != pending {
+                state.pending.set(pending);
+            }
+        });
+
+        Field {
+            value,
+            dirty: create_memo(move |_| value.with(|value| *value != initial)),
+            state,
+            submitting: self.submitting,
+            validators,
+            async_validator,
+        }
+    }
+
+    /// Whether every field is valid, which it isn't while a field is being validated
+    /// asynchronously.
+    pub fn is_valid(&self) -> Memo<bool> {
+        self.is_valid
+    }
+
+    /// Whether the form is being submitted, which disables its bound fields and submit button.
+    /// Set it while an asynchronous submission is in progress.
+    pub fn submitting(&self) -> RwSignal<bool> {
+        self.submitting
+    }
+
+    /// Marks every field as touched so their errors are shown, then calls `on_submit` if the form
+    /// is valid, or focuses the first invalid field otherwise. Returns whether it was valid.
+    pub fn submit(&self, on_submit: impl FnOnce()) -> bool {
+        let fields = self.fields.get_untracked();
+        for field in &fields {
+            if !field.touched.get_untracked() {
+                field.touched.set(true);
+            }
+        }
+        let invalid = fields.iter().find(|field| !untrack(|| field.is_valid()));
+        if let Some(invalid) = invalid {
+            if let Some(id) = invalid.view.get_untracked() {
+                id.request_focus();
+                id.scroll_to(None);
+            }
+            return false;
+        }
+        on_submit();
+        true
+    }
+
+    /// A button that [submits](Form::submit) the form, disabled while it's being submitted.
+    pub fn submit_button<V: IntoView + 'static>(
+        &self,
+        child: V,
+        on_submit: impl Fn() + 'static,
+    ) -> Button {
+        let form = *self;
+        button(child)
+            .action(move || {
+                form.submit(&on_submit);
+            })
+            .disabled(move || form.submitting.get())
+    }
+}
+
+/// A field of a [`Form`], holding a value of type `T`.
+pub struct Field<T: 'static> {
+    value: RwSignal<T>,
+    dirty: Memo<bool>,
+    state: FieldState,
+    submitting: RwSignal<bool>,
+    validators: RwSignal<Vec<Validator<T>>>,
+    async_validator: RwSignal<Option<AsyncValidator<T>>>,
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Field<T> {}
+
+impl<T: 'static> Field<T> {
+    /// The value of the field, to bind to the input editing it.
+    pub fn value(&self) -> RwSignal<T> {
+        self.value
+    }
+
+    /// Whether the input of the field has been left, or the form submitted.
+    pub fn touched(&self) -> ReadSignal<bool> {
+        self.state.touched.read_only()
+    }
+
+    /// Whether the value differs from the initial one.
+    pub fn dirty(&self) -> Memo<bool> {
+        self.dirty
+    }
+
+    /// The message of the first validator that failed, if any.
+    pub fn error(&self) -> ReadSignal<Option<String>> {
+        self.state.error.read_only()
+    }
+
+    /// Whether the asynchronous validator is checking the value.
+    pub fn validating(&self) -> ReadSignal<bool> {
+        self.state.pending.read_only()
+    }
+
+    /// Adds a validator returning an error message if the value isn't valid. Validators run in
+    /// the order they're added, stopping at the first error.
+    pub fn validate(self, validate: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        self.validators
+            .update(|validators| validators.push(Rc::new(validate)));
+        self
+    }
+
+    /// Sets a validator that checks the value asynchronously, such as on a server, once the
+    /// other validators pass. It's given a callback to call with the result, from any thread.
+    /// The field is invalid until it's called.
+    pub fn validate_async(self, validate: impl Fn(T, ValidationDone) + 'static) -> Self {
+        self.async_validator.set(Some(Rc::new(validate)));
+        self
+    }
+
+    /// Binds the input editing the field, which is focused when the form is submitted while the
+    /// field is invalid. The field becomes touched when the input loses focus, and the input is
+    /// disabled while the form is being submitted and has the [`FieldErrorClass`] while it shows
+    /// an error, which the default theme shows with a red border.
+    pub fn bind<V: Decorators>(&self, view: V) -> V::DV {
+        let state = self.state;
+        let submitting = self.submitting;
+        let view = view.into_view();
+        state.view.set(Some(view.id()));
+        #[cfg(feature = "accessibility")]
+        view.id().add_accessibility_properties(move |node| {
+            if untrack(|| state.shows_error()) {
+                node.set_invalid(Invalid::True);
+            }
+        });
+        view.on_event_cont(EventListener::FocusLost, move |_| {
+            state.touched.set(true);
+        })
+        .disabled(move || submitting.get())
+        .class_if(move || state.shows_error(), FieldErrorClass)
+    }
+
+    /// A label showing the error of the field once it's touched, styled with
+    /// [`FormErrorClass`].
+    pub fn error_view(&self) -> Label {
+        let state = self.state;
+        label(move || state.error.get().unwrap_or_default())
+            .class(FormErrorClass)
+            .style(move |s| s.apply_if(!state.shows_error(), |s| s.hide()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use floem_reactive::{SignalGet, SignalUpdate, SignalWith};
+
+    use super::form;
+
+    fn required() -> impl Fn(&String) -> Result<(), String> {
+        |text: &String| match text.is_empty() {
+            true => Err("Required".to_string()),
+            false => Ok(()),
+        }
+    }
+
+    #[test]
+    fn validate_and_submit() {
+        let form = form();
+        let name = form.field(String::new()).validate(required());
+        let agreed = form.field(false);
+        assert_eq!(name.error().get(), Some("Required".to_string()));
+        assert!(!form.is_valid().get());
+        assert!(!name.touched().get());
+
+        assert!(!form.submit(|| panic!("submitted an invalid form")));
+        assert!(name.touched().get());
+
+        name.value().set("Ada".to_string());
+        assert_eq!(name.error().get(), None);
+        assert!(form.is_valid().get());
+        assert!(name.dirty().get());
+        assert!(!agreed.dirty().get());
+
+        let mut submitted = false;
+        assert!(form.submit(|| submitted = true));
+        assert!(submitted);
+    }
+
+    #[test]
+    fn validate_against_other_field() {
+        let form = form();
+        let password = form.field("secret".to_string());
+        let confirm = form
+            .field(String::new())
+            .validate(required())
+            .validate(
+                move |confirm| match password.value().with(|password| password == confirm) {
+                    true => Ok(()),
+                    false => Err("Passwords don't match".to_string()),
+                },
+            );
+        assert_eq!(confirm.error().get(), Some("Required".to_string()));
+
+        confirm.value().set("secret".to_string());
+        assert!(form.is_valid().get());
+
+        password.value().set("other".to_string());
+        assert_eq!(
+            confirm.error().get(),
+            Some("Passwords don't match".to_string())
+        );
+        assert!(!form.is_valid().get());
+    }
+}
@@ -0,0 +1,56 @@
+use std::rc::Rc;
+
+use floem_reactive::create_effect;
+
+use crate::{
+    context::StyleCx,
+    id::ViewId,
+    view::{IntoView, View},
+};
+
+/// Styles its child with the `selected` selector while `highlighted` returns `true`.
+pub(crate) struct Highlight {
+    id: ViewId,
+    child: ViewId,
+    highlighted: Rc<dyn Fn() -> bool>,
+}
+
+pub(crate) fn highlight(
+    child: impl IntoView,
+    highlighted: impl Fn() -> bool + 'static,
+) -> Highlight {
+    let id = ViewId::new();
+    let child = child.into_view();
+    let child_id = child.id();
+    id.set_children(vec![child]);
+    let highlighted: Rc<dyn Fn() -> bool> = Rc::new(highlighted);
+    {
+        let highlighted = highlighted.clone();
+        create_effect(move |_| {
+            highlighted();
+            id.request_style_recursive();
+        });
+    }
+    Highlight {
+        id,
+        child: child_id,
+        highlighted,
+    }
+}
+
+impl View for Highlight {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if (self.highlighted)() {
+            cx.save();
+            cx.selected();
+            cx.style_view(self.child);
+            cx.restore();
+        } else {
+            cx.style_view(self.child);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use std::{any::Any, fmt::Display, mem::swap};
 
+#[cfg(feature = "accessibility")]
+use accesskit::{NodeBuilder, Role};
+
 use crate::{
     context::{PaintCx, UpdateCx},
     event::{Event, EventListener, EventPropagation},
@@ -71,6 +74,8 @@ pub struct Label {
 
 impl Label {
     fn new(id: ViewId, label: String) -> Self {
+        #[cfg(feature = "accessibility")]
+        id.set_accessibility_role(Role::Label);
         Label {
             id,
             label,
@@ -313,6 +318,11 @@ impl View for Label {
         }
     }
 
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_value(self.label.clone());
+    }
+
     fn event_before_children(
         &mut self,
         _cx: &mut crate::context::EventCx,
@@ -1,5 +1,7 @@
-use super::{container, v_stack_from_iter, Decorators};
-use crate::context::StyleCx;
+use super::{container, dyn_stack::reorder_drop, v_stack_from_iter, Decorators};
+#[cfg(feature = "accessibility")]
+use crate::accessibility::node_id;
+use crate::context::{EventCx, StyleCx};
 use crate::event::EventPropagation;
 use crate::id::ViewId;
 use crate::reactive::create_effect;
@@ -11,6 +13,8 @@ use crate::{
     keyboard::{Key, NamedKey},
     view::View,
 };
+#[cfg(feature = "accessibility")]
+use accesskit::{NodeBuilder, Role};
 use floem_reactive::{create_rw_signal, RwSignal, SignalGet, SignalTrack, SignalUpdate};
 
 style_class!(pub ListClass);
@@ -34,6 +38,7 @@ pub struct List {
     id: ViewId,
     selection: RwSignal<Option<usize>>,
     onaccept: Option<Box<dyn Fn(Option<usize>)>>,
+    on_reorder: Option<Box<dyn Fn(usize, usize)>>,
     child: ViewId,
 }
 
@@ -54,8 +59,46 @@ impl List {
         self.onaccept = Some(Box::new(on_accept));
         self
     }
+
+    /// Lets the items be reordered by dragging them within the list. The list moves the dropped
+    /// item itself, and the selection follows the item it was on.
+    ///
+    /// `on_reorder` is then called with the previous index of the item and its new index,
+    /// counted after removing it from the list, so the data the items were built from can be
+    /// updated with `items.insert(to, items.remove(from))`.
+    pub fn reorderable(mut self, on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        for item in self.child.children() {
+            item.draggable();
+        }
+        self.on_reorder = Some(Box::new(on_reorder));
+        self
+    }
+
+    fn reorder(&self, from: usize, to: usize) {
+        let mut items = self.child.children();
+        let item = items.remove(from);
+        items.insert(to, item);
+        for (index, item) in items.iter().enumerate() {
+            item.update_state(ItemIndex(index));
+        }
+        self.child.set_children_ids(items);
+        self.child.request_all();
+
+        if let Some(selected) = self.selection.get_untracked() {
+            let selected = if selected == from {
+                to
+            } else {
+                let selected = selected - usize::from(selected > from);
+                selected + usize::from(selected >= to)
+            };
+            self.selection.set(Some(selected));
+        }
+    }
 }
 
+/// The new index of a list item after it was reordered.
+struct ItemIndex(usize);
+
 /// A list of views built from an iterator which remains static and always contains the same elements in the same order.
 ///
 /// A list is like a [stack](super::stack()) but also has built-in support for the selection of items: up and down using arrow keys, top and bottom control using the home and end keys, and for the "acceptance" of an item using the Enter key.
@@ -70,6 +113,8 @@ where
     V: IntoView + 'static,
 {
     let list_id = ViewId::new();
+    #[cfg(feature = "accessibility")]
+    list_id.set_accessibility_role(Role::ListBox);
     let selection = create_rw_signal(None);
     create_effect(move |_| {
         selection.track();
@@ -80,6 +125,8 @@ where
         let v = container(v).class(ListItemClass);
         let child = v.id();
         id.set_children(vec![v]);
+        #[cfg(feature = "accessibility")]
+        id.set_accessibility_role(Role::ListBoxOption);
         Item {
             id,
             selection,
@@ -87,6 +134,13 @@ where
             child,
         }
         .on_click_stop(move |_| {
+            // The item may have been reordered since it was created
+            let Some(index) = id
+                .parent()
+                .and_then(|stack| stack.children().iter().position(|child| *child == id))
+            else {
+                return;
+            };
             if selection.get_untracked() != Some(index) {
                 selection.set(Some(index));
                 list_id.update_state(ListUpdate::Accept);
@@ -102,6 +156,7 @@ where
         selection,
         child,
         onaccept: None,
+        on_reorder: None,
     }
     .keyboard_navigatable()
     .on_event(EventListener::KeyDown, move |e| {
@@ -194,6 +249,35 @@ impl View for List {
             }
         }
     }
+
+    fn event_after_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        if self.on_reorder.is_none() {
+            return EventPropagation::Continue;
+        }
+        match reorder_drop(cx, self.child, event) {
+            Some((from, to)) => {
+                if from != to {
+                    self.reorder(from, to);
+                    if let Some(on_reorder) = &self.on_reorder {
+                        on_reorder(from, to);
+                    }
+                }
+                EventPropagation::Stop
+            }
+            None => EventPropagation::Continue,
+        }
+    }
+
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        if let Some(item) = self
+            .selection
+            .get_untracked()
+            .and_then(|index| self.child.children().get(index).copied())
+        {
+            node.set_active_descendant(node_id(item));
+        }
+    }
 }
 
 impl View for Item {
@@ -209,6 +293,18 @@ impl View for Item {
         "Item".into()
     }
 
+    fn update(&mut self, _cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(index) = state.downcast::<ItemIndex>() {
+            self.index = index.0;
+            self.id.request_style();
+        }
+    }
+
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_selected(self.selection.get_untracked() == Some(self.index));
+    }
+
     fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
         let selected = self.selection.get_untracked();
         if Some(self.index) == selected {
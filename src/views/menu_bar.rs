@@ -0,0 +1,864 @@
+//! Menus drawn by Floem: the [`menu_bar`] view and the popup menus it opens, which are also used
+//! for context menus on platforms without native ones.
+//!
+//! Their look comes from the style classes in this module, [`MenuBarClass`] and
+//! [`MenuBarItemClass`] for the bar, [`MenuClass`] for a popup menu, [`SubMenuClass`] for a
+//! submenu popup, [`MenuItemClass`] and [`MenuSeparatorClass`] for the entries and
+//! [`MenuAcceleratorClass`] for the accelerator shown next to an item. The highlighted item and
+//! the open menu of the bar are styled with the `selected` selector.
+//!
+//! An `&` in a title marks the following letter as the mnemonic, so `&File` opens with `Alt+F`
+//! and the items of an open menu can be chosen by pressing their letter. Use `&&` for an
+//! ampersand.
+//!
+//! ```rust
+//! # use floem::menu::{Menu, MenuItem};
+//! # use floem::views::{menu_bar, Decorators, MenuItemClass};
+//! menu_bar(|| {
+//!     Menu::new("")
+//!         .entry(
+//!             Menu::new("&File")
+//!                 .entry(MenuItem::new("&Open").accelerator("mod+o"))
+//!                 .separator()
+//!                 .entry(MenuItem::new("&Quit")),
+//!         )
+//!         .entry(Menu::new("&Edit").entry(MenuItem::new("&Undo")))
+//! })
+//! .style(|s| s.class(MenuItemClass, |s| s.padding_horiz(8.0)));
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use floem_reactive::{create_effect, create_rw_signal, RwSignal, SignalGet, SignalUpdate};
+
+use crate::{
+    action::{add_overlay, exec_after, remove_overlay, TimerToken},
+    context::{StyleCx, UpdateCx},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::{Key, KeyEvent, Modifiers, NamedKey},
+    menu::{parse_mnemonic, Menu, MenuEntry},
+    reactive::SignalWith,
+    shortcut::{KeyStroke, Shortcut},
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+    views::{
+        container, dyn_stack, empty, h_stack, highlight, stack, svg, text, v_stack_from_iter,
+        Decorators,
+    },
+};
+
+style_class!(pub MenuBarClass);
+style_class!(pub MenuBarItemClass);
+style_class!(pub MenuClass);
+style_class!(pub SubMenuClass);
+style_class!(pub MenuItemClass);
+style_class!(pub MenuSeparatorClass);
+style_class!(pub MenuAcceleratorClass);
+
+/// How long the pointer has to rest on an item before its submenu opens.
+const SUBMENU_OPEN_DELAY: Duration = Duration::from_millis(200);
+/// How long an open submenu stays open while the pointer crosses other items on its way there.
+const SUBMENU_CLOSE_DELAY: Duration = Duration::from_millis(300);
+/// Typed characters are combined into one type-ahead search when they are at most this far apart.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+const SUBMENU_SVG: &str = r#"<svg width="16" height="16" viewBox="0 0 16 16" xmlns="http://www.w3.org/2000/svg" fill="currentColor"><path fill-rule="evenodd" clip-rule="evenodd" d="M10.072 8.024L5.715 3.667l.618-.62L11 7.716v.618L6.333 13l-.618-.619 4.357-4.357z"/></svg>"#;
+
+/// A menu entry as displayed by the menus drawn by Floem.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum MenuDisplay {
+    Separator(usize),
+    Item {
+        id: u64,
+        enabled: bool,
+        title: String,
+        mnemonic: Option<char>,
+        checked: Option<bool>,
+        radio: bool,
+        icon: Option<String>,
+        accelerator: Option<String>,
+        children: Option<Vec<MenuDisplay>>,
+    },
+}
+
+impl MenuDisplay {
+    fn is_enabled_item(&self) -> bool {
+        matches!(self, MenuDisplay::Item { enabled: true, .. })
+    }
+
+    fn children(&self) -> Option<&[MenuDisplay]> {
+        match self {
+            MenuDisplay::Item { children, .. } => children.as_deref(),
+            MenuDisplay::Separator(_) => None,
+        }
+    }
+}
+
+pub(crate) fn format_menu(menu: &Menu) -> Vec<MenuDisplay> {
+    menu.children
+        .iter()
+        .enumerate()
+        .map(|(s, e)| match e {
+            MenuEntry::Separator => MenuDisplay::Separator(s),
+            MenuEntry::Item(i) => {
+                let (title, mnemonic) = parse_mnemonic(&i.title);
+                MenuDisplay::Item {
+                    id: i.id,
+                    enabled: i.enabled,
+                    title,
+                    mnemonic,
+                    checked: i.selected,
+                    radio: i.radio,
+                    icon: i.icon.clone(),
+                    accelerator: i.shortcut.as_ref().map(|shortcut| shortcut.to_string()),
+                    children: None,
+                }
+            }
+            MenuEntry::SubMenu(m) => {
+                let (title, mnemonic) = parse_mnemonic(&m.item.title);
+                MenuDisplay::Item {
+                    id: m.item.id,
+                    enabled: m.item.enabled,
+                    title,
+                    mnemonic,
+                    checked: None,
+                    radio: false,
+                    icon: m.item.icon.clone(),
+                    accelerator: None,
+                    children: Some(format_menu(m)),
+                }
+            }
+        })
+        .collect()
+}
+
+/// The next enabled item after `from`, or before it when `forward` is false, wrapping around.
+fn next_enabled(entries: &[MenuDisplay], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = entries.len();
+    (1..=len)
+        .map(|step| match (from, forward) {
+            (Some(from), true) => (from + step) % len,
+            (Some(from), false) => (from + len - step % len) % len,
+            (None, true) => step - 1,
+            (None, false) => len - step,
+        })
+        .find(|&i| entries[i].is_enabled_item())
+}
+
+/// The state of a popup menu and its open submenus.
+#[derive(Clone)]
+pub(crate) struct MenuPopup {
+    entries: Rc<Vec<MenuDisplay>>,
+    /// The highlighted item of every open level, where level 0 is the popup itself and each
+    /// further level is the submenu of the highlighted item of the level before it.
+    path: RwSignal<Vec<Option<usize>>>,
+    hover_timer: RwSignal<TimerToken>,
+    type_ahead: Rc<RefCell<(String, Instant)>>,
+    on_activate: Rc<dyn Fn(u64)>,
+    on_close: Rc<dyn Fn()>,
+    on_cross: Option<Rc<dyn Fn(bool)>>,
+}
+
+impl MenuPopup {
+    pub(crate) fn new(
+        entries: Vec<MenuDisplay>,
+        on_activate: impl Fn(u64) + 'static,
+        on_close: impl Fn() + 'static,
+    ) -> Self {
+        Self {
+            entries: Rc::new(entries),
+            path: create_rw_signal(vec![None]),
+            hover_timer: create_rw_signal(TimerToken::INVALID),
+            type_ahead: Rc::new(RefCell::new((String::new(), Instant::now()))),
+            on_activate: Rc::new(on_activate),
+            on_close: Rc::new(on_close),
+            on_cross: None,
+        }
+    }
+
+    /// Called with `true` for right and `false` for left when the arrow keys leave the popup.
+    pub(crate) fn on_cross(mut self, on_cross: impl Fn(bool) + 'static) -> Self {
+        self.on_cross = Some(Rc::new(on_cross));
+        self
+    }
+
+    pub(crate) fn highlight_first(self) -> Self {
+        let first = next_enabled(&self.entries, None, true);
+        self.path.set(vec![first]);
+        self
+    }
+
+    fn level_entries(&self, path: &[Option<usize>], level: usize) -> &[MenuDisplay] {
+        let mut entries: &[MenuDisplay] = &self.entries;
+        for index in path.iter().take(level) {
+            entries = index
+                .and_then(|i| entries.get(i))
+                .and_then(|entry| entry.children())
+                .unwrap_or_default();
+        }
+        entries
+    }
+
+    fn schedule(&self, delay: Duration, action: impl FnOnce() + 'static) {
+        let hover_timer = self.hover_timer;
+        let token = exec_after(delay, move |token| {
+            if hover_timer.get_untracked() == token {
+                action();
+            }
+        });
+        hover_timer.set(token);
+    }
+
+    fn hover(&self, level: usize, index: usize, has_children: bool) {
+        let path = self.path;
+        let (highlighted, submenu_open) = path.with_untracked(|p| {
+            (
+                p.get(level).copied().flatten() == Some(index),
+                p.len() > level + 1,
+            )
+        });
+        if highlighted {
+            if !submenu_open {
+                self.hover_timer.set(TimerToken::INVALID);
+            }
+            return;
+        }
+
+        let open_submenu = move || {
+            path.update(|p| {
+                if p.len() == level + 1 && p[level] == Some(index) {
+                    p.push(None);
+                }
+            })
+        };
+        let this = self.clone();
+        let select = move || {
+            path.update(|p| {
+                p.truncate(level + 1);
+                p[level] = Some(index);
+            });
+            if has_children {
+                this.schedule(SUBMENU_OPEN_DELAY, open_submenu);
+            } else {
+                this.hover_timer.set(TimerToken::INVALID);
+            }
+        };
+        if submenu_open {
+            // Moving diagonally towards the open submenu crosses other items, so give the
+            // pointer some time to get there before switching.
+            self.schedule(SUBMENU_CLOSE_DELAY, select);
+        } else {
+            select();
+        }
+    }
+
+    fn activate(&self, level: usize, index: usize, from_keyboard: bool) {
+        let entries = self
+            .path
+            .with_untracked(|p| self.level_entries(p, level).to_vec());
+        let Some(MenuDisplay::Item {
+            id,
+            enabled: true,
+            children,
+            ..
+        }) = entries.get(index)
+        else {
+            return;
+        };
+        self.hover_timer.set(TimerToken::INVALID);
+        if let Some(children) = children {
+            let first = from_keyboard
+                .then(|| next_enabled(children, None, true))
+                .flatten();
+            self.path.update(|p| {
+                p.truncate(level + 1);
+                p[level] = Some(index);
+                p.push(first);
+            });
+        } else {
+            (self.on_close)();
+            (self.on_activate)(*id);
+        }
+    }
+
+    fn key_down(&self, event: &KeyEvent) -> EventPropagation {
+        let path = self.path.get_untracked();
+        let level = path.len() - 1;
+        let entries = self.level_entries(&path, level);
+        let highlighted = path[level];
+        let set_highlight = |index: Option<usize>| {
+            self.path.update(|p| p[level] = index);
+        };
+
+        match &event.key.logical_key {
+            Key::Named(NamedKey::ArrowDown) => {
+                set_highlight(next_enabled(entries, highlighted, true));
+            }
+            Key::Named(NamedKey::ArrowUp) => {
+                set_highlight(next_enabled(entries, highlighted, false));
+            }
+            Key::Named(NamedKey::Home) => set_highlight(next_enabled(entries, None, true)),
+            Key::Named(NamedKey::End) => set_highlight(next_enabled(entries, None, false)),
+            Key::Named(NamedKey::ArrowRight) => {
+                let has_submenu = highlighted
+                    .and_then(|i| entries.get(i))
+                    .is_some_and(|entry| entry.is_enabled_item() && entry.children().is_some());
+                if has_submenu {
+                    self.activate(level, highlighted.unwrap(), true);
+                } else if let Some(on_cross) = &self.on_cross {
+                    on_cross(true);
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                if level > 0 {
+                    self.path.update(|p| {
+                        p.pop();
+                    });
+                } else if let Some(on_cross) = &self.on_cross {
+                    on_cross(false);
+                }
+            }
+            Key::Named(NamedKey::Enter | NamedKey::Space) => {
+                if let Some(index) = highlighted {
+                    self.activate(level, index, true);
+                }
+            }
+            Key::Named(NamedKey::Escape) => {
+                if level > 0 {
+                    self.path.update(|p| {
+                        p.pop();
+                    });
+                } else {
+                    (self.on_close)();
+                }
+            }
+            Key::Character(c)
+                if !event
+                    .modifiers
+                    .intersects(Modifiers::CONTROL | Modifiers::META) =>
+            {
+                let c = c.to_lowercase();
+                self.type_key(level, entries, highlighted, &c);
+            }
+            _ => return EventPropagation::Continue,
+        }
+        EventPropagation::Stop
+    }
+
+    /// Chooses the item whose mnemonic is `c`, or otherwise moves to the next item whose title
+    /// starts with the characters typed so far.
+    fn type_key(&self, level: usize, entries: &[MenuDisplay], highlighted: Option<usize>, c: &str) {
+        let mnemonic_matches: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                matches!(entry, MenuDisplay::Item { enabled: true, mnemonic: Some(m), .. }
+                    if c.chars().eq(std::iter::once(*m)))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        match mnemonic_matches.as_slice() {
+            [] => {}
+            [index] => {
+                self.activate(level, *index, true);
+                return;
+            }
+            [first, ..] => {
+                // Several items share the mnemonic, so cycle through them.
+                let next = mnemonic_matches
+                    .iter()
+                    .find(|&&i| Some(i) > highlighted)
+                    .unwrap_or(first);
+                self.path.update(|p| p[level] = Some(*next));
+                return;
+            }
+        }
+
+        let query = {
+            let mut type_ahead = self.type_ahead.borrow_mut();
+            let (query, last) = &mut *type_ahead;
+            if last.elapsed() > TYPE_AHEAD_TIMEOUT {
+                query.clear();
+            }
+            *last = Instant::now();
+            query.push_str(c);
+            query.clone()
+        };
+        // A new search starts after the highlighted item, a longer query may still match it.
+        let start = match highlighted {
+            Some(i) if query.chars().count() > 1 => i,
+            Some(i) => i + 1,
+            None => 0,
+        };
+        let len = entries.len();
+        let found = (0..len).map(|step| (start + step) % len).find(|&i| {
+            matches!(&entries[i], MenuDisplay::Item { enabled: true, title, .. }
+                if title.to_lowercase().starts_with(&query))
+        });
+        if let Some(index) = found {
+            self.path.update(|p| p[level] = Some(index));
+        }
+    }
+
+    /// The popup view. It takes the keyboard focus and closes when it loses it.
+    pub(crate) fn view(self) -> impl IntoView {
+        let entries = self.entries.to_vec();
+        let this = self.clone();
+        let on_close = self.on_close.clone();
+        self.panel(0, entries)
+            .keyboard_navigatable()
+            .on_event(EventListener::KeyDown, move |event| {
+                if let Event::KeyDown(event) = event {
+                    this.key_down(event)
+                } else {
+                    EventPropagation::Continue
+                }
+            })
+            .on_event_stop(EventListener::FocusLost, move |_| on_close())
+            .on_event_stop(EventListener::PointerDown, |_| {})
+            .on_event_stop(EventListener::PointerMove, |_| {})
+    }
+
+    fn panel(&self, level: usize, entries: Vec<MenuDisplay>) -> impl IntoView {
+        let has_checks = entries.iter().any(|entry| {
+            matches!(
+                entry,
+                MenuDisplay::Item {
+                    checked: Some(_),
+                    ..
+                }
+            )
+        });
+        let this = self.clone();
+        let panel = v_stack_from_iter(
+            entries
+                .into_iter()
+                .enumerate()
+                .map(move |(index, entry)| this.entry_view(level, index, entry, has_checks)),
+        )
+        .class(MenuClass)
+        .style(|s| s.flex_col());
+        if level > 0 {
+            panel.class(SubMenuClass)
+        } else {
+            panel
+        }
+    }
+
+    fn entry_view(
+        &self,
+        level: usize,
+        index: usize,
+        entry: MenuDisplay,
+        has_checks: bool,
+    ) -> Box<dyn View> {
+        let MenuDisplay::Item {
+            enabled,
+            title,
+            checked,
+            radio,
+            icon,
+            accelerator,
+            children,
+            ..
+        } = entry
+        else {
+            return empty()
+                .class(MenuSeparatorClass)
+                .style(|s| s.width_full())
+                .into_any();
+        };
+
+        let path = self.path;
+        let has_submenu = children.is_some();
+        let row = h_stack((
+            text(match checked {
+                Some(true) if radio => "●",
+                Some(true) => "✓",
+                _ => "",
+            })
+            .style(move |s| s.min_width(16.0).apply_if(!has_checks, |s| s.hide())),
+            svg(icon.clone().unwrap_or_default()).style(move |s| {
+                s.size(16.0, 16.0)
+                    .margin_right(6.0)
+                    .apply_if(icon.is_none(), |s| s.hide())
+            }),
+            text(title).style(|s| s.flex_grow(1.0)),
+            text(accelerator.clone().unwrap_or_default())
+                .class(MenuAcceleratorClass)
+                .style(move |s| s.apply_if(accelerator.is_none(), |s| s.hide())),
+            svg(SUBMENU_SVG).style(move |s| {
+                s.size(20.0, 20.0)
+                    .margin_left(20.0)
+                    .apply_if(!has_submenu, |s| s.hide())
+            }),
+        ))
+        .class(MenuItemClass)
+        .disabled(move || !enabled)
+        .style(|s| s.width_full().items_center());
+
+        let this = self.clone();
+        let row = row.on_event_stop(EventListener::PointerEnter, move |_| {
+            this.hover(level, index, has_submenu)
+        });
+        let this = self.clone();
+        let row = row.on_click_stop(move |_| this.activate(level, index, false));
+        let this = self.clone();
+        let row = row.on_secondary_click_stop(move |_| this.activate(level, index, false));
+        let row = highlight(row, move || {
+            path.with(|p| p.get(level).copied().flatten() == Some(index))
+        });
+
+        let submenu = match children {
+            Some(children) => self
+                .panel(level + 1, children)
+                .style(move |s| {
+                    let open = path.with(|p| p.len() > level + 1 && p[level] == Some(index));
+                    s.absolute()
+                        .inset_left_pct(100.0)
+                        .inset_top(0.0)
+                        .apply_if(!open, |s| s.hide())
+                })
+                .into_any(),
+            None => empty().into_any(),
+        };
+
+        stack((row, submenu)).style(|s| s.width_full()).into_any()
+    }
+}
+
+pub(crate) enum MenuBarUpdate {
+    /// Open the menu at the index, or close the open menu.
+    Open {
+        index: Option<usize>,
+        from_keyboard: bool,
+    },
+    /// Run the item with the id, or open it if it is a menu of the bar.
+    Activate(u64),
+}
+
+/// A bar of menus drawn inside the window, for windows with a custom title bar or platforms
+/// without a native menu bar. See [`menu_bar`].
+pub struct MenuBar {
+    id: ViewId,
+    child: ViewId,
+    menus: RwSignal<Vec<MenuDisplay>>,
+    actions: Rc<RefCell<HashMap<usize, Rc<dyn Fn()>>>>,
+    open: RwSignal<Option<usize>>,
+    overlay: Rc<RefCell<Option<ViewId>>>,
+    /// The style context of the bar, which the menus opened in overlays are styled with.
+    context: Rc<Style>,
+}
+
+/// A menu bar with the submenus of `menu` as its menus. Entries of `menu` that are items are
+/// shown as buttons in the bar.
+///
+/// The menu is rebuilt when signals read by `menu` change. Item accelerators and the `Alt`
+/// mnemonics of the menus work while the window has focus.
+pub fn menu_bar(menu: impl Fn() -> Menu + 'static) -> MenuBar {
+    let id = ViewId::new();
+    let menus = create_rw_signal(Vec::new());
+    let actions = Rc::new(RefCell::new(HashMap::new()));
+    let open = create_rw_signal(None);
+    let overlay: Rc<RefCell<Option<ViewId>>> = Rc::new(RefCell::new(None));
+
+    {
+        let actions = actions.clone();
+        create_effect(move |_| {
+            let mut menu = menu();
+            let mut new_actions = HashMap::new();
+            let mut accelerators = Vec::new();
+            menu.take_actions(&mut new_actions, &mut accelerators);
+            let entries = format_menu(&menu);
+
+            let mut shortcuts: Vec<(Shortcut, u64)> = accelerators
+                .into_iter()
+                .map(|(shortcut, id)| (shortcut, id as u64))
+                .collect();
+            for entry in &entries {
+                if let MenuDisplay::Item {
+                    id: item_id,
+                    enabled: true,
+                    mnemonic: Some(mnemonic),
+                    ..
+                } = entry
+                {
+                    let stroke =
+                        KeyStroke::new(Key::Character(mnemonic.to_string().into()), Modifiers::ALT);
+                    shortcuts.push((stroke.into(), *item_id));
+                }
+            }
+            id.set_menu_bar_shortcuts(shortcuts);
+
+            *actions.borrow_mut() = new_actions
+                .into_iter()
+                .map(|(id, action)| (id, Rc::from(action)))
+                .collect();
+            id.update_state(MenuBarUpdate::Open {
+                index: None,
+                from_keyboard: false,
+            });
+            menus.set(entries);
+        });
+    }
+
+    let child = dyn_stack(
+        move || menus.get().into_iter().enumerate(),
+        |(index, entry)| (*index, entry.clone()),
+        move |(index, entry)| bar_item(id, index, entry, open),
+    )
+    .style(|s| s.items_center());
+    let child_id = child.id();
+    id.set_children(vec![child.into_any()]);
+
+    MenuBar {
+        id,
+        child: child_id,
+        menus,
+        actions,
+        open,
+        overlay: overlay.clone(),
+        context: Rc::new(Style::new()),
+    }
+    .class(MenuBarClass)
+    .on_cleanup(move || {
+        if let Some(overlay_id) = overlay.borrow_mut().take() {
+            remove_overlay(overlay_id);
+        }
+    })
+}
+
+fn bar_item(
+    bar: ViewId,
+    index: usize,
+    entry: MenuDisplay,
+    open: RwSignal<Option<usize>>,
+) -> impl IntoView {
+    let (item_id, enabled, title, has_menu) = match entry {
+        MenuDisplay::Item {
+            id,
+            enabled,
+            title,
+            children,
+            ..
+        } => (id, enabled, title, children.is_some()),
+        MenuDisplay::Separator(_) => return empty().class(MenuSeparatorClass).into_any(),
+    };
+    let item = text(title)
+        .class(MenuBarItemClass)
+        .disabled(move || !enabled)
+        .on_event_stop(EventListener::PointerDown, move |_| {
+            if !has_menu {
+                return;
+            }
+            let index = (open.get_untracked() != Some(index)).then_some(index);
+            // Set right away, so the focus leaving the menu that was open doesn't close the bar.
+            open.set(index);
+            bar.update_state(MenuBarUpdate::Open {
+                index,
+                from_keyboard: false,
+            });
+        })
+        .on_click_stop(move |_| {
+            if !has_menu {
+                bar.update_state(MenuBarUpdate::Activate(item_id));
+            }
+        })
+        .on_event_cont(EventListener::PointerEnter, move |_| {
+            // Moving over the bar switches menus once one is open.
+            if has_menu && open.get_untracked().is_some_and(|open| open != index) {
+                bar.update_state(MenuBarUpdate::Open {
+                    index: Some(index),
+                    from_keyboard: false,
+                });
+            }
+        });
+    highlight(item, move || open.get() == Some(index)).into_any()
+}
+
+impl MenuBar {
+    fn open_menu(&mut self, index: Option<usize>, from_keyboard: bool) {
+        if let Some(overlay_id) = self.overlay.borrow_mut().take() {
+            remove_overlay(overlay_id);
+        }
+        let entry = index.and_then(|index| {
+            self.menus
+                .with_untracked(|menus| menus.get(index).cloned())
+                .filter(|entry| entry.is_enabled_item() && entry.children().is_some())
+        });
+        let (Some(index), Some(entry)) = (index, entry) else {
+            self.open.set(None);
+            return;
+        };
+        let Some(item) = self.child.children().get(index).copied() else {
+            return;
+        };
+        self.open.set(Some(index));
+
+        let origin = item.state().borrow().window_origin;
+        let height = item.get_size().unwrap_or_default().height;
+        let bar = self.id;
+        let open = self.open;
+        let actions = self.actions.clone();
+        let openable: Vec<usize> = self.menus.with_untracked(|menus| {
+            menus
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.is_enabled_item() && entry.children().is_some())
+                .map(|(i, _)| i)
+                .collect()
+        });
+        let context = self.context.clone();
+        let children = entry.children().unwrap_or_default().to_vec();
+
+        let overlay_id = add_overlay(origin + (0.0, height), move |_| {
+            let popup = MenuPopup::new(
+                children,
+                move |id| {
+                    let action = actions.borrow().get(&(id as usize)).cloned();
+                    if let Some(action) = action {
+                        action();
+                    }
+                },
+                move || {
+                    // Only close if this menu is still the open one, the focus moves away
+                    // from it when another menu of the bar is opened.
+                    if open.get_untracked() == Some(index) {
+                        bar.update_state(MenuBarUpdate::Open {
+                            index: None,
+                            from_keyboard: false,
+                        });
+                    }
+                },
+            )
+            .on_cross(move |right| {
+                let count = openable.len();
+                let position = openable.iter().position(|&i| i == index).unwrap_or(0);
+                let next = if right {
+                    (position + 1) % count
+                } else {
+                    (position + count - 1) % count
+                };
+                bar.update_state(MenuBarUpdate::Open {
+                    index: Some(openable[next]),
+                    from_keyboard: true,
+                });
+            });
+            let popup = if from_keyboard {
+                popup.highlight_first()
+            } else {
+                popup
+            };
+            let view = popup.view().into_view();
+            view.id().request_focus();
+            container(view).style(move |_| (*context).clone())
+        });
+        *self.overlay.borrow_mut() = Some(overlay_id);
+    }
+}
+
+impl View for MenuBar {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "MenuBar".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(update) = state.downcast::<MenuBarUpdate>() {
+            match *update {
+                MenuBarUpdate::Open {
+                    index,
+                    from_keyboard,
+                } => self.open_menu(index, from_keyboard),
+                MenuBarUpdate::Activate(id) => {
+                    let index = self.menus.with_untracked(|menus| {
+                        menus.iter().position(|entry| {
+                            matches!(entry, MenuDisplay::Item { id: menu_id, children: Some(_), .. } if *menu_id == id)
+                        })
+                    });
+                    let action = self.actions.borrow().get(&(id as usize)).cloned();
+                    if let Some(index) = index {
+                        self.open_menu(Some(index), true);
+                    } else if let Some(action) = action {
+                        self.open_menu(None, false);
+                        action();
+                    }
+                }
+            }
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        self.context = cx.current.clone();
+        cx.style_view(self.child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(enabled: bool) -> MenuDisplay {
+        MenuDisplay::Item {
+            id: 0,
+            enabled,
+            title: String::new(),
+            mnemonic: None,
+            checked: None,
+            radio: false,
+            icon: None,
+            accelerator: None,
+            children: None,
+        }
+    }
+
+    #[test]
+    fn mnemonics() {
+        assert_eq!(parse_mnemonic("&File"), ("File".to_string(), Some('f')));
+        assert_eq!(
+            parse_mnemonic("Save &As"),
+            ("Save As".to_string(), Some('a'))
+        );
+        assert_eq!(
+            parse_mnemonic("Find && &Replace"),
+            ("Find & Replace".to_string(), Some('r'))
+        );
+        // only the first marker counts
+        assert_eq!(parse_mnemonic("&Go &To"), ("Go To".to_string(), Some('g')));
+        assert_eq!(parse_mnemonic("Copy&"), ("Copy".to_string(), None));
+        assert_eq!(parse_mnemonic("Help"), ("Help".to_string(), None));
+    }
+
+    #[test]
+    fn skip_disabled_and_separators() {
+        let entries = [
+            item(false),
+            item(true),
+            MenuDisplay::Separator(0),
+            item(false),
+            item(true),
+        ];
+        assert_eq!(next_enabled(&entries, None, true), Some(1));
+        assert_eq!(next_enabled(&entries, None, false), Some(4));
+        assert_eq!(next_enabled(&entries, Some(1), true), Some(4));
+        assert_eq!(next_enabled(&entries, Some(4), false), Some(1));
+        // wraps around
+        assert_eq!(next_enabled(&entries, Some(4), true), Some(1));
+        assert_eq!(next_enabled(&entries, Some(1), false), Some(4));
+        assert_eq!(
+            next_enabled(&[item(false), MenuDisplay::Separator(0)], None, true),
+            None
+        );
+        assert_eq!(next_enabled(&[], None, false), None);
+    }
+}
@@ -5,7 +5,7 @@
 //! ## Composing Views
 //! The views in this module are the main building blocks for composing UIs in Floem.
 //! There is a collection of different `stacks` and `lists` that can be used to build collections of Views.
-//! There are also basic widgets such as [text_inputs](text_input::text_input), [labels](label::label), [images](img::img), and [svgs](svg::svg).
+//! There are also basic widgets such as [text_inputs](text_input::text_input), [text areas](text_area::text_area), [labels](label::label), [images](img::img), and [svgs](svg::svg).
 //! For more widgets see the [widgets module](crate::widgets).
 //!
 //! ## The counter example to show composing views
@@ -28,6 +28,7 @@
 //! The basic stacks and basic lists are static and always contain the same elements in the same order, but the children can still get reactive updates.
 //! The dynamic stack can dynamically change the elements in the stack by reactively updating the list of items provided to the [dyn_stack](dyn_stack()).
 //! Virtual stacks and virtual lists are like the dynamic stack but they also lazily load the items as they appear in a [scroll view](scroll()) and do not support the flexbox nor grid layout algorithms.
+//! [Virtual grids](virtual_grid()) do the same in both directions, for tables of cells or [wrapped galleries](virtual_grid_wrapped()).
 //! Instead, they give every element a consistent size and use a basic layout.
 //! This is done for performance and allows for lists of millions of items to be used with very high performance.
 //!
@@ -75,21 +76,51 @@ pub use virtual_list::*;
 mod virtual_stack;
 pub use virtual_stack::*;
 
+mod virtual_grid;
+pub use virtual_grid::*;
+
 pub mod scroll;
 pub use scroll::{scroll, Scroll};
 
 mod tab;
 pub use tab::*;
 
+mod table;
+pub use table::*;
+
+mod tabs;
+pub use tabs::*;
+
+mod tree_view;
+pub use tree_view::*;
+
 mod tooltip;
 pub use tooltip::*;
 
+mod popover;
+pub use popover::*;
+
+mod menu_bar;
+pub use menu_bar::*;
+
+mod highlight;
+pub(crate) use highlight::highlight;
+
 mod stack;
 pub use stack::*;
 
 mod text_input;
 pub use text_input::*;
 
+mod text_area;
+pub use text_area::*;
+
+mod number_input;
+pub use number_input::*;
+
+mod form;
+pub use form::*;
+
 mod empty;
 pub use empty::*;
 
@@ -0,0 +1,607 @@
+//! A text input for numbers, with buttons to step the value up and down.
+//!
+//! The value also steps with the arrow keys and the mouse wheel while the input is focused, by a
+//! larger step while Shift is held, and with Page Up and Page Down. Text that doesn't parse or is
+//! out of range marks the input as invalid until it's committed with Enter or by leaving the
+//! input, which clamps it to the range or restores the last valid value.
+
+use std::{cell::Cell, fmt::Display, rc::Rc, str::FromStr};
+
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, Role};
+use floem_reactive::{
+    create_effect, create_rw_signal, ReadSignal, RwSignal, SignalGet, SignalUpdate, SignalWith,
+};
+use floem_winit::keyboard::{Key, NamedKey};
+
+use crate::{
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::Modifiers,
+    style_class,
+    view::{IntoView, View},
+    views::{h_stack, svg, text_input, v_stack, Decorators},
+};
+
+style_class!(pub NumberInputClass);
+style_class!(pub NumberInputButtonClass);
+/// Added to the text input of a number input while its text is invalid.
+style_class!(pub NumberInputInvalidClass);
+
+const INCREMENT_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 10l4-4 4 4" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+const DECREMENT_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 6l4 4 4-4" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+
+/// A number that can be edited with a [`number_input`]. It's implemented for the primitive
+/// integer and floating point types.
+pub trait Num: Copy + PartialOrd + Display + FromStr + 'static {
+    const ONE: Self;
+    const TEN: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_sub(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_num_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Num for $ty {
+                const ONE: Self = 1;
+                const TEN: Self = 10;
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$ty>::saturating_sub(self, rhs)
+                }
+
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$ty>::saturating_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_num_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Num for $ty {
+                const ONE: Self = 1.0;
+                const TEN: Self = 10.0;
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+
+                fn saturating_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    self * rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_num_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_num_float!(f32, f64);
+
+/// The separators a number is written with, which differ between locales. The default is a
+/// `.` decimal separator without grouping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    pub group_separator: Option<char>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            group_separator: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// A format such as `NumberFormat::new(',', Some('.'))` for `1.234,5`.
+    pub fn new(decimal_separator: char, group_separator: Option<char>) -> Self {
+        Self {
+            decimal_separator,
+            group_separator,
+        }
+    }
+
+    /// The usual format for a locale given as a language tag such as `de-DE` or `fr`, or the
+    /// English format, `1,234.5`, for languages it doesn't know.
+    ///
+    /// Finding out the locale of the user is left to the application, for example with the
+    /// `sys-locale` crate.
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale.replace('_', "-").to_ascii_lowercase();
+        let (language, region) = match locale.split_once('-') {
+            Some((language, region)) => (language, Some(region)),
+            None => (locale.as_str(), None),
+        };
+        match (language, region) {
+            ("de" | "it", Some("ch")) | ("rm", _) => Self::new('.', Some('’')),
+            ("es", Some("mx" | "us")) | ("pt", Some("ao" | "mz")) => Self::new('.', Some(',')),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "da" | "tr" | "id" | "el" | "ro" | "hr" | "sl",
+                _,
+            ) => Self::new(',', Some('.')),
+            (
+                "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "nn" | "hu"
+                | "bg" | "lt" | "lv" | "et",
+                _,
+            ) => Self::new(',', Some('\u{a0}')),
+            _ => Self::new('.', Some(',')),
+        }
+    }
+
+    /// Writes `value` with `decimals` digits after the decimal separator, or as many as it needs
+    /// if that's `None`. Integers are written without any.
+    pub fn format<T: Num>(&self, value: T, decimals: Option<usize>) -> String {
+        let text = match decimals {
+            Some(decimals) => format!("{value:.decimals$}"),
+            None => value.to_string(),
+        };
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(text) => ("-", text),
+            None => ("", text.as_str()),
+        };
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+
+        let mut formatted = sign.to_string();
+        for (i, c) in integer.chars().enumerate() {
+            if let Some(group_separator) = self.group_separator {
+                if i > 0 && (integer.len() - i) % 3 == 0 {
+                    formatted.push(group_separator);
+                }
+            }
+            formatted.push(c);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+
+    /// Reads a number written in this format, ignoring group separators.
+    pub fn parse<T: Num>(&self, text: &str) -> Option<T> {
+        let text = text.trim();
+        if self.decimal_separator != '.' && text.contains('.') && self.group_separator != Some('.')
+        {
+            return None;
+        }
+        let text: String = text
+            .chars()
+            .filter(|c| Some(*c) != self.group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+        let value = text.parse::<T>().ok()?;
+        // Rejects NaN, which isn't a number anyone means to type
+        value.partial_cmp(&value).map(|_| value)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct NumberOptions<T> {
+    min: T,
+    max: T,
+    step: T,
+    large_step: Option<T>,
+    decimals: Option<usize>,
+    format: NumberFormat,
+}
+
+impl<T: Num> NumberOptions<T> {
+    fn clamp(&self, value: T) -> T {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+
+    /// Parses `text`, if it's a number in range.
+    fn parse(&self, text: &str) -> Option<T> {
+        self.format
+            .parse(text)
+            .filter(|value| *value >= self.min && *value <= self.max)
+    }
+
+    /// The number of decimals the value is shown and rounded with. Without a set number it's as
+    /// many as the step has, so steps of 0.1 show 0.3 and not 0.30000000000000004.
+    fn decimals(&self) -> Option<usize> {
+        self.decimals.or_else(|| {
+            let step = self.step.to_string();
+            step.split_once('.').map(|(_, fraction)| fraction.len())
+        })
+    }
+
+    fn format(&self, value: T) -> String {
+        self.format.format(value, self.decimals())
+    }
+
+    /// Steps `value` up or down by the step, or the large step, staying in range and rounding to
+    /// the number of decimals.
+    fn step(&self, value: T, up: bool, large: bool) -> T {
+        let step = match large {
+            true => self.large_step.unwrap_or(self.step.saturating_mul(T::TEN)),
+            false => self.step,
+        };
+        let value = match up {
+            true => value.saturating_add(step),
+            false => value.saturating_sub(step),
+        };
+        let value = self.clamp(value);
+        match self.decimals() {
+            Some(_) => self.format.parse(&self.format(value)).unwrap_or(value),
+            None => value,
+        }
+    }
+}
+
+/// A text input for numbers. See [`number_input`].
+pub struct NumberInput<T: Num> {
+    id: ViewId,
+    value: RwSignal<T>,
+    text: RwSignal<String>,
+    invalid: RwSignal<bool>,
+    options: Rc<Cell<NumberOptions<T>>>,
+}
+
+/// A text input editing the number in `value`, with buttons to step it up and down.
+///
+/// `value` is set as valid numbers are typed, and the input is marked as invalid while its text
+/// isn't one. The text input then has the [`NumberInputInvalidClass`], which the default theme
+/// shows with a red border.
+///
+/// ```rust
+/// # use floem::reactive::create_rw_signal;
+/// # use floem::views::{number_input, NumberFormat};
+/// let volume = create_rw_signal(0.5);
+/// number_input(volume)
+///     .range(0.0, 1.0)
+///     .step(0.05)
+///     .decimals(2)
+///     .number_format(NumberFormat::new(',', Some('.')));
+/// ```
+pub fn number_input<T: Num>(value: RwSignal<T>) -> NumberInput<T> {
+    let id = ViewId::new();
+    let options = Rc::new(Cell::new(NumberOptions {
+        min: T::MIN,
+        max: T::MAX,
+        step: T::ONE,
+        large_step: None,
+        decimals: None,
+        format: NumberFormat::default(),
+    }));
+    let text = create_rw_signal(options.get().format(value.get_untracked()));
+    let invalid = create_rw_signal(false);
+    let focused = create_rw_signal(false);
+
+    {
+        let options = options.clone();
+        create_effect(move |_| {
+            let value = value.get();
+            let options = options.get();
+            // The text is kept while it reads as the value, such as `1.50` for 1.5
+            if text.with_untracked(|text| options.parse(text) != Some(value)) {
+                text.set(options.format(value));
+            }
+        });
+    }
+    {
+        let options = options.clone();
+        create_effect(move |_| {
+            let parsed = text.with(|text| options.get().parse(text));
+            if invalid.get_untracked() != parsed.is_none() {
+                invalid.set(parsed.is_none());
+            }
+            if let Some(parsed) = parsed {
+                if value.get_untracked() != parsed {
+                    value.set(parsed);
+                }
+            }
+        });
+    }
+
+    // Clamps the text to the range, or restores the value if it isn't a number
+    let commit = {
+        let options = options.clone();
+        move || {
+            let options = options.get();
+            let committed = text
+                .with_untracked(|text| options.format.parse(text))
+                .map_or(value.get_untracked(), |parsed| options.clamp(parsed));
+            if value.get_untracked() != committed {
+                value.set(committed);
+            }
+            text.set(options.format(committed));
+        }
+    };
+    let step = {
+        let options = options.clone();
+        let commit = commit.clone();
+        move |up: bool, large: bool| {
+            commit();
+            value.set(options.get().step(value.get_untracked(), up, large));
+        }
+    };
+
+    let input = {
+        let options = options.clone();
+        let commit = commit.clone();
+        let step = step.clone();
+        let wheel_step = step.clone();
+        text_input(text)
+            .char_filter(move |c| {
+                let format = options.get().format;
+                c.is_ascii_digit()
+                    || matches!(c, '-' | '+')
+                    || c == format.decimal_separator
+                    || Some(c) == format.group_separator
+            })
+            .on_submit(move |_| commit())
+            .on_event_cont(EventListener::FocusGained, move |_| focused.set(true))
+            .on_event_cont(EventListener::FocusLost, move |_| focused.set(false))
+            .on_event(EventListener::KeyDown, move |event| {
+                let Event::KeyDown(event) = event else {
+                    return EventPropagation::Continue;
+                };
+                let large = event.modifiers.contains(Modifiers::SHIFT);
+                match event.key.logical_key {
+                    Key::Named(NamedKey::ArrowUp) => step(true, large),
+                    Key::Named(NamedKey::ArrowDown) => step(false, large),
+                    Key::Named(NamedKey::PageUp) => step(true, true),
+                    Key::Named(NamedKey::PageDown) => step(false, true),
+                    _ => return EventPropagation::Continue,
+                }
+                EventPropagation::Stop
+            })
+            .on_event(EventListener::PointerWheel, move |event| {
+                let Event::PointerWheel(event) = event else {
+                    return EventPropagation::Continue;
+                };
+                // The wheel only steps while focused, so scrolling past the input doesn't
+                // change it
+                if !focused.get_untracked() {
+                    return EventPropagation::Continue;
+                }
+                // Some platforms turn a vertical scroll with Shift held into a horizontal one
+                let delta = if event.delta.y != 0.0 {
+                    event.delta.y
+                } else {
+                    event.delta.x
+                };
+                if delta != 0.0 {
+                    wheel_step(delta < 0.0, event.modifiers.contains(Modifiers::SHIFT));
+                }
+                EventPropagation::Stop
+            })
+            .class_if(move || invalid.get(), NumberInputInvalidClass)
+            .style(|s| s.flex_grow(1.0))
+    };
+    #[cfg(feature = "accessibility")]
+    {
+        let id = input.id();
+        let increment = step.clone();
+        let decrement = step.clone();
+        id.set_accessibility_role(Role::SpinButton);
+        id.add_accessibility_action(Action::Increment, move |_| increment(true, false));
+        id.add_accessibility_action(Action::Decrement, move |_| decrement(false, false));
+    }
+
+    let button = move |icon: &'static str, up: bool| {
+        let step = step.clone();
+        svg(icon)
+            .class(NumberInputButtonClass)
+            .on_click_stop(move |event| {
+                let large = match event {
+                    Event::PointerUp(event) => event.modifiers.contains(Modifiers::SHIFT),
+                    _ => false,
+                };
+                step(up, large);
+            })
+    };
+    let buttons = v_stack((button(INCREMENT_SVG, true), button(DECREMENT_SVG, false)));
+
+    id.set_children(vec![h_stack((input, buttons))
+        .style(|s| s.items_center().width_full())
+        .into_any()]);
+
+    NumberInput {
+        id,
+        value,
+        text,
+        invalid,
+        options,
+    }
+    .class(NumberInputClass)
+}
+
+impl<T: Num> NumberInput<T> {
+    fn set_options(self, f: impl FnOnce(&mut NumberOptions<T>)) -> Self {
+        let mut options = self.options.get();
+        f(&mut options);
+        self.options.set(options);
+        let value = options.clamp(self.value.get_untracked());
+        if self.value.get_untracked() != value {
+            self.value.set(value);
+        }
+        self.text.set(options.format(value));
+        self
+    }
+
+    /// Limits the value to `min..=max`.
+    pub fn range(self, min: T, max: T) -> Self {
+        self.set_options(|options| {
+            options.min = min;
+            options.max = max;
+        })
+    }
+
+    pub fn min(self, min: T) -> Self {
+        self.set_options(|options| options.min = min)
+    }
+
+    pub fn max(self, max: T) -> Self {
+        self.set_options(|options| options.max = max)
+    }
+
+    /// How much the buttons, arrow keys and mouse wheel change the value by. It's 1 by default.
+    pub fn step(self, step: T) -> Self {
+        self.set_options(|options| options.step = step)
+    }
+
+    /// How much the value changes by while Shift is held and with Page Up and Page Down. It's
+    /// ten steps by default.
+    pub fn large_step(self, large_step: T) -> Self {
+        self.set_options(|options| options.large_step = Some(large_step))
+    }
+
+    /// Shows the value with `decimals` digits after the decimal separator, and rounds stepped
+    /// values to them. By default it's as many digits as the step has.
+    pub fn decimals(self, decimals: usize) -> Self {
+        self.set_options(|options| options.decimals = Some(decimals))
+    }
+
+    /// Sets the separators the value is written and read with, for the locale of the user. See
+    /// [`NumberFormat::from_locale`].
+    pub fn number_format(self, format: NumberFormat) -> Self {
+        self.set_options(|options| options.format = format)
+    }
+
+    /// Whether the text isn't a number in range.
+    pub fn invalid(&self) -> ReadSignal<bool> {
+        self.invalid.read_only()
+    }
+}
+
+impl<T: Num> View for NumberInput<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NumberFormat, NumberOptions};
+
+    #[test]
+    fn format_numbers() {
+        let format = NumberFormat::new(',', Some('.'));
+        assert_eq!(format.format(1234567, None), "1.234.567");
+        assert_eq!(format.format(-1234.5, Some(2)), "-1.234,50");
+        assert_eq!(format.format(123, None), "123");
+        assert_eq!(NumberFormat::default().format(0.25, None), "0.25");
+    }
+
+    #[test]
+    fn locale_formats() {
+        assert_eq!(
+            NumberFormat::from_locale("de-DE"),
+            NumberFormat::new(',', Some('.'))
+        );
+        assert_eq!(
+            NumberFormat::from_locale("de_CH"),
+            NumberFormat::new('.', Some('’'))
+        );
+        assert_eq!(
+            NumberFormat::from_locale("fr").format(1234.5, None),
+            "1\u{a0}234,5"
+        );
+        assert_eq!(
+            NumberFormat::from_locale("en-US"),
+            NumberFormat::new('.', Some(','))
+        );
+        assert_eq!(
+            NumberFormat::from_locale("xx"),
+            NumberFormat::new('.', Some(','))
+        );
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let format = NumberFormat::new(',', Some('.'));
+        assert_eq!(format.parse::<f64>("1.234,5"), Some(1234.5));
+        assert_eq!(format.parse::<i32>("-1.234"), Some(-1234));
+        assert_eq!(format.parse::<i32>("12,5"), None);
+        assert_eq!(format.parse::<f64>(""), None);
+        assert_eq!(format.parse::<u8>("300"), None);
+
+        let format = NumberFormat::new(',', None);
+        assert_eq!(format.parse::<f64>("1.5"), None);
+        assert_eq!(NumberFormat::default().parse::<f64>("NaN"), None);
+    }
+
+    #[test]
+    fn step_within_range() {
+        let options = NumberOptions {
+            min: 0.0,
+            max: 1.0,
+            step: 0.1,
+            large_step: None,
+            decimals: Some(1),
+            format: NumberFormat::default(),
+        };
+        assert_eq!(options.step(0.2, true, false), 0.3);
+        assert_eq!(options.step(0.95, true, false), 1.0);
+        assert_eq!(options.step(0.5, false, true), 0.0);
+        assert_eq!(options.parse("1.5"), None);
+        assert_eq!(options.parse("0.5"), Some(0.5));
+
+        let options = NumberOptions {
+            min: u8::MIN,
+            max: u8::MAX,
+            step: 1,
+            large_step: Some(50),
+            decimals: None,
+            format: NumberFormat::default(),
+        };
+        assert_eq!(options.step(250, true, false), 251);
+        assert_eq!(options.step(250, true, true), 255);
+        assert_eq!(options.step(3, false, false), 2);
+        assert_eq!(options.step(0, false, false), 0);
+    }
+
+    #[test]
+    fn round_to_step() {
+        let options = NumberOptions {
+            min: f64::MIN,
+            max: f64::MAX,
+            step: 0.1,
+            large_step: None,
+            decimals: None,
+            format: NumberFormat::default(),
+        };
+        assert_eq!(options.step(0.2, true, false), 0.3);
+        assert_eq!(options.format(0.1 + 0.2), "0.3");
+        assert_eq!(options.format(2.0), "2.0");
+
+        let options = NumberOptions {
+            step: 1.0,
+            ..options
+        };
+        assert_eq!(options.format(0.25), "0.25");
+    }
+}
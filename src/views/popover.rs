@@ -0,0 +1,675 @@
+//! Popovers and modal dialogs, shown in an overlay above the window content.
+//!
+//! A popover is anchored to another view and placed on a preferred side of it. If there isn't
+//! enough room on that side it flips to the opposite one, and it's shifted along the edge of the
+//! anchor to stay inside the window. A dialog is a modal popover centered in the window.
+
+use std::{cell::Cell, rc::Rc};
+
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
+use floem_reactive::{create_effect, create_rw_signal, RwSignal, SignalGet, SignalUpdate};
+use peniko::kurbo::{BezPath, Point, Rect, Size};
+
+use crate::{
+    action::{add_layer, remove_overlay},
+    context::{ComputeLayoutCx, EventCx, PaintCx, StyleCx, UpdateCx},
+    event::{Event, EventPropagation},
+    id::ViewId,
+    style::{Style, StyleClass as _},
+    style_class,
+    view::{default_compute_layout, IntoView, View},
+    views::Decorators,
+};
+
+style_class!(pub PopoverClass);
+style_class!(pub PopoverBackdropClass);
+
+/// The size of the arrow pointing from a popover to its anchor.
+const ARROW_SIZE: f64 = 7.0;
+/// The distance popovers keep from the edges of the window.
+pub(crate) const WINDOW_MARGIN: f64 = 5.0;
+
+/// The side of the anchor a popover is placed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    fn opposite(self) -> Self {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, Side::Top | Side::Bottom)
+    }
+}
+
+/// How a popover is aligned with the edge of the anchor it's placed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+/// Where a popover ends up, in window coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Placement {
+    pub(crate) origin: Point,
+    /// The side of the anchor the popover is on, after flipping.
+    pub(crate) side: Side,
+    /// The position of the arrow along the edge facing the anchor, or `None` without an anchor.
+    pub(crate) arrow: Option<f64>,
+}
+
+/// Moves a span of `len` starting at `pos` inside `min..max`, preferring `min` if it doesn't fit.
+fn shift(pos: f64, len: f64, min: f64, max: f64) -> f64 {
+    pos.min(max - len).max(min)
+}
+
+/// Places a popover of `size` next to `anchor`, keeping it inside `bounds`.
+pub(crate) fn place(
+    anchor: Rect,
+    size: Size,
+    bounds: Rect,
+    side: Side,
+    align: Align,
+    gap: f64,
+) -> Placement {
+    let space = |side| match side {
+        Side::Top => anchor.y0 - bounds.y0,
+        Side::Bottom => bounds.y1 - anchor.y1,
+        Side::Left => anchor.x0 - bounds.x0,
+        Side::Right => bounds.x1 - anchor.x1,
+    };
+    let needed = gap
+        + if side.is_vertical() {
+            size.height
+        } else {
+            size.width
+        };
+    let side = if space(side) < needed && space(side.opposite()) > space(side) {
+        side.opposite()
+    } else {
+        side
+    };
+
+    let main = match side {
+        Side::Top => anchor.y0 - gap - size.height,
+        Side::Bottom => anchor.y1 + gap,
+        Side::Left => anchor.x0 - gap - size.width,
+        Side::Right => anchor.x1 + gap,
+    };
+    let (start, end, len, min, max) = if side.is_vertical() {
+        (anchor.x0, anchor.x1, size.width, bounds.x0, bounds.x1)
+    } else {
+        (anchor.y0, anchor.y1, size.height, bounds.y0, bounds.y1)
+    };
+    let cross = match align {
+        Align::Start => start,
+        Align::Center => (start + end - len) / 2.0,
+        Align::End => end - len,
+    };
+    let cross = shift(cross, len, min, max);
+    let arrow = ((start + end) / 2.0 - cross).clamp(0.0, len);
+
+    let origin = if side.is_vertical() {
+        Point::new(cross, shift(main, size.height, bounds.y0, bounds.y1))
+    } else {
+        Point::new(shift(main, size.width, bounds.x0, bounds.x1), cross)
+    };
+    Placement {
+        origin,
+        side,
+        arrow: Some(arrow),
+    }
+}
+
+/// Places a popover of `size` in the center of `bounds`.
+pub(crate) fn center(size: Size, bounds: Rect) -> Placement {
+    let center = bounds.center();
+    Placement {
+        origin: Point::new(
+            shift(
+                center.x - size.width / 2.0,
+                size.width,
+                bounds.x0,
+                bounds.x1,
+            ),
+            shift(
+                center.y - size.height / 2.0,
+                size.height,
+                bounds.y0,
+                bounds.y1,
+            ),
+        ),
+        side: Side::Bottom,
+        arrow: None,
+    }
+}
+
+/// The state sent to a popover layer to close it.
+pub(crate) struct PopoverDismiss;
+
+#[derive(Clone, Copy)]
+pub(crate) struct PopoverConfig {
+    pub(crate) anchor: Option<ViewId>,
+    pub(crate) side: Side,
+    pub(crate) align: Align,
+    pub(crate) offset: f64,
+    pub(crate) arrow: bool,
+    pub(crate) modal: bool,
+    pub(crate) dismiss_on_click_outside: bool,
+    pub(crate) dismiss_on_escape: bool,
+}
+
+impl PopoverConfig {
+    pub(crate) fn new(anchor: Option<ViewId>) -> Self {
+        Self {
+            anchor,
+            side: Side::Bottom,
+            align: Align::Start,
+            offset: 4.0,
+            arrow: false,
+            modal: false,
+            dismiss_on_click_outside: true,
+            dismiss_on_escape: true,
+        }
+    }
+}
+
+/// The content of a popover, positioned by its layer.
+struct PopoverPanel {
+    id: ViewId,
+    content: ViewId,
+    arrow: bool,
+    placement: Rc<Cell<Option<Placement>>>,
+}
+
+impl View for PopoverPanel {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        let origin = self
+            .placement
+            .get()
+            .map(|placement| placement.origin)
+            .unwrap_or_default();
+        Some(
+            Style::new()
+                .absolute()
+                .inset_left(origin.x)
+                .inset_top(origin.y),
+        )
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Popover Panel".into()
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some(placement) = self.placement.get() else {
+            return;
+        };
+        cx.paint_children(self.id);
+
+        let Some(arrow) = placement.arrow.filter(|_| self.arrow) else {
+            return;
+        };
+        let size = self.content.get_size().unwrap_or_default();
+        let style = self.content.get_combined_style();
+        let style = style.builtin();
+        let edge = if placement.side.is_vertical() {
+            size.width
+        } else {
+            size.height
+        };
+        let arrow = arrow.clamp(ARROW_SIZE, (edge - ARROW_SIZE).max(ARROW_SIZE));
+
+        // The arrow sits on the edge facing the anchor, with its base covering the border.
+        let (base_start, tip, base_end) = match placement.side {
+            Side::Bottom => (
+                Point::new(arrow - ARROW_SIZE, 1.0),
+                Point::new(arrow, 1.0 - ARROW_SIZE),
+                Point::new(arrow + ARROW_SIZE, 1.0),
+            ),
+            Side::Top => (
+                Point::new(arrow - ARROW_SIZE, size.height - 1.0),
+                Point::new(arrow, size.height - 1.0 + ARROW_SIZE),
+                Point::new(arrow + ARROW_SIZE, size.height - 1.0),
+            ),
+            Side::Right => (
+                Point::new(1.0, arrow - ARROW_SIZE),
+                Point::new(1.0 - ARROW_SIZE, arrow),
+                Point::new(1.0, arrow + ARROW_SIZE),
+            ),
+            Side::Left => (
+                Point::new(size.width - 1.0, arrow - ARROW_SIZE),
+                Point::new(size.width - 1.0 + ARROW_SIZE, arrow),
+                Point::new(size.width - 1.0, arrow + ARROW_SIZE),
+            ),
+        };
+        let mut path = BezPath::new();
+        path.move_to(base_start);
+        path.line_to(tip);
+        path.line_to(base_end);
+        path.close_path();
+        if let Some(background) = style.background() {
+            cx.fill(&path, &background, 0.0);
+        }
+        let border = style.border_top().0;
+        if border > 0.0 {
+            let mut edges = BezPath::new();
+            edges.move_to(base_start);
+            edges.line_to(tip);
+            edges.line_to(base_end);
+            cx.stroke(&edges, &style.border_color(), border);
+        }
+    }
+}
+
+/// A view covering the whole window that positions a popover and handles its dismissal.
+pub(crate) struct PopoverLayer {
+    id: ViewId,
+    panel: ViewId,
+    config: PopoverConfig,
+    placement: Rc<Cell<Option<Placement>>>,
+    on_dismiss: Rc<dyn Fn()>,
+}
+
+impl PopoverLayer {
+    pub(crate) fn new(
+        content: impl IntoView,
+        config: PopoverConfig,
+        on_dismiss: impl Fn() + 'static,
+    ) -> Self {
+        let id = ViewId::new();
+        let content = content.into_view();
+        let placement = Rc::new(Cell::new(None));
+        let panel = PopoverPanel {
+            id: ViewId::new(),
+            content: content.id(),
+            arrow: config.arrow && config.anchor.is_some(),
+            placement: placement.clone(),
+        };
+        panel.id.set_children(vec![content]);
+        let panel_id = panel.id;
+        id.set_children(vec![panel]);
+
+        if config.modal {
+            #[cfg(feature = "accessibility")]
+            panel_id.set_accessibility_role(Role::Dialog);
+            panel_id.set_focus_trap(true);
+        }
+        if config.dismiss_on_escape {
+            id.set_dismiss_on_escape();
+        }
+
+        Self {
+            id,
+            panel: panel_id,
+            config,
+            placement,
+            on_dismiss: Rc::new(on_dismiss),
+        }
+    }
+}
+
+impl View for PopoverLayer {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().size_full())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Popover".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if state.is::<PopoverDismiss>() {
+            (self.on_dismiss)();
+        }
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let Some(pos) = event.point() else {
+            return EventPropagation::Continue;
+        };
+        if self.panel.layout_rect().contains(pos) {
+            return EventPropagation::Continue;
+        }
+        if let Event::PointerDown(_) = event {
+            let on_anchor = self
+                .config
+                .anchor
+                .is_some_and(|anchor| anchor.layout_rect().contains(pos));
+            if self.config.dismiss_on_click_outside && !on_anchor {
+                (self.on_dismiss)();
+            }
+        }
+        // Modal popovers block the rest of the window.
+        if self.config.modal {
+            EventPropagation::Stop
+        } else {
+            EventPropagation::Continue
+        }
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let bounds = self
+            .id
+            .get_size()
+            .unwrap_or_default()
+            .to_rect()
+            .inset(-WINDOW_MARGIN);
+        let size = self.panel.get_size().unwrap_or_default();
+        let placement = match self.config.anchor {
+            Some(anchor) => {
+                let gap = self.config.offset + if self.config.arrow { ARROW_SIZE } else { 0.0 };
+                place(
+                    anchor.layout_rect(),
+                    size,
+                    bounds,
+                    self.config.side,
+                    self.config.align,
+                    gap,
+                )
+            }
+            None => center(size, bounds),
+        };
+        if self.placement.get() != Some(placement) {
+            self.placement.set(Some(placement));
+            self.panel.request_style();
+            self.panel.request_layout();
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
+
+/// A popover or dialog. See [`popover`] and [`dialog`].
+pub struct Popover {
+    id: ViewId,
+    open: RwSignal<bool>,
+    content: Rc<dyn Fn() -> Box<dyn View>>,
+    config: PopoverConfig,
+    on_dismiss: Option<Rc<dyn Fn()>>,
+    layer: Rc<Cell<Option<ViewId>>>,
+    panel_style: RwSignal<Style>,
+    backdrop_style: RwSignal<Style>,
+}
+
+/// A popover anchored to the view `anchor`, shown while `open` is true.
+///
+/// The popover view itself takes no space, place it anywhere in the tree that lives as long as
+/// the popover should. It closes and sets `open` to false when the user clicks outside of it or
+/// presses Escape.
+///
+/// ```rust
+/// # use floem::reactive::{create_rw_signal, SignalUpdate};
+/// # use floem::views::{button, popover, stack, text, Side};
+/// # use floem::View;
+/// let open = create_rw_signal(false);
+/// let anchor = button("Options").action(move || open.set(true));
+/// let anchor_id = anchor.id();
+/// stack((
+///     anchor,
+///     popover(anchor_id, open, || text("Some options")).side(Side::Right).arrow(true),
+/// ));
+/// ```
+pub fn popover<V: IntoView + 'static>(
+    anchor: ViewId,
+    open: RwSignal<bool>,
+    content: impl Fn() -> V + 'static,
+) -> Popover {
+    Popover::new(open, content, PopoverConfig::new(Some(anchor)))
+}
+
+/// A modal dialog centered in the window, shown while `open` is true.
+///
+/// A backdrop covers the rest of the window and the keyboard focus is kept inside the dialog.
+pub fn dialog<V: IntoView + 'static>(
+    open: RwSignal<bool>,
+    content: impl Fn() -> V + 'static,
+) -> Popover {
+    Popover::new(
+        open,
+        content,
+        PopoverConfig {
+            modal: true,
+            ..PopoverConfig::new(None)
+        },
+    )
+}
+
+impl Popover {
+    fn new<V: IntoView + 'static>(
+        open: RwSignal<bool>,
+        content: impl Fn() -> V + 'static,
+        config: PopoverConfig,
+    ) -> Self {
+        let id = ViewId::new();
+        let layer = Rc::new(Cell::new(None));
+        create_effect(move |_| {
+            id.update_state(open.get());
+        });
+        Popover {
+            id,
+            open,
+            content: Rc::new(move || content().into_any()),
+            config,
+            on_dismiss: None,
+            layer: layer.clone(),
+            panel_style: create_rw_signal(Style::new()),
+            backdrop_style: create_rw_signal(Style::new()),
+        }
+        .on_cleanup(move || {
+            if let Some(layer) = layer.take() {
+                remove_overlay(layer);
+            }
+        })
+    }
+
+    /// Sets the preferred side of the anchor. The popover flips to the opposite side if there
+    /// isn't enough room. The default is [`Side::Bottom`].
+    pub fn side(mut self, side: Side) -> Self {
+        self.config.side = side;
+        self
+    }
+
+    /// Sets how the popover is aligned with the anchor. The default is [`Align::Start`].
+    pub fn align(mut self, align: Align) -> Self {
+        self.config.align = align;
+        self
+    }
+
+    /// Sets the distance between the popover and the anchor, not counting the arrow.
+    pub fn offset(mut self, offset: f64) -> Self {
+        self.config.offset = offset;
+        self
+    }
+
+    /// Shows an arrow pointing from the popover to the anchor.
+    pub fn arrow(mut self, arrow: bool) -> Self {
+        self.config.arrow = arrow;
+        self
+    }
+
+    /// Makes the popover modal, with a backdrop blocking the rest of the window and the
+    /// keyboard focus kept inside the popover.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.config.modal = modal;
+        self
+    }
+
+    /// Sets whether clicking outside of the popover closes it. The default is true.
+    pub fn dismiss_on_click_outside(mut self, dismiss: bool) -> Self {
+        self.config.dismiss_on_click_outside = dismiss;
+        self
+    }
+
+    /// Sets whether pressing Escape closes the popover. The default is true.
+    pub fn dismiss_on_escape(mut self, dismiss: bool) -> Self {
+        self.config.dismiss_on_escape = dismiss;
+        self
+    }
+
+    /// Calls `on_dismiss` when the user closes the popover by clicking outside or with Escape.
+    pub fn on_dismiss(mut self, on_dismiss: impl Fn() + 'static) -> Self {
+        self.on_dismiss = Some(Rc::new(on_dismiss));
+        self
+    }
+
+    fn show(&mut self) {
+        if self.layer.get().is_some() {
+            return;
+        }
+        let content = self.content.clone();
+        let config = self.config;
+        let open = self.open;
+        let on_dismiss = self.on_dismiss.clone();
+        let panel_style = self.panel_style;
+        let backdrop_style = self.backdrop_style;
+        let layer = add_layer(move |_| {
+            let content = content().style(move |s| s.apply(panel_style.get()));
+            PopoverLayer::new(content, config, move || {
+                open.set(false);
+                if let Some(on_dismiss) = &on_dismiss {
+                    on_dismiss();
+                }
+            })
+            .style(move |s| s.apply_if(config.modal, |s| s.apply(backdrop_style.get())))
+        });
+        self.layer.set(Some(layer));
+    }
+
+    fn hide(&mut self) {
+        if let Some(layer) = self.layer.take() {
+            remove_overlay(layer);
+        }
+    }
+}
+
+impl View for Popover {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().absolute())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Popover".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(open) = state.downcast::<bool>() {
+            if *open {
+                self.show();
+            } else {
+                self.hide();
+            }
+        }
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        self.panel_style.set(
+            Style::new().apply_classes_from_context(&[PopoverClass::class_ref()], &cx.current),
+        );
+        self.backdrop_style.set(
+            Style::new()
+                .apply_classes_from_context(&[PopoverBackdropClass::class_ref()], &cx.current),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rect = Rect::new(0.0, 0.0, 400.0, 300.0);
+
+    #[test]
+    fn places_on_preferred_side() {
+        let anchor = Rect::new(100.0, 100.0, 150.0, 120.0);
+        let placement = place(
+            anchor,
+            Size::new(80.0, 40.0),
+            BOUNDS,
+            Side::Bottom,
+            Align::Start,
+            4.0,
+        );
+        assert_eq!(placement.side, Side::Bottom);
+        assert_eq!(placement.origin, Point::new(100.0, 124.0));
+        assert_eq!(placement.arrow, Some(25.0));
+    }
+
+    #[test]
+    fn flips_when_out_of_room() {
+        let anchor = Rect::new(100.0, 270.0, 150.0, 290.0);
+        let placement = place(
+            anchor,
+            Size::new(80.0, 40.0),
+            BOUNDS,
+            Side::Bottom,
+            Align::Center,
+            4.0,
+        );
+        assert_eq!(placement.side, Side::Top);
+        assert_eq!(placement.origin, Point::new(85.0, 226.0));
+    }
+
+    #[test]
+    fn shifts_inside_bounds() {
+        let anchor = Rect::new(370.0, 100.0, 390.0, 120.0);
+        let placement = place(
+            anchor,
+            Size::new(80.0, 40.0),
+            BOUNDS,
+            Side::Bottom,
+            Align::Start,
+            0.0,
+        );
+        assert_eq!(placement.origin, Point::new(320.0, 120.0));
+        // The arrow still points at the center of the anchor.
+        assert_eq!(placement.arrow, Some(60.0));
+    }
+
+    #[test]
+    fn keeps_side_with_more_room() {
+        let anchor = Rect::new(100.0, 200.0, 150.0, 220.0);
+        let placement = place(
+            anchor,
+            Size::new(80.0, 250.0),
+            BOUNDS,
+            Side::Bottom,
+            Align::Start,
+            0.0,
+        );
+        assert_eq!(placement.side, Side::Top);
+        assert_eq!(placement.origin.y, 0.0);
+    }
+
+    #[test]
+    fn centers_dialog() {
+        let placement = center(Size::new(100.0, 50.0), BOUNDS);
+        assert_eq!(placement.origin, Point::new(150.0, 125.0));
+        assert_eq!(placement.arrow, None);
+    }
+}
@@ -4,7 +4,9 @@ use crate::{
     views::{self, container, empty, h_stack, Decorators},
     IntoView,
 };
-use floem_reactive::{SignalGet, SignalUpdate};
+#[cfg(feature = "accessibility")]
+use accesskit::{Role, Toggled};
+use floem_reactive::{create_memo, Memo, SignalGet, SignalUpdate};
 
 use super::{create_value_container_signals, value_container, ValueContainer};
 
@@ -13,18 +15,41 @@ style_class!(pub RadioButtonDotClass);
 style_class!(pub RadioButtonDotSelectedClass);
 style_class!(pub LabeledRadioButtonClass);
 
-fn radio_button_svg<T>(represented_value: T, actual_value: impl SignalGet<T> + 'static) -> impl View
+fn is_selected<T>(represented_value: T, actual_value: impl SignalGet<T> + 'static) -> Memo<bool>
 where
     T: Eq + PartialEq + Clone + 'static,
 {
-    container(empty().class(RadioButtonDotClass).style(move |s| {
-        s.apply_if(actual_value.get() != represented_value, |s| {
-            s.display(taffy::style::Display::None)
-        })
-    }))
+    create_memo(move |_| actual_value.get() == represented_value)
+}
+
+fn radio_button_svg(selected: Memo<bool>) -> impl View {
+    container(
+        empty().class(RadioButtonDotClass).style(move |s| {
+            s.apply_if(!selected.get(), |s| s.display(taffy::style::Display::None))
+        }),
+    )
     .class(RadioButtonClass)
 }
 
+/// Makes `view` focusable and describes it as a radio button to assistive technologies.
+fn radio_button_view<V: Decorators>(view: V, selected: Memo<bool>) -> V::DV {
+    let view = view.keyboard_navigatable();
+    #[cfg(feature = "accessibility")]
+    {
+        view.id().set_accessibility_role(Role::RadioButton);
+        view.id().add_accessibility_properties(move |node| {
+            node.set_toggled(if selected.get_untracked() {
+                Toggled::True
+            } else {
+                Toggled::False
+            })
+        });
+    }
+    #[cfg(not(feature = "accessibility"))]
+    let _ = selected;
+    view
+}
+
 /// The `RadioButton` struct provides various methods to create and manage radio buttons.
 ///
 /// # Related Functions
@@ -44,12 +69,12 @@ impl RadioButton {
     {
         let (inbound_signal, outbound_signal) = create_value_container_signals(actual_value);
 
+        let selected = is_selected(represented_value.clone(), inbound_signal.read_only());
+
         value_container(
-            radio_button_svg(represented_value.clone(), inbound_signal.read_only())
-                .keyboard_navigatable()
-                .on_click_stop(move |_| {
-                    outbound_signal.set(represented_value.clone());
-                }),
+            radio_button_view(radio_button_svg(selected), selected).on_click_stop(move |_| {
+                outbound_signal.set(represented_value.clone());
+            }),
             move || outbound_signal.get(),
         )
     }
@@ -65,7 +90,8 @@ impl RadioButton {
     where
         T: Eq + PartialEq + Clone + 'static,
     {
-        radio_button_svg(represented_value, actual_value).keyboard_navigatable()
+        let selected = is_selected(represented_value, actual_value);
+        radio_button_view(radio_button_svg(selected), selected)
     }
 
     /// Creates a new radio button with a signal that provides and updates its selected state.
@@ -79,13 +105,11 @@ impl RadioButton {
     where
         T: Eq + PartialEq + Clone + 'static,
     {
-        let cloneable_represented_value = represented_value.clone();
+        let selected = is_selected(represented_value.clone(), actual_value);
 
-        radio_button_svg(cloneable_represented_value.clone(), actual_value)
-            .keyboard_navigatable()
-            .on_click_stop(move |_| {
-                actual_value.set(cloneable_represented_value.clone());
-            })
+        radio_button_view(radio_button_svg(selected), selected).on_click_stop(move |_| {
+            actual_value.set(represented_value.clone());
+        })
     }
 
     /// Creates a new labeled radio button with a closure that determines its selected state.
@@ -102,14 +126,15 @@ impl RadioButton {
     {
         let (inbound_signal, outbound_signal) = create_value_container_signals(actual_value);
 
+        let selected = is_selected(represented_value.clone(), inbound_signal.read_only());
+
         value_container(
-            h_stack((
-                radio_button_svg(represented_value.clone(), inbound_signal.read_only()),
-                views::label(label),
-            ))
-            .class(LabeledRadioButtonClass)
-            .style(|s| s.items_center())
-            .keyboard_navigatable()
+            radio_button_view(
+                h_stack((radio_button_svg(selected), views::label(label)))
+                    .class(LabeledRadioButtonClass)
+                    .style(|s| s.items_center()),
+                selected,
+            )
             .on_click_stop(move |_| {
                 outbound_signal.set(represented_value.clone());
             }),
@@ -129,13 +154,13 @@ impl RadioButton {
     where
         T: Eq + PartialEq + Clone + 'static,
     {
-        h_stack((
-            radio_button_svg(represented_value, actual_value),
-            views::label(label),
-        ))
-        .class(LabeledRadioButtonClass)
-        .style(|s| s.items_center())
-        .keyboard_navigatable()
+        let selected = is_selected(represented_value, actual_value);
+        radio_button_view(
+            h_stack((radio_button_svg(selected), views::label(label)))
+                .class(LabeledRadioButtonClass)
+                .style(|s| s.items_center()),
+            selected,
+        )
     }
 
     /// Creates a new labeled radio button with a signal that provides and updates its selected state.
@@ -150,17 +175,16 @@ impl RadioButton {
     where
         T: Eq + PartialEq + Clone + 'static,
     {
-        let cloneable_represented_value = represented_value.clone();
-
-        h_stack((
-            radio_button_svg(cloneable_represented_value.clone(), actual_value),
-            views::label(label),
-        ))
-        .class(LabeledRadioButtonClass)
-        .style(|s| s.items_center())
-        .keyboard_navigatable()
+        let selected = is_selected(represented_value.clone(), actual_value);
+
+        radio_button_view(
+            h_stack((radio_button_svg(selected), views::label(label)))
+                .class(LabeledRadioButtonClass)
+                .style(|s| s.items_center()),
+            selected,
+        )
         .on_click_stop(move |_| {
-            actual_value.set(cloneable_represented_value.clone());
+            actual_value.set(represented_value.clone());
         })
     }
 }
@@ -3,6 +3,12 @@ use floem_renderer::Renderer;
 use peniko::kurbo::{Point, Rect, Size, Vec2};
 use peniko::{Brush, Color};
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+#[cfg(target_arch = "wasm32")]
+use web_time::Duration;
+
+use crate::action::exec_after;
 use crate::style::CustomStylable;
 use crate::unit::PxPct;
 use crate::{
@@ -19,18 +25,25 @@ use crate::{
 
 use super::Decorators;
 
-enum ScrollState {
+pub(crate) enum ScrollState {
     EnsureVisible(Rect),
     ScrollDelta(Vec2),
     ScrollTo(Point),
     ScrollToPercent(f32),
     ScrollToView(ViewId),
+    AutoScroll,
 }
 
 /// Minimum length for any scrollbar to be when measured on that
 /// scrollbar's primary axis.
 const SCROLLBAR_MIN_SIZE: f64 = 10.0;
 
+/// Distance from the edges of the scroll view in which a drag scrolls the content.
+const AUTO_SCROLL_EDGE: f64 = 32.0;
+/// Scroll distance per auto-scroll step when the pointer is at the very edge.
+const AUTO_SCROLL_MAX_STEP: f64 = 16.0;
+const AUTO_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
 /// Denotes which scrollbar, if any, is currently being dragged.
 #[derive(Debug, Copy, Clone)]
 enum BarHeldState {
@@ -99,6 +112,9 @@ pub struct Scroll {
 
     onscroll: Option<Box<dyn Fn(Rect)>>,
     held: BarHeldState,
+    /// The scroll step per interval while something is dragged near the edges.
+    auto_scroll: Vec2,
+    auto_scroll_scheduled: bool,
     v_handle_hover: bool,
     h_handle_hover: bool,
     v_track_hover: bool,
@@ -126,6 +142,8 @@ pub fn scroll<V: IntoView + 'static>(child: V) -> Scroll {
         computed_child_viewport: Rect::ZERO,
         onscroll: None,
         held: BarHeldState::None,
+        auto_scroll: Vec2::ZERO,
+        auto_scroll_scheduled: false,
         v_handle_hover: false,
         h_handle_hover: false,
         v_track_hover: false,
@@ -547,6 +565,41 @@ impl Scroll {
         }
     }
 
+    /// The auto-scroll step for a drag at `pos`, which grows as the pointer gets closer to an edge.
+    fn auto_scroll_step(&self, pos: Point) -> Vec2 {
+        fn step(pos: f64, min: f64, max: f64) -> f64 {
+            let edge = AUTO_SCROLL_EDGE.min((max - min) / 2.0);
+            if edge <= 0.0 {
+                0.0
+            } else if pos < min + edge {
+                -AUTO_SCROLL_MAX_STEP * ((min + edge - pos) / edge).min(1.0)
+            } else if pos > max - edge {
+                AUTO_SCROLL_MAX_STEP * ((pos - (max - edge)) / edge).min(1.0)
+            } else {
+                0.0
+            }
+        }
+        let rect = self.actual_rect;
+        let mut delta = Vec2::new(step(pos.x, rect.x0, rect.x1), step(pos.y, rect.y0, rect.y1));
+        if self.child_size.width <= rect.width() {
+            delta.x = 0.0;
+        }
+        if self.child_size.height <= rect.height() {
+            delta.y = 0.0;
+        }
+        delta
+    }
+
+    fn schedule_auto_scroll(&mut self) {
+        if self.auto_scroll != Vec2::ZERO && !self.auto_scroll_scheduled {
+            self.auto_scroll_scheduled = true;
+            let id = self.id;
+            exec_after(AUTO_SCROLL_INTERVAL, move |_| {
+                id.update_state(ScrollState::AutoScroll);
+            });
+        }
+    }
+
     /// true if either scrollbar is currently held down/being dragged
     fn are_bars_held(&self) -> bool {
         !matches!(self.held, BarHeldState::None)
@@ -654,6 +707,15 @@ impl View for Scroll {
                 ScrollState::ScrollToView(id) => {
                     self.do_scroll_to_view(cx.app_state, id, None);
                 }
+                ScrollState::AutoScroll => {
+                    self.auto_scroll_scheduled = false;
+                    if !cx.app_state.is_dragging() {
+                        self.auto_scroll = Vec2::ZERO;
+                        return;
+                    }
+                    self.do_scroll_delta(cx.app_state, self.auto_scroll);
+                    self.schedule_auto_scroll();
+                }
             }
             self.id.request_layout();
         }
@@ -761,6 +823,7 @@ impl View for Scroll {
                 }
             }
             Event::PointerUp(_event) => {
+                self.auto_scroll = Vec2::ZERO;
                 if self.are_bars_held() {
                     self.held = BarHeldState::None;
                     // Force a repaint.
@@ -768,6 +831,10 @@ impl View for Scroll {
                 }
             }
             Event::PointerMove(event) => {
+                if cx.app_state.is_dragging() {
+                    self.auto_scroll = self.auto_scroll_step(event.pos);
+                    self.schedule_auto_scroll();
+                }
                 if !self.scroll_style.hide_bar() {
                     let pos = event.pos + scroll_offset;
                     self.update_hover_states(cx.app_state, event.pos);
@@ -802,6 +869,7 @@ impl View for Scroll {
                 }
             }
             Event::PointerLeave => {
+                self.auto_scroll = Vec2::ZERO;
                 self.v_handle_hover = false;
                 self.h_handle_hover = false;
                 self.v_track_hover = false;
@@ -1,5 +1,7 @@
 //! A toggle button widget. An example can be found in widget-gallery/button in the floem examples.
 
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, ActionData, NodeBuilder, Role};
 use floem_reactive::{create_effect, SignalGet, SignalUpdate};
 use floem_renderer::Renderer;
 use floem_winit::keyboard::{Key, NamedKey};
@@ -23,6 +25,13 @@ pub fn slider(percent: impl Fn() -> f32 + 'static) -> Slider {
 
 enum SliderUpdate {
     Percent(f32),
+    /// A change requested by an assistive technology, which is handled like a change by the
+    /// user.
+    #[cfg(feature = "accessibility")]
+    Change(f32),
+    /// A step up, or down if `false`, requested by an assistive technology.
+    #[cfg(feature = "accessibility")]
+    Step(bool),
 }
 
 prop!(pub EdgeAlign: bool {} = false);
@@ -102,6 +111,7 @@ pub struct Slider {
     held: bool,
     percent: f32,
     prev_percent: f32,
+    step: f32,
     base_bar_style: BarStyle,
     accent_bar_style: BarStyle,
     handle: Circle,
@@ -120,11 +130,26 @@ impl View for Slider {
         if let Ok(update) = state.downcast::<SliderUpdate>() {
             match *update {
                 SliderUpdate::Percent(percent) => self.percent = percent,
+                #[cfg(feature = "accessibility")]
+                SliderUpdate::Change(percent) => self.change(percent),
+                #[cfg(feature = "accessibility")]
+                SliderUpdate::Step(up) => {
+                    let step = if up { self.step } else { -self.step };
+                    self.change(self.percent + step)
+                }
             }
             self.id.request_layout();
         }
     }
 
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_numeric_value(self.percent as f64);
+        node.set_min_numeric_value(0.0);
+        node.set_max_numeric_value(100.0);
+        node.set_numeric_value_step(self.step as f64);
+    }
+
     fn event_before_children(
         &mut self,
         cx: &mut crate::context::EventCx,
@@ -166,11 +191,11 @@ impl View for Slider {
             crate::event::Event::KeyDown(event) => {
                 if event.key.logical_key == Key::Named(NamedKey::ArrowLeft) {
                     self.id.request_layout();
-                    self.percent -= 10.;
+                    self.percent -= self.step;
                     true
                 } else if event.key.logical_key == Key::Named(NamedKey::ArrowRight) {
                     self.id.request_layout();
-                    self.percent += 10.;
+                    self.percent += self.step;
                     true
                 } else {
                     false
@@ -182,12 +207,7 @@ impl View for Slider {
         self.update_restrict_position();
 
         if pos_changed && self.percent != self.prev_percent {
-            if let Some(onchangepx) = &self.onchangepx {
-                onchangepx(self.handle_center());
-            }
-            if let Some(onchangepct) = &self.onchangepct {
-                onchangepct(self.percent)
-            }
+            self.notify_change();
         }
 
         EventPropagation::Continue
@@ -310,6 +330,21 @@ impl Slider {
             let percent = percent();
             id.update_state(SliderUpdate::Percent(percent));
         });
+        #[cfg(feature = "accessibility")]
+        {
+            id.set_accessibility_role(Role::Slider);
+            id.add_accessibility_action(Action::SetValue, move |data| {
+                if let Some(ActionData::NumericValue(value)) = data {
+                    id.update_state(SliderUpdate::Change(*value as f32));
+                }
+            });
+            id.add_accessibility_action(Action::Increment, move |_| {
+                id.update_state(SliderUpdate::Step(true));
+            });
+            id.add_accessibility_action(Action::Decrement, move |_| {
+                id.update_state(SliderUpdate::Step(false));
+            });
+        }
         Slider {
             id,
             onchangepx: None,
@@ -317,6 +352,7 @@ impl Slider {
             held: false,
             percent: 0.0,
             prev_percent: 0.0,
+            step: 10.0,
             handle: Default::default(),
             base_bar_style: Default::default(),
             accent_bar_style: Default::default(),
@@ -343,6 +379,25 @@ impl Slider {
         Self::new(move || percent.get()).on_change_pct(move |pct| percent.set(pct))
     }
 
+    fn notify_change(&self) {
+        if let Some(onchangepx) = &self.onchangepx {
+            onchangepx(self.handle_center());
+        }
+        if let Some(onchangepct) = &self.onchangepct {
+            onchangepct(self.percent)
+        }
+    }
+
+    #[cfg(feature = "accessibility")]
+    fn change(&mut self, percent: f32) {
+        let old_percent = self.percent;
+        self.percent = percent;
+        self.update_restrict_position();
+        if self.percent != old_percent {
+            self.notify_change();
+        }
+    }
+
     fn update_restrict_position(&mut self) {
         self.percent = self.percent.clamp(0., 100.);
     }
@@ -365,6 +420,13 @@ impl Slider {
         self
     }
 
+    /// Sets the percent that the arrow keys and assistive technologies move the slider by. The
+    /// default is 10.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
     /// Sets the custom style properties of the `Slider`.
     pub fn slider_style(
         self,
@@ -1,5 +1,7 @@
 use std::{hash::Hash, marker::PhantomData};
 
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
 use floem_reactive::{as_child_of_current_scope, create_effect, Scope};
 use smallvec::SmallVec;
 use taffy::style::Display;
@@ -47,6 +49,9 @@ where
     T: 'static,
 {
     let id = ViewId::new();
+    // Only the active child is visible, so the view is the panel of the active tab.
+    #[cfg(feature = "accessibility")]
+    id.set_accessibility_role(Role::TabPanel);
 
     create_effect(move |prev_hash_run| {
         let items = each_fn();
@@ -0,0 +1,1080 @@
+//! A table of records with typed columns, such as a spreadsheet or a list of database rows.
+//!
+//! Only the rows and columns inside the viewport get a view, so the table scales to large data
+//! sets. The header stays at the top while the rows scroll and the first columns can be frozen
+//! so they stay at the left.
+//!
+//! Columns can be resized by dragging the right edge of their header, reordered by dragging the
+//! header and sorted by clicking it. Cells are selected with the pointer or the arrow keys, with
+//! Shift extending the selection, and Ctrl+C (Cmd+C on macOS) copies the selection as tab
+//! separated values. Editable cells are edited with Enter, F2 or a double click.
+
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+    rc::Rc,
+};
+
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
+use floem_reactive::{
+    create_effect, create_memo, create_rw_signal, RwSignal, SignalGet, SignalUpdate, SignalWith,
+};
+use peniko::kurbo::Rect;
+
+use crate::{
+    context::{EventCx, StyleCx},
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    keyboard::{Key, Modifiers, NamedKey},
+    prop, prop_extractor,
+    style::Style,
+    style_class,
+    view::{AnyView, IntoView, View},
+    views::{
+        clip, dyn_container, dyn_stack, empty, h_stack, highlight, label, scroll, text, text_input,
+        virtual_stack, Decorators, Stack, VirtualDirection, VirtualItemSize, VirtualVector,
+    },
+    Clipboard,
+};
+
+style_class!(pub TableClass);
+style_class!(pub TableHeaderClass);
+style_class!(pub TableHeaderCellClass);
+style_class!(pub TableResizeHandleClass);
+style_class!(pub TableRowClass);
+style_class!(pub TableCellClass);
+style_class!(pub TableCellEditorClass);
+style_class!(pub TableFrozenClass);
+
+prop!(pub TableRowHeight: f64 {} = 24.0);
+
+prop_extractor! {
+    TableRowStyle {
+        row_height: TableRowHeight,
+    }
+}
+
+/// A column of a [`table`], showing a value of each row.
+pub struct Column<T: 'static> {
+    title: String,
+    width: f64,
+    min_width: f64,
+    sortable: bool,
+    resizable: bool,
+    text: Box<dyn Fn(&T) -> String>,
+    compare: Box<dyn Fn(&T, &T) -> Ordering>,
+    cell: Option<Box<dyn Fn(&T) -> AnyView>>,
+    on_edit: Option<Box<dyn Fn(&mut T, String)>>,
+}
+
+impl<T: 'static> Column<T> {
+    /// A column titled `title` showing the value returned by `value` for each row.
+    ///
+    /// The value is displayed as text, copied with the selection and used to sort the column.
+    /// Columns are identified by their title, so titles should be unique within a table.
+    pub fn new<V: Display + PartialOrd + 'static>(
+        title: impl Into<String>,
+        value: impl Fn(&T) -> V + 'static,
+    ) -> Self {
+        let value = Rc::new(value);
+        let text_value = value.clone();
+        Self {
+            title: title.into(),
+            width: 120.0,
+            min_width: 30.0,
+            sortable: true,
+            resizable: true,
+            text: Box::new(move |row| text_value(row).to_string()),
+            compare: Box::new(move |a, b| {
+                value(a).partial_cmp(&value(b)).unwrap_or(Ordering::Equal)
+            }),
+            cell: None,
+            on_edit: None,
+        }
+    }
+
+    /// Sets the initial width of the column. The default is 120 pixels.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the width the column can't be resized below. The default is 30 pixels.
+    pub fn min_width(mut self, min_width: f64) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Sets whether clicking the header sorts the table by this column. The default is true.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Sets whether the column can be resized by dragging the edge of its header. The default
+    /// is true.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Shows the cells of the column with the views built by `view_fn` instead of the value as
+    /// text.
+    pub fn cell<V: IntoView + 'static>(mut self, view_fn: impl Fn(&T) -> V + 'static) -> Self {
+        self.cell = Some(Box::new(move |row| view_fn(row).into_any()));
+        self
+    }
+
+    /// Makes the cells of the column editable with a text input, starting from the value as
+    /// text. `on_edit` is called with the row and the entered text when the edit is committed
+    /// with Enter. Moving the focus away or pressing Escape cancels the edit.
+    pub fn editable(mut self, on_edit: impl Fn(&mut T, String) + 'static) -> Self {
+        self.on_edit = Some(Box::new(on_edit));
+        self
+    }
+}
+
+/// The column a [`table`] is sorted by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableSort {
+    /// The title of the column.
+    pub column: String,
+    pub descending: bool,
+}
+
+/// The position of a cell of a [`table`], in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CellPos {
+    /// The index of the row after sorting.
+    pub row: usize,
+    /// The index of the column after reordering.
+    pub column: usize,
+}
+
+/// A rectangular range of selected cells of a [`table`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellRange {
+    /// The cell the selection started from.
+    pub anchor: CellPos,
+    /// The cell the selection was extended to, which has the keyboard focus.
+    pub focus: CellPos,
+}
+
+impl CellRange {
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.row.min(self.focus.row)..=self.anchor.row.max(self.focus.row)
+    }
+
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.anchor.column.min(self.focus.column)..=self.anchor.column.max(self.focus.column)
+    }
+
+    pub fn contains(&self, pos: CellPos) -> bool {
+        self.rows().contains(&pos.row) && self.columns().contains(&pos.column)
+    }
+}
+
+/// The data indices of the rows in display order, sorted with `compare`.
+fn sorted_order(
+    len: usize,
+    compare: impl Fn(usize, usize) -> Ordering,
+    descending: bool,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    // The sort is stable, so equal rows keep their order in both directions.
+    order.sort_by(|&a, &b| {
+        let ordering = compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    order
+}
+
+/// Which columns, in display order, get a view.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ColumnSpans {
+    /// The number of frozen columns.
+    frozen: usize,
+    frozen_width: f64,
+    /// The scrolling columns inside the viewport.
+    visible: Range<usize>,
+    /// The width before the first visible column, including the frozen columns.
+    leading: f64,
+    total: f64,
+}
+
+fn column_spans(widths: &[f64], frozen: usize, x0: f64, width: f64) -> ColumnSpans {
+    let frozen = frozen.min(widths.len());
+    let frozen_width: f64 = widths[..frozen].iter().sum();
+    // The frozen columns cover the start of the viewport.
+    let (min, max) = (x0 + frozen_width, x0 + width);
+    let mut start = None;
+    let mut end = widths.len();
+    let mut x = frozen_width;
+    for (i, &w) in widths.iter().enumerate().skip(frozen) {
+        if x >= max {
+            end = i;
+            break;
+        }
+        if start.is_none() && x + w > min {
+            start = Some((i, x));
+        }
+        x += w;
+    }
+    let (start, leading) = start.unwrap_or((end, x));
+    ColumnSpans {
+        frozen,
+        frozen_width,
+        visible: start..end,
+        leading,
+        total: widths.iter().sum(),
+    }
+}
+
+/// Joins cells into tab separated values, replacing tabs and line breaks inside cells.
+fn tsv(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.replace(['\t', '\n', '\r'], " "))
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Clone)]
+struct TableItem<T> {
+    display: usize,
+    data: usize,
+    revision: u64,
+    item: T,
+}
+
+struct TableItems<T: 'static> {
+    order: Rc<Vec<usize>>,
+    rows: RwSignal<Vec<T>>,
+    revision: u64,
+}
+
+impl<T: Clone + 'static> VirtualVector<TableItem<T>> for TableItems<T> {
+    fn total_len(&self) -> usize {
+        self.order.len()
+    }
+
+    fn slice(&mut self, range: Range<usize>) -> impl Iterator<Item = TableItem<T>> {
+        let end = range.end.min(self.order.len());
+        let start = range.start.min(end);
+        let items: Vec<_> = self.rows.with_untracked(|rows| {
+            self.order[start..end]
+                .iter()
+                .enumerate()
+                .filter_map(|(i, &data)| {
+                    rows.get(data).map(|item| TableItem {
+                        display: start + i,
+                        data,
+                        revision: self.revision,
+                        item: item.clone(),
+                    })
+                })
+                .collect()
+        });
+        items.into_iter()
+    }
+}
+
+/// The payload of a column header being dragged.
+struct ColumnDrag(usize);
+
+struct TableCore<T: 'static> {
+    id: ViewId,
+    rows: RwSignal<Vec<T>>,
+    columns: Vec<Column<T>>,
+    /// The widths of the columns, in definition order.
+    widths: Vec<RwSignal<f64>>,
+    /// The columns in display order.
+    column_order: RwSignal<Vec<usize>>,
+    frozen: RwSignal<usize>,
+    sort: RwSignal<Option<TableSort>>,
+    /// The data indices of the rows in display order, with a revision that changes whenever the
+    /// rows or their order change.
+    order: RwSignal<(u64, Rc<Vec<usize>>)>,
+    spans: RwSignal<ColumnSpans>,
+    viewport: RwSignal<Rect>,
+    row_height: RwSignal<f64>,
+    selection: RwSignal<Option<CellRange>>,
+    /// The data row and column of the cell being edited.
+    editing: RwSignal<Option<(usize, usize)>>,
+    reorderable: Cell<bool>,
+    body: Cell<Option<ViewId>>,
+}
+
+impl<T: Clone + 'static> TableCore<T> {
+    fn row_count(&self) -> usize {
+        self.order.with_untracked(|(_, order)| order.len())
+    }
+
+    /// The widths of the columns in display order.
+    fn display_widths(&self) -> Vec<f64> {
+        self.column_order
+            .with(|order| order.iter().map(|&c| self.widths[c].get()).collect())
+    }
+
+    fn display_of(&self, column: usize) -> usize {
+        self.column_order
+            .with(|order| order.iter().position(|&c| c == column))
+            .unwrap_or(column)
+    }
+
+    fn frozen_columns(&self) -> Vec<usize> {
+        self.column_order
+            .with(|order| self.spans.with(|spans| order[..spans.frozen].to_vec()))
+    }
+
+    fn visible_columns(&self) -> Vec<usize> {
+        self.column_order.with(|order| {
+            self.spans
+                .with(|spans| order[spans.visible.clone()].to_vec())
+        })
+    }
+
+    /// The data row and column of a cell.
+    fn cell(&self, pos: CellPos) -> Option<(usize, usize)> {
+        let row = self
+            .order
+            .with_untracked(|(_, order)| order.get(pos.row).copied())?;
+        let column = self
+            .column_order
+            .with_untracked(|order| order.get(pos.column).copied())?;
+        Some((row, column))
+    }
+
+    fn cell_text(&self, pos: CellPos) -> Option<String> {
+        let (row, column) = self.cell(pos)?;
+        self.rows
+            .with_untracked(|rows| rows.get(row).map(|row| (self.columns[column].text)(row)))
+    }
+
+    fn select(&self, pos: CellPos, extend: bool) {
+        let anchor = match self.selection.get_untracked() {
+            Some(selection) if extend => selection.anchor,
+            _ => pos,
+        };
+        self.selection.set(Some(CellRange { anchor, focus: pos }));
+    }
+
+    fn scroll_to_cell(&self, pos: CellPos) {
+        let Some(body) = self.body.get() else {
+            return;
+        };
+        let height = self.row_height.get_untracked();
+        let y = pos.row as f64 * height;
+        let viewport = self.viewport.get_untracked();
+        let spans = self.spans.get_untracked();
+        let (x0, x1) = if pos.column < spans.frozen {
+            // Frozen columns are always in view horizontally.
+            (viewport.x0, viewport.x0)
+        } else {
+            let widths = self.column_order.with_untracked(|order| {
+                order
+                    .iter()
+                    .map(|&c| self.widths[c].get_untracked())
+                    .collect::<Vec<_>>()
+            });
+            let x: f64 = widths[..pos.column].iter().sum();
+            // Keep the cell clear of the frozen columns.
+            (x - spans.frozen_width, x + widths[pos.column])
+        };
+        body.scroll_to(Some(Rect::new(x0, y, x1, y + height)));
+    }
+
+    fn start_edit(&self, pos: CellPos) {
+        if let Some((row, column)) = self.cell(pos) {
+            if self.columns[column].on_edit.is_some() {
+                self.editing.set(Some((row, column)));
+            }
+        }
+    }
+
+    fn commit_edit(&self, text: String) {
+        let Some((row, column)) = self.editing.get_untracked() else {
+            return;
+        };
+        self.editing.set(None);
+        if let Some(on_edit) = &self.columns[column].on_edit {
+            self.rows.update(|rows| {
+                if let Some(row) = rows.get_mut(row) {
+                    on_edit(row, text);
+                }
+            });
+        }
+        self.id.request_focus();
+    }
+
+    fn copy_selection(&self) {
+        let Some(selection) = self.selection.get_untracked() else {
+            return;
+        };
+        let rows: Vec<Vec<String>> = selection
+            .rows()
+            .map(|row| {
+                selection
+                    .columns()
+                    .filter_map(|column| self.cell_text(CellPos { row, column }))
+                    .collect()
+            })
+            .collect();
+        let _ = Clipboard::set_contents(tsv(&rows));
+    }
+
+    /// Sorts by the column, ascending then descending then unsorted.
+    fn toggle_sort(&self, column: usize) {
+        let title = &self.columns[column].title;
+        self.sort.update(|sort| {
+            *sort = match sort.take() {
+                Some(sort) if sort.column == *title && !sort.descending => Some(TableSort {
+                    column: title.clone(),
+                    descending: true,
+                }),
+                Some(sort) if sort.column == *title => None,
+                _ => Some(TableSort {
+                    column: title.clone(),
+                    descending: false,
+                }),
+            };
+        });
+    }
+
+    /// Moves a column to the display position of another.
+    fn move_column(&self, column: usize, to: usize) {
+        if column == to {
+            return;
+        }
+        self.column_order.update(|order| {
+            if let (Some(from), Some(to)) = (
+                order.iter().position(|&c| c == column),
+                order.iter().position(|&c| c == to),
+            ) {
+                let column = order.remove(from);
+                order.insert(to, column);
+            }
+        });
+    }
+
+    fn key_down(&self, key: &Key, modifiers: Modifiers) -> bool {
+        let rows = self.row_count();
+        let columns = self.columns.len();
+        if rows == 0 || columns == 0 {
+            return false;
+        }
+        let cmd = modifiers.control() || modifiers.meta();
+        let focus = self.selection.get_untracked().map(|s| s.focus);
+        let current = focus.unwrap_or(CellPos { row: 0, column: 0 });
+        let page = (self.viewport.get_untracked().height() / self.row_height.get_untracked())
+            .floor()
+            .max(1.0) as usize;
+        let target = match key {
+            Key::Named(NamedKey::ArrowUp) => CellPos {
+                row: current.row.saturating_sub(1),
+                ..current
+            },
+            Key::Named(NamedKey::ArrowDown) => CellPos {
+                row: (current.row + 1).min(rows - 1),
+                ..current
+            },
+            Key::Named(NamedKey::ArrowLeft) => CellPos {
+                column: current.column.saturating_sub(1),
+                ..current
+            },
+            Key::Named(NamedKey::ArrowRight) => CellPos {
+                column: (current.column + 1).min(columns - 1),
+                ..current
+            },
+            Key::Named(NamedKey::PageUp) => CellPos {
+                row: current.row.saturating_sub(page),
+                ..current
+            },
+            Key::Named(NamedKey::PageDown) => CellPos {
+                row: (current.row + page).min(rows - 1),
+                ..current
+            },
+            Key::Named(NamedKey::Home) if cmd => CellPos { row: 0, column: 0 },
+            Key::Named(NamedKey::Home) => CellPos {
+                column: 0,
+                ..current
+            },
+            Key::Named(NamedKey::End) if cmd => CellPos {
+                row: rows - 1,
+                column: columns - 1,
+            },
+            Key::Named(NamedKey::End) => CellPos {
+                column: columns - 1,
+                ..current
+            },
+            Key::Named(NamedKey::Enter | NamedKey::F2) => {
+                let Some(focus) = focus else {
+                    return false;
+                };
+                self.start_edit(focus);
+                return true;
+            }
+            Key::Character(c) if cmd && c.eq_ignore_ascii_case("c") => {
+                self.copy_selection();
+                return true;
+            }
+            Key::Character(c) if cmd && c.eq_ignore_ascii_case("a") => {
+                self.selection.set(Some(CellRange {
+                    anchor: CellPos { row: 0, column: 0 },
+                    focus: CellPos {
+                        row: rows - 1,
+                        column: columns - 1,
+                    },
+                }));
+                return true;
+            }
+            _ => return false,
+        };
+        self.select(target, modifiers.shift());
+        self.scroll_to_cell(target);
+        true
+    }
+}
+
+/// A table. See [`table`].
+pub struct Table<T: 'static> {
+    id: ViewId,
+    core: Rc<TableCore<T>>,
+    style: TableRowStyle,
+}
+
+/// A table showing `rows` with the given columns.
+///
+/// Editing a cell updates `rows`, while sorting and reordering columns only change how they're
+/// displayed.
+///
+/// ```rust
+/// # use floem::reactive::create_rw_signal;
+/// # use floem::views::{table, Column};
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let people = create_rw_signal(vec![
+///     Person { name: "Ada".into(), age: 36 },
+///     Person { name: "Alan".into(), age: 41 },
+/// ]);
+/// table(
+///     people,
+///     vec![
+///         Column::new("Name", |p: &Person| p.name.clone())
+///             .width(200.0)
+///             .editable(|p, name| p.name = name),
+///         Column::new("Age", |p: &Person| p.age),
+///     ],
+/// )
+/// .frozen_columns(1);
+/// ```
+pub fn table<T: Clone + 'static>(rows: RwSignal<Vec<T>>, columns: Vec<Column<T>>) -> Table<T> {
+    let id = ViewId::new();
+    let widths = columns
+        .iter()
+        .map(|column| create_rw_signal(column.width))
+        .collect();
+    let core = Rc::new(TableCore {
+        id,
+        rows,
+        widths,
+        column_order: create_rw_signal((0..columns.len()).collect()),
+        columns,
+        frozen: create_rw_signal(0),
+        sort: create_rw_signal(None),
+        order: create_rw_signal((0, Rc::new(Vec::new()))),
+        spans: create_rw_signal(ColumnSpans::default()),
+        viewport: create_rw_signal(Rect::ZERO),
+        row_height: create_rw_signal(TableRowHeight::default_value()),
+        selection: create_rw_signal(None),
+        editing: create_rw_signal(None),
+        reorderable: Cell::new(true),
+        body: Cell::new(None),
+    });
+
+    {
+        let core = core.clone();
+        create_effect(move |_| {
+            let sort = core.sort.get();
+            let order = core.rows.with(|rows| {
+                let column = sort.as_ref().and_then(|sort| {
+                    core.columns
+                        .iter()
+                        .position(|c| c.sortable && c.title == sort.column)
+                });
+                match (column, &sort) {
+                    (Some(c), Some(sort)) => sorted_order(
+                        rows.len(),
+                        |a, b| (core.columns[c].compare)(&rows[a], &rows[b]),
+                        sort.descending,
+                    ),
+                    _ => (0..rows.len()).collect(),
+                }
+            });
+            core.order.update(|(revision, old)| {
+                *revision += 1;
+                *old = Rc::new(order);
+            });
+        });
+    }
+    {
+        let core = core.clone();
+        create_effect(move |_| {
+            let widths = core.display_widths();
+            let viewport = core.viewport.get();
+            let spans = column_spans(&widths, core.frozen.get(), viewport.x0, viewport.width());
+            if core.spans.with_untracked(|old| *old != spans) {
+                core.spans.set(spans);
+            }
+        });
+    }
+
+    let header = {
+        let header_core = core.clone();
+        let viewport = core.viewport;
+        clip(
+            cells_row(
+                &core,
+                Rc::new(move |column| header_cell(&header_core, column)),
+            )
+            .style(move |s| s.inset_left(-viewport.get().x0)),
+        )
+        .class(TableHeaderClass)
+        .style(|s| s.width_full().flex_shrink(0.0))
+    };
+
+    let body = {
+        let core = core.clone();
+        let row_core = core.clone();
+        let row_height = core.row_height;
+        let spans = core.spans;
+        virtual_stack(
+            VirtualDirection::Vertical,
+            VirtualItemSize::Fixed(Box::new(move || row_height.get())),
+            move || {
+                let (revision, order) = core.order.get();
+                TableItems {
+                    order,
+                    rows: core.rows,
+                    revision,
+                }
+            },
+            // Rows are rebuilt when the data or the order changes.
+            |row: &TableItem<T>| (row.data, row.revision),
+            move |row| {
+                let core = row_core.clone();
+                let row = Rc::new(row);
+                let row = cells_row(
+                    &row_core,
+                    Rc::new(move |column| body_cell(&core, &row, column)),
+                )
+                .class(TableRowClass);
+                #[cfg(feature = "accessibility")]
+                row.id().set_accessibility_role(Role::Row);
+                row.style(move |s| s.height(row_height.get()))
+            },
+        )
+        .style(move |s| s.flex_col().min_width(spans.with(|s| s.total)))
+    };
+    core.body.set(Some(body.id()));
+    let viewport = core.viewport;
+    let body = scroll(body)
+        .on_scroll(move |rect| viewport.set(rect))
+        .style(|s| s.width_full().flex_grow(1.0).min_height(0.0));
+
+    id.set_children(vec![header.into_any(), body.into_any()]);
+    #[cfg(feature = "accessibility")]
+    id.set_accessibility_role(Role::Grid);
+
+    Table {
+        id,
+        core,
+        style: Default::default(),
+    }
+    .class(TableClass)
+    .keyboard_navigatable()
+}
+
+/// A row of cells laid out like the columns, with views only for the frozen and visible
+/// columns.
+fn cells_row<T: Clone + 'static>(
+    core: &Rc<TableCore<T>>,
+    cell_fn: Rc<dyn Fn(usize) -> AnyView>,
+) -> Stack {
+    let spans = core.spans;
+    let viewport = core.viewport;
+    let spacer = empty().style(move |s| {
+        s.width(spans.with(|s| s.leading))
+            .height_full()
+            .flex_shrink(0.0)
+    });
+    let visible = {
+        let core = core.clone();
+        let cell_fn = cell_fn.clone();
+        dyn_stack(
+            move || core.visible_columns(),
+            |column| *column,
+            move |column| cell_fn(column),
+        )
+        .style(|s| s.flex_row().height_full())
+    };
+    // The frozen columns come last so they're painted over the scrolled columns.
+    let frozen = {
+        let core = core.clone();
+        dyn_stack(
+            move || core.frozen_columns(),
+            |column| *column,
+            move |column| cell_fn(column),
+        )
+        .class(TableFrozenClass)
+        .style(move |s| {
+            s.flex_row()
+                .absolute()
+                .inset_top(0.0)
+                .inset_left(viewport.get().x0)
+                .height_full()
+                .apply_if(spans.with(|s| s.frozen == 0), |s| s.hide())
+        })
+    };
+    h_stack((spacer, visible, frozen)).style(move |s| s.width(spans.with(|s| s.total)))
+}
+
+fn header_cell<T: Clone + 'static>(core: &Rc<TableCore<T>>, column: usize) -> AnyView {
+    let definition = &core.columns[column];
+    let width = core.widths[column];
+    let min_width = definition.min_width;
+    let sort = core.sort;
+    let title = definition.title.clone();
+    let indicator = label(move || {
+        sort.with(|sort| match sort {
+            Some(sort) if sort.column == title && sort.descending => "▼",
+            Some(sort) if sort.column == title => "▲",
+            _ => "",
+        })
+    });
+
+    // The pointer position when the resize started, in window coordinates, and the width of
+    // the column at that time.
+    let resize_start: Rc<Cell<Option<(f64, f64)>>> = Rc::new(Cell::new(None));
+    let resizing = Rc::new(Cell::new(false));
+    let handle = empty().class(TableResizeHandleClass);
+    let handle_id = handle.id();
+    let handle = handle
+        .on_event(EventListener::PointerDown, {
+            let resizing = resizing.clone();
+            let resize_start = resize_start.clone();
+            move |_| {
+                resizing.set(true);
+                resize_start.set(None);
+                handle_id.request_active();
+                EventPropagation::Stop
+            }
+        })
+        .on_event(EventListener::PointerMove, {
+            let resizing = resizing.clone();
+            move |e| {
+                let Event::PointerMove(e) = e else {
+                    return EventPropagation::Continue;
+                };
+                if !resizing.get() {
+                    return EventPropagation::Continue;
+                }
+                let x = handle_id.layout_rect().x0 + e.pos.x;
+                match resize_start.get() {
+                    Some((start_x, start_width)) => {
+                        width.set((start_width + x - start_x).max(min_width));
+                    }
+                    None => resize_start.set(Some((x, width.get_untracked()))),
+                }
+                EventPropagation::Stop
+            }
+        })
+        .on_event(EventListener::PointerUp, move |_| {
+            if resizing.replace(false) {
+                EventPropagation::Stop
+            } else {
+                EventPropagation::Continue
+            }
+        })
+        .style({
+            let resizable = definition.resizable;
+            move |s| {
+                s.absolute()
+                    .inset_top(0.0)
+                    .inset_right(0.0)
+                    .height_full()
+                    .apply_if(!resizable, |s| s.hide())
+            }
+        });
+
+    let cell = h_stack((text(&definition.title), indicator, handle)).class(TableHeaderCellClass);
+    #[cfg(feature = "accessibility")]
+    cell.id().set_accessibility_role(Role::ColumnHeader);
+    let cell = cell
+        .on_click_stop({
+            let core = core.clone();
+            move |_| {
+                if core.columns[column].sortable {
+                    core.toggle_sort(column);
+                }
+            }
+        })
+        .style(move |s| s.width(width.get()).flex_shrink(0.0).height_full());
+    if core.reorderable.get() {
+        let core = core.clone();
+        cell.drag_data(move || ColumnDrag(column))
+            .on_drop_data(move |drag: Rc<ColumnDrag>, _| core.move_column(drag.0, column))
+            .into_any()
+    } else {
+        cell.into_any()
+    }
+}
+
+fn body_cell<T: Clone + 'static>(
+    core: &Rc<TableCore<T>>,
+    row: &Rc<TableItem<T>>,
+    column: usize,
+) -> AnyView {
+    let width = core.widths[column];
+    let data = row.data;
+    let display_row = row.display;
+    let editing = {
+        let editing = core.editing;
+        create_memo(move |_| editing.get() == Some((data, column)))
+    };
+    let content = {
+        let core = core.clone();
+        let row = row.clone();
+        dyn_container(
+            move || editing.get(),
+            move |is_editing| {
+                let definition = &core.columns[column];
+                if is_editing {
+                    cell_editor(&core, (definition.text)(&row.item))
+                } else if let Some(cell) = &definition.cell {
+                    cell(&row.item)
+                } else {
+                    text((definition.text)(&row.item)).into_any()
+                }
+            },
+        )
+        .style(|s| s.width_full().height_full().items_center())
+    };
+
+    let cell = content.class(TableCellClass);
+    #[cfg(feature = "accessibility")]
+    cell.id().set_accessibility_role(Role::Cell);
+    let cell = cell
+        .on_event_cont(EventListener::PointerDown, {
+            let core = core.clone();
+            move |e| {
+                let Event::PointerDown(e) = e else {
+                    return;
+                };
+                let pos = CellPos {
+                    row: display_row,
+                    column: core.display_of(column),
+                };
+                core.select(pos, e.modifiers.shift());
+            }
+        })
+        .on_double_click_stop({
+            let core = core.clone();
+            move |_| {
+                core.start_edit(CellPos {
+                    row: display_row,
+                    column: core.display_of(column),
+                })
+            }
+        })
+        .style(|s| s.width_full().height_full());
+
+    let core = core.clone();
+    highlight(cell, move || {
+        let pos = CellPos {
+            row: display_row,
+            column: core.display_of(column),
+        };
+        core.selection
+            .with(|selection| selection.map_or(false, |selection| selection.contains(pos)))
+    })
+    .style(move |s| s.width(width.get()).flex_shrink(0.0).height_full())
+    .into_any()
+}
+
+fn cell_editor<T: Clone + 'static>(core: &Rc<TableCore<T>>, initial: String) -> AnyView {
+    let buffer = create_rw_signal(initial);
+    let editing = core.editing;
+    let input = text_input(buffer)
+        .class(TableCellEditorClass)
+        .on_event(EventListener::KeyDown, {
+            let core = core.clone();
+            move |e| match e {
+                Event::KeyDown(e) if e.key.logical_key == Key::Named(NamedKey::Enter) => {
+                    core.commit_edit(buffer.get_untracked());
+                    EventPropagation::Stop
+                }
+                _ => EventPropagation::Continue,
+            }
+        })
+        .on_event_cont(EventListener::FocusLost, move |_| {
+            // Moving the focus away, including with Escape, cancels the edit.
+            if editing.get_untracked().is_some() {
+                editing.set(None);
+            }
+        })
+        .style(|s| s.width_full().height_full());
+    input.id().request_focus();
+    input.into_any()
+}
+
+impl<T: Clone + 'static> Table<T> {
+    /// Keeps the sorting of the table in sync with `sort`, so it can be set programmatically
+    /// and observed.
+    pub fn sort_state(self, sort: RwSignal<Option<TableSort>>) -> Self {
+        let internal = self.core.sort;
+        create_effect(move |_| {
+            let sort = sort.get();
+            if internal.with_untracked(|internal| *internal != sort) {
+                internal.set(sort);
+            }
+        });
+        create_effect(move |_| {
+            let internal = internal.get();
+            if sort.with_untracked(|sort| *sort != internal) {
+                sort.set(internal);
+            }
+        });
+        self
+    }
+
+    /// Freezes the first `count` columns, so they stay in view when the table scrolls
+    /// horizontally.
+    pub fn frozen_columns(self, count: usize) -> Self {
+        self.core.frozen.set(count);
+        self
+    }
+
+    /// Sets whether columns can be reordered by dragging their header. The default is true.
+    pub fn reorderable_columns(self, reorderable: bool) -> Self {
+        self.core.reorderable.set(reorderable);
+        self
+    }
+
+    /// The selected cells.
+    pub fn selection(&self) -> RwSignal<Option<CellRange>> {
+        self.core.selection
+    }
+
+    /// The indices of the columns, as passed to [`table`], in display order.
+    pub fn column_order(&self) -> RwSignal<Vec<usize>> {
+        self.core.column_order
+    }
+}
+
+impl<T: Clone + 'static> View for Table<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Table".into()
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.style.read(cx) && self.core.row_height.get_untracked() != self.style.row_height() {
+            self.core.row_height.set(self.style.row_height());
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        if let Event::KeyDown(e) = event {
+            if self.core.key_down(&e.key.logical_key, e.modifiers) {
+                return EventPropagation::Stop;
+            }
+        }
+        EventPropagation::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{column_spans, sorted_order, tsv, CellPos, CellRange};
+
+    #[test]
+    fn sort_is_stable_in_both_directions() {
+        let values = [2, 1, 2, 0];
+        let compare = |a: usize, b: usize| values[a].cmp(&values[b]);
+        assert_eq!(sorted_order(4, compare, false), vec![3, 1, 0, 2]);
+        assert_eq!(sorted_order(4, compare, true), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn spans_cover_the_viewport() {
+        let widths = [100.0, 50.0, 50.0, 50.0, 50.0];
+        // Unscrolled, the columns up to the right edge of the viewport are visible.
+        let spans = column_spans(&widths, 0, 0.0, 120.0);
+        assert_eq!(spans.visible, 0..2);
+        assert_eq!(spans.leading, 0.0);
+        assert_eq!(spans.total, 300.0);
+        // Scrolled, the columns to the left are skipped.
+        let spans = column_spans(&widths, 0, 160.0, 100.0);
+        assert_eq!(spans.visible, 2..5);
+        assert_eq!(spans.leading, 150.0);
+    }
+
+    #[test]
+    fn frozen_columns_hide_the_columns_under_them() {
+        let widths = [100.0, 50.0, 50.0, 50.0, 50.0];
+        let spans = column_spans(&widths, 1, 60.0, 160.0);
+        assert_eq!(spans.frozen, 1);
+        assert_eq!(spans.frozen_width, 100.0);
+        // The viewport shows 160..220 next to the frozen column.
+        assert_eq!(spans.visible, 2..4);
+        assert_eq!(spans.leading, 150.0);
+        // A viewport narrower than the frozen columns shows no other column.
+        assert_eq!(column_spans(&widths, 1, 0.0, 80.0).visible.len(), 0);
+    }
+
+    #[test]
+    fn ranges_are_normalized() {
+        let range = CellRange {
+            anchor: CellPos { row: 4, column: 1 },
+            focus: CellPos { row: 2, column: 3 },
+        };
+        assert_eq!(range.rows(), 2..=4);
+        assert_eq!(range.columns(), 1..=3);
+        assert!(range.contains(CellPos { row: 3, column: 2 }));
+        assert!(!range.contains(CellPos { row: 1, column: 2 }));
+    }
+
+    #[test]
+    fn tsv_escapes_separators() {
+        let rows = vec![
+            vec!["a".to_string(), "b\tc".to_string()],
+            vec!["d\ne".to_string(), String::new()],
+        ];
+        assert_eq!(tsv(&rows), "a\tb c\nd e\t");
+    }
+}
@@ -0,0 +1,415 @@
+//! A tabbed container with a strip of tab headers above the content of the active tab.
+//!
+//! The headers can be closed with their close button or a middle click and reordered by dragging
+//! them. When they don't fit, the strip scrolls and a button at its end lists the hidden tabs.
+//! Ctrl+Tab and Ctrl+Shift+Tab switch between the tabs while the focus is inside the container.
+
+use std::{
+    cell::{Cell, RefCell},
+    hash::Hash,
+    rc::Rc,
+};
+
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
+use floem_reactive::{
+    create_effect, create_rw_signal, RwSignal, SignalGet, SignalUpdate, SignalWith,
+};
+
+use crate::{
+    context::UpdateCx,
+    event::{Event, EventListener, EventPropagation},
+    id::ViewId,
+    style::Style,
+    style_class,
+    view::{IntoView, View},
+    views::{
+        container, dyn_stack, h_stack, highlight, popover, scroll, svg, tab, v_stack_from_iter,
+        Align, Decorators,
+    },
+};
+
+style_class!(pub TabsClass);
+style_class!(pub TabBarClass);
+style_class!(pub TabHeaderClass);
+style_class!(pub TabCloseClass);
+style_class!(pub TabOverflowClass);
+style_class!(pub TabOverflowItemClass);
+
+const CLOSE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 4l8 8M12 4l-8 8" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+const OVERFLOW_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 6l4 4 4-4" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+
+pub(crate) enum TabsUpdate {
+    /// Activate the next tab, or the previous one if `backwards`, wrapping around at the ends.
+    Cycle { backwards: bool },
+}
+
+/// The payload of a tab header being dragged.
+struct TabDrag<K>(K);
+
+struct TabsOptions<T> {
+    closable: Cell<bool>,
+    reorderable: Cell<bool>,
+    on_close: RefCell<Option<Box<dyn Fn(T)>>>,
+}
+
+/// A tabbed container. See [`tabs`].
+pub struct Tabs<T: 'static> {
+    id: ViewId,
+    items: RwSignal<Vec<T>>,
+    active: RwSignal<usize>,
+    options: Rc<TabsOptions<T>>,
+}
+
+/// A tabbed container showing the items of `items` as tabs, with the one at `active` selected.
+///
+/// `header_fn` builds the content of a tab header, such as its title and icon, and `view_fn`
+/// the content shown while the tab is active. Like [`tab`], the content views are kept alive
+/// while their tab is open and identified by `key_fn`.
+///
+/// Closing a tab removes it from `items` and reordering moves it, keeping `active` on the same
+/// tab where possible.
+///
+/// ```rust
+/// # use floem::reactive::create_rw_signal;
+/// # use floem::views::{tabs, text};
+/// let items = create_rw_signal(vec!["main.rs", "lib.rs", "Cargo.toml"]);
+/// let active = create_rw_signal(0);
+/// tabs(items, active, |name| *name, |name| text(name), |name| text(format!("Editing {name}")))
+///     .on_close(|name| println!("closed {name}"));
+/// ```
+pub fn tabs<T, KF, K, HF, H, VF, V>(
+    items: RwSignal<Vec<T>>,
+    active: RwSignal<usize>,
+    key_fn: KF,
+    header_fn: HF,
+    view_fn: VF,
+) -> Tabs<T>
+where
+    T: Clone + 'static,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + Clone + 'static,
+    HF: Fn(&T) -> H + 'static,
+    H: IntoView + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let key_fn = Rc::new(key_fn);
+    let header_fn = Rc::new(header_fn);
+    let options = Rc::new(TabsOptions {
+        closable: Cell::new(true),
+        reorderable: Cell::new(true),
+        on_close: RefCell::new(None),
+    });
+
+    let position = {
+        let key_fn = key_fn.clone();
+        move |key: &K| items.with_untracked(|items| items.iter().position(|i| key_fn(i) == *key))
+    };
+
+    let headers = {
+        let key_fn = key_fn.clone();
+        let header_fn = header_fn.clone();
+        let options = options.clone();
+        dyn_stack(
+            move || items.get(),
+            {
+                let key_fn = key_fn.clone();
+                move |item| key_fn(item)
+            },
+            move |item| {
+                let key = key_fn(&item);
+                let is_active = {
+                    let key_fn = key_fn.clone();
+                    let key = key.clone();
+                    move || {
+                        let index = items.with(|items| items.iter().position(|i| key_fn(i) == key));
+                        index == Some(active.get())
+                    }
+                };
+                let index = {
+                    let position = position.clone();
+                    let key = key.clone();
+                    move || position(&key)
+                };
+
+                let close_button = {
+                    let index = index.clone();
+                    let options = options.clone();
+                    svg(CLOSE_SVG)
+                        .class(TabCloseClass)
+                        .on_click_stop(move |_| {
+                            if let Some(index) = index() {
+                                close_tab(items, active, index, &options);
+                            }
+                        })
+                        .style({
+                            let options = options.clone();
+                            move |s| s.apply_if(!options.closable.get(), |s| s.hide())
+                        })
+                };
+
+                let header = h_stack((header_fn(&item), close_button))
+                    .class(TabHeaderClass)
+                    .keyboard_navigatable();
+                #[cfg(feature = "accessibility")]
+                let header = header.accessibility_role(Role::Tab).accessibility({
+                    let is_active = is_active.clone();
+                    move |node| node.set_selected(is_active())
+                });
+                let header = header
+                    .on_click_stop({
+                        let index = index.clone();
+                        move |_| {
+                            if let Some(index) = index() {
+                                active.set(index);
+                            }
+                        }
+                    })
+                    .on_event(EventListener::PointerDown, {
+                        let index = index.clone();
+                        let options = options.clone();
+                        move |e| match (e, index()) {
+                            (Event::PointerDown(e), Some(index))
+                                if e.button.is_auxiliary() && options.closable.get() =>
+                            {
+                                close_tab(items, active, index, &options);
+                                EventPropagation::Stop
+                            }
+                            _ => EventPropagation::Continue,
+                        }
+                    });
+                let header = if options.reorderable.get() {
+                    let position = position.clone();
+                    header.drag_data(move || TabDrag(key.clone())).on_drop_data(
+                        move |drag: Rc<TabDrag<K>>, _| {
+                            if let (Some(from), Some(to)) = (position(&drag.0), index()) {
+                                move_tab(items, active, from, to);
+                            }
+                        },
+                    )
+                } else {
+                    header
+                };
+                let header_id = header.id();
+
+                // Keep the active tab in view when it changes.
+                let is_active_tab = is_active.clone();
+                create_effect(move |_| {
+                    if is_active_tab() {
+                        header_id.scroll_to(None);
+                    }
+                });
+
+                highlight(header, is_active)
+            },
+        )
+        .roving_focus()
+        .style(|s| s.flex_row())
+    };
+    let headers_id = headers.id();
+    #[cfg(feature = "accessibility")]
+    headers_id.set_accessibility_role(Role::TabList);
+
+    let content_width = create_rw_signal(0.0);
+    let viewport_width = create_rw_signal(0.0);
+    let strip = scroll(headers.on_resize(move |rect| content_width.set(rect.width())))
+        .on_resize(move |rect| viewport_width.set(rect.width()))
+        .scroll_style(|s| s.hide_bars(true).vertical_scroll_as_horizontal(true))
+        .style(|s| s.flex_grow(1.0).min_width(0.0));
+    let strip_id = strip.id();
+
+    let overflow_open = create_rw_signal(false);
+    let overflow_button = svg(OVERFLOW_SVG)
+        .class(TabOverflowClass)
+        .keyboard_navigatable()
+        .on_click_stop(move |_| overflow_open.set(true))
+        .style(move |s| {
+            s.apply_if(content_width.get() <= viewport_width.get() + 0.5, |s| {
+                s.hide()
+            })
+        });
+    #[cfg(feature = "accessibility")]
+    let overflow_button = overflow_button
+        .accessibility_role(Role::Button)
+        .accessibility_label(|| "Show hidden tabs");
+    let overflow_list = popover(overflow_button.id(), overflow_open, move || {
+        // The tabs whose header is scrolled out of view, even partially.
+        let viewport = strip_id.layout_rect();
+        let hidden = items
+            .get_untracked()
+            .into_iter()
+            .zip(headers_id.children())
+            .enumerate()
+            .filter(|(_, (_, header))| {
+                let rect = header.layout_rect();
+                rect.x0 < viewport.x0 - 0.5 || rect.x1 > viewport.x1 + 0.5
+            })
+            .map(|(index, (item, _))| {
+                container(header_fn(&item))
+                    .class(TabOverflowItemClass)
+                    .keyboard_navigatable()
+                    .on_click_stop(move |_| {
+                        active.set(index);
+                        overflow_open.set(false);
+                    })
+            });
+        v_stack_from_iter(hidden)
+    })
+    .align(Align::End);
+
+    let bar = h_stack((strip, overflow_button, overflow_list))
+        .class(TabBarClass)
+        .style(|s| s.width_full().items_center());
+
+    let content = tab(
+        move || active.get(),
+        move || items.get(),
+        move |item| key_fn(item),
+        view_fn,
+    )
+    .style(|s| s.flex_grow(1.0).min_height(0.0).width_full());
+
+    id.set_children(vec![bar.into_any(), content.into_any()]);
+    id.set_cycles_tabs();
+
+    Tabs {
+        id,
+        items,
+        active,
+        options,
+    }
+    .class(TabsClass)
+}
+
+impl<T: 'static> Tabs<T> {
+    /// Sets whether tabs can be closed with their close button or a middle click. The default
+    /// is true.
+    pub fn closable(self, closable: bool) -> Self {
+        self.options.closable.set(closable);
+        self
+    }
+
+    /// Sets whether tabs can be reordered by dragging their header. The default is true.
+    pub fn reorderable(self, reorderable: bool) -> Self {
+        self.options.reorderable.set(reorderable);
+        self
+    }
+
+    /// Calls `on_close` with each tab the user closes, after it's removed from the items.
+    pub fn on_close(self, on_close: impl Fn(T) + 'static) -> Self {
+        *self.options.on_close.borrow_mut() = Some(Box::new(on_close));
+        self
+    }
+}
+
+impl<T: 'static> View for Tabs<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().flex_col())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tabs".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(state) = state.downcast::<TabsUpdate>() {
+            match *state {
+                TabsUpdate::Cycle { backwards } => {
+                    let len = self.items.with_untracked(|items| items.len());
+                    if len > 0 {
+                        self.active.update(|active| {
+                            *active = if backwards {
+                                (*active + len - 1) % len
+                            } else {
+                                (*active + 1) % len
+                            };
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The index of the active tab after the tab at `closed` is removed, leaving `len` tabs.
+fn active_after_close(active: usize, closed: usize, len: usize) -> usize {
+    if closed < active || active >= len {
+        active.saturating_sub(1)
+    } else {
+        active
+    }
+}
+
+/// The index of the active tab after the tab at `from` is moved to `to`.
+fn active_after_move(active: usize, from: usize, to: usize) -> usize {
+    if active == from {
+        to
+    } else if from < active && active <= to {
+        active - 1
+    } else if to <= active && active < from {
+        active + 1
+    } else {
+        active
+    }
+}
+
+fn close_tab<T: 'static>(
+    items: RwSignal<Vec<T>>,
+    active: RwSignal<usize>,
+    index: usize,
+    options: &TabsOptions<T>,
+) {
+    let Some(item) = items
+        .try_update(|items| (index < items.len()).then(|| items.remove(index)))
+        .flatten()
+    else {
+        return;
+    };
+    let len = items.with_untracked(|items| items.len());
+    active.update(|active| *active = active_after_close(*active, index, len));
+    if let Some(on_close) = options.on_close.borrow().as_ref() {
+        on_close(item);
+    }
+}
+
+fn move_tab<T: 'static>(items: RwSignal<Vec<T>>, active: RwSignal<usize>, from: usize, to: usize) {
+    if from == to {
+        return;
+    }
+    items.update(|items| {
+        let item = items.remove(from);
+        items.insert(to, item);
+    });
+    active.update(|active| *active = active_after_move(*active, from, to));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{active_after_close, active_after_move};
+
+    #[test]
+    fn close_keeps_active_tab() {
+        // Closing a tab before the active one shifts it left.
+        assert_eq!(active_after_close(2, 0, 3), 1);
+        // Closing a tab after it changes nothing.
+        assert_eq!(active_after_close(1, 2, 3), 1);
+        // Closing the active tab activates the next one, or the previous one at the end.
+        assert_eq!(active_after_close(1, 1, 3), 1);
+        assert_eq!(active_after_close(2, 2, 2), 1);
+        assert_eq!(active_after_close(0, 0, 0), 0);
+    }
+
+    #[test]
+    fn move_keeps_active_tab() {
+        assert_eq!(active_after_move(1, 1, 3), 3);
+        assert_eq!(active_after_move(2, 0, 3), 1);
+        assert_eq!(active_after_move(2, 3, 0), 3);
+        assert_eq!(active_after_move(0, 2, 3), 0);
+    }
+}
@@ -0,0 +1,863 @@
+use crate::action::exec_after;
+use crate::context::{ComputeLayoutCx, EventCx, LayoutCx, PaintCx, StyleCx, UpdateCx};
+use crate::event::{Event, EventPropagation};
+use crate::id::ViewId;
+use crate::keyboard::{KeyEvent, Modifiers};
+use crate::pointer::{PointerButton, PointerInputEvent};
+use crate::style::{FontProps, SelectionStyle, Style, TextColor};
+use crate::text::{Attrs, AttrsList, FamilyOwned, LayoutGlyph, TextLayout};
+use crate::unit::PxPctAuto;
+use crate::view::View;
+use crate::{prop_extractor, style_class, Clipboard};
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, ActionData, NodeBuilder, Role};
+use floem_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate, SignalWith};
+use floem_renderer::Renderer;
+use floem_winit::keyboard::{Key, NamedKey, SmolStr};
+use peniko::kurbo::{Point, Rect};
+use peniko::Color;
+use taffy::tree::NodeId;
+use unicode_segmentation::UnicodeSegmentation;
+
+use std::{any::Any, ops::Range};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use super::{
+    get_dbl_click_selection, get_word_based_motion, is_submit_shortcut, is_text_editing_key,
+    limit_insertion, replace_range, Decorators, Direction, DragTick, History, Movement,
+    TextCommand, DRAG_TICK_INTERVAL_MS,
+};
+
+style_class!(pub TextAreaClass);
+
+prop_extractor! {
+    Extractor {
+        color: TextColor,
+    }
+}
+
+const DEFAULT_FONT_SIZE: f32 = 14.0;
+const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
+/// Approximately how many characters wide the text area is when the width is not set in the
+/// styles.
+const DEFAULT_COLUMNS: f32 = 20.0;
+/// How many lines high the text area is at least when the height is not set in the styles.
+const DEFAULT_ROWS: f32 = 3.0;
+
+/// A multi-line plain text editing view. See [`text_area`].
+pub struct TextArea {
+    id: ViewId,
+    buffer: RwSignal<String>,
+    // The text as last set by the text area, to tell its own edits from outside changes
+    last_buffer: String,
+    text_layout: TextLayout,
+    text_node: Option<NodeId>,
+    // The width the text is wrapped to
+    wrap_width: Option<f32>,
+    cursor: usize,
+    // The other end of the selection, which is empty when this is the cursor
+    anchor: usize,
+    // The horizontal position kept while moving up and down through shorter lines
+    preferred_x: Option<f64>,
+    // Whether to scroll the cursor into view after the next layout
+    reveal_cursor: bool,
+    viewport: Rect,
+    history: History,
+    max_length: Option<usize>,
+    read_only: Option<Box<dyn Fn() -> bool>>,
+    on_submit: Option<Box<dyn Fn(&str)>>,
+    // The pointer position in the window while dragging a selection, which stays put while
+    // the text scrolls under it
+    drag_pos: Option<Point>,
+    drag_tick_scheduled: bool,
+    style: Extractor,
+    font: FontProps,
+    selection_style: SelectionStyle,
+    last_cursor_action_on: Instant,
+}
+
+/// A multi-line plain text editing view, whose text wraps to its width.
+///
+/// The text area grows to fit its text, so it's usually put in a [scroll view](super::scroll()),
+/// which is scrolled to keep the cursor visible. Enter inserts a new line, and the text is
+/// submitted with Ctrl+Enter (Cmd+Enter on macOS) to the [`on_submit`](TextArea::on_submit)
+/// callback.
+///
+/// ## Example
+/// ```rust
+/// # use floem::reactive::create_rw_signal;
+/// # use floem::views::{scroll, text_area, Decorators};
+/// let notes = create_rw_signal(String::new());
+/// scroll(text_area(notes).max_length(500).style(|s| s.width_full()))
+///     .style(|s| s.height(120.0));
+/// ```
+pub fn text_area(buffer: RwSignal<String>) -> TextArea {
+    let id = ViewId::new();
+    id.set_claims_key(is_text_editing_key);
+
+    create_effect(move |_| {
+        let text = buffer.get();
+        id.update_state(text);
+    });
+
+    let mut text_area = TextArea {
+        id,
+        buffer,
+        last_buffer: buffer.get_untracked(),
+        text_layout: TextLayout::new(),
+        text_node: None,
+        wrap_width: None,
+        cursor: 0,
+        anchor: 0,
+        preferred_x: None,
+        reveal_cursor: false,
+        viewport: Rect::ZERO,
+        history: History::default(),
+        max_length: None,
+        read_only: None,
+        on_submit: None,
+        drag_pos: None,
+        drag_tick_scheduled: false,
+        style: Default::default(),
+        font: FontProps::default(),
+        selection_style: Default::default(),
+        last_cursor_action_on: Instant::now(),
+    };
+    text_area.update_text_layout();
+
+    #[cfg(feature = "accessibility")]
+    {
+        text_area
+            .id
+            .set_accessibility_role(Role::MultilineTextInput);
+        text_area
+            .id
+            .add_accessibility_action(Action::SetValue, move |data| {
+                if let Some(ActionData::Value(value)) = data {
+                    buffer.set(value.to_string());
+                }
+            });
+    }
+
+    text_area.keyboard_navigatable().class(TextAreaClass)
+}
+
+impl TextArea {
+    /// Limits the text to `max_length` characters. Typing or pasting past it is cut short, but
+    /// the buffer isn't truncated if it's set to something longer.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Prevents editing the text while `read_only` returns true. The text can still be
+    /// selected and copied.
+    pub fn read_only(mut self, read_only: impl Fn() -> bool + 'static) -> Self {
+        self.read_only = Some(Box::new(read_only));
+        self
+    }
+
+    /// Calls `on_submit` with the text when Ctrl+Enter (Cmd+Enter on macOS) is pressed.
+    pub fn on_submit(mut self, on_submit: impl Fn(&str) + 'static) -> Self {
+        self.on_submit = Some(Box::new(on_submit));
+        self
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only.as_ref().is_some_and(|read_only| read_only())
+    }
+
+    fn selection(&self) -> Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+    }
+
+    fn get_text_attrs(&self) -> AttrsList {
+        let mut attrs = Attrs::new().color(self.style.color().unwrap_or(Color::BLACK));
+
+        attrs = attrs.font_size(self.font.size().unwrap_or(DEFAULT_FONT_SIZE));
+
+        if let Some(font_style) = self.font.style() {
+            attrs = attrs.style(font_style);
+        }
+        let font_family = self.font.family().as_ref().map(|font_family| {
+            let family: Vec<FamilyOwned> = FamilyOwned::parse_list(font_family).collect();
+            family
+        });
+        if let Some(font_family) = font_family.as_ref() {
+            attrs = attrs.family(font_family);
+        }
+        if let Some(font_weight) = self.font.weight() {
+            attrs = attrs.weight(font_weight);
+        }
+        AttrsList::new(attrs)
+    }
+
+    fn update_text_layout(&mut self) {
+        let attrs_list = self.get_text_attrs();
+        let mut text_layout = TextLayout::new();
+        self.buffer.with_untracked(|buff| {
+            // The layout only has the empty line after a trailing newline with something on it
+            if buff.ends_with('\n') {
+                text_layout.set_text(&format!("{buff} "), attrs_list);
+            } else {
+                text_layout.set_text(buff, attrs_list);
+            }
+        });
+        if let Some(width) = self.wrap_width {
+            text_layout.set_size(width, f32::MAX);
+        }
+        self.text_layout = text_layout;
+        self.id.request_layout();
+    }
+
+    fn line_height(&self) -> f64 {
+        self.text_layout
+            .layout_runs()
+            .next()
+            .map_or(DEFAULT_FONT_SIZE as f64, |run| run.line_height as f64)
+    }
+
+    /// Where the text is drawn in the view.
+    fn text_origin(&self) -> Point {
+        self.text_node
+            .and_then(|node| self.id.taffy_layout(node))
+            .map_or(Point::ZERO, |layout| {
+                Point::new(layout.location.x as f64, layout.location.y as f64)
+            })
+    }
+
+    /// The horizontal position, top and height of the cursor at `index` in the text.
+    fn caret(&self, index: usize) -> (f64, f64, f64) {
+        let lines = self.text_layout.lines_range();
+        let line = lines
+            .iter()
+            .rposition(|range| range.start <= index)
+            .unwrap_or(0);
+        let local = index - lines.get(line).map_or(0, |range| range.start);
+        let mut caret = None;
+        for run in self
+            .text_layout
+            .layout_runs()
+            .filter(|run| run.line_i == line)
+        {
+            let x = x_in_run(run.glyphs, local);
+            caret = Some((x as f64, run.line_top as f64, run.line_height as f64));
+            // At a wrap, the index is at the start of the next run
+            if run.glyphs.last().map_or(true, |glyph| local < glyph.end) {
+                break;
+            }
+        }
+        caret.unwrap_or((0.0, 0.0, self.line_height()))
+    }
+
+    /// The index in the text nearest to `point`, relative to the text.
+    fn index_at(&self, point: Point) -> usize {
+        let len = self.buffer.with_untracked(|buff| buff.len());
+        if point.y < 0.0 {
+            return 0;
+        }
+        match self.text_layout.hit(point.x as f32, point.y as f32) {
+            Some(cursor) => {
+                let line_start = self
+                    .text_layout
+                    .lines_range()
+                    .get(cursor.line)
+                    .map_or(0, |range| range.start);
+                (line_start + cursor.index).min(len)
+            }
+            None => len,
+        }
+    }
+
+    /// The index of the line above or below the cursor, keeping to the same horizontal
+    /// position.
+    fn vertical_target(&mut self, down: bool) -> usize {
+        let (x, top, height) = self.caret(self.cursor);
+        let x = *self.preferred_x.get_or_insert(x);
+        let y = if down {
+            top + height * 1.5
+        } else {
+            top - height * 0.5
+        };
+        if y > self.text_layout.size().height {
+            return self.buffer.with_untracked(|buff| buff.len());
+        }
+        self.index_at(Point::new(x, y))
+    }
+
+    fn move_to(&mut self, index: usize, extend: bool) -> bool {
+        self.cursor = index;
+        if !extend {
+            self.anchor = index;
+        }
+        true
+    }
+
+    /// Replaces `range` of the text with `text`, cut short to the maximum length, and moves the
+    /// cursor after it. Returns false if nothing changed.
+    fn edit(&mut self, range: Range<usize>, text: &str) -> bool {
+        if self.is_read_only() {
+            return false;
+        }
+        let max_length = self.max_length;
+        let text = self
+            .buffer
+            .with_untracked(|buff| limit_insertion(buff, range.clone(), text, max_length));
+        if range.is_empty() && text.is_empty() {
+            return false;
+        }
+        let history = &mut self.history;
+        self.buffer
+            .with_untracked(|buff| history.record(buff, range.clone(), text));
+        self.set_text(|buff| replace_range(buff, range.clone(), Some(text)));
+        self.move_to(range.start + text.len(), false);
+        true
+    }
+
+    fn set_text(&mut self, update: impl FnOnce(&mut String)) {
+        let last_buffer = &mut self.last_buffer;
+        self.buffer.update(|buff| {
+            update(buff);
+            *last_buffer = buff.clone();
+        });
+        self.update_text_layout();
+    }
+
+    fn insert(&mut self, text: &str) -> bool {
+        self.edit(self.selection(), text)
+    }
+
+    /// Deletes the selection, or from the cursor to where `movement` moves it if there's none.
+    fn delete(&mut self, movement: Movement, direction: Direction) -> bool {
+        let selection = self.selection();
+        if !selection.is_empty() {
+            return self.edit(selection, "");
+        }
+        let target = self
+            .buffer
+            .with_untracked(|buff| movement_target(buff, self.cursor, movement, direction));
+        self.edit(self.cursor.min(target)..self.cursor.max(target), "")
+    }
+
+    fn undo(&mut self, redo: bool) {
+        if self.is_read_only() {
+            return;
+        }
+        let text = self.buffer.get_untracked();
+        let restored = if redo {
+            self.history.redo(&text, self.cursor)
+        } else {
+            self.history.undo(&text, self.cursor)
+        };
+        if let Some((text, cursor)) = restored {
+            self.set_text(|buff| *buff = text);
+            self.move_to(cursor, false);
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let selection = self.selection();
+        if selection.is_empty() {
+            return None;
+        }
+        self.buffer
+            .with_untracked(|buff| buff.get(selection).map(|text| text.to_string()))
+    }
+
+    fn handle_modifier_cmd(&mut self, event: &KeyEvent, character: &SmolStr) -> bool {
+        if event.modifiers.is_empty() {
+            return false;
+        }
+
+        let command: TextCommand = (event, character).into();
+        match command {
+            TextCommand::SelectAll => {
+                self.anchor = 0;
+                self.cursor = self.buffer.with_untracked(|buff| buff.len());
+            }
+            TextCommand::Copy => {
+                if let Some(text) = self.selected_text() {
+                    let _ = Clipboard::set_contents(text);
+                }
+            }
+            TextCommand::Cut => {
+                if let Some(text) = self.selected_text() {
+                    let _ = Clipboard::set_contents(text);
+                    self.insert("");
+                }
+            }
+            TextCommand::Paste => {
+                if let Ok(text) = Clipboard::get_contents() {
+                    self.insert(&text.replace("\r\n", "\n"));
+                }
+            }
+            TextCommand::Undo => self.undo(false),
+            TextCommand::Redo => self.undo(true),
+            TextCommand::None => return false,
+        }
+        true
+    }
+
+    fn handle_key_down(&mut self, cx: &mut EventCx, event: &KeyEvent) -> bool {
+        // Only moving up and down keeps the horizontal position
+        let preferred_x = self.preferred_x.take();
+        let extend = event.modifiers.contains(Modifiers::SHIFT);
+        let movement = get_word_based_motion(event).unwrap_or(Movement::Glyph);
+        match event.key.logical_key {
+            Key::Character(ref ch) => self.handle_modifier_cmd(event, ch) || self.insert(ch),
+            Key::Unidentified(_) => event.key.text.as_ref().map_or(false, |ch| {
+                self.handle_modifier_cmd(event, ch) || self.insert(ch)
+            }),
+            Key::Named(NamedKey::Space) => self.insert(" "),
+            Key::Named(NamedKey::Enter) => {
+                if is_submit_shortcut(event) {
+                    if let Some(on_submit) = &self.on_submit {
+                        self.buffer.with_untracked(|buff| on_submit(buff));
+                        return true;
+                    }
+                    false
+                } else {
+                    self.insert("\n")
+                }
+            }
+            Key::Named(NamedKey::Backspace) => self.delete(movement, Direction::Left),
+            Key::Named(NamedKey::Delete) => self.delete(movement, Direction::Right),
+            Key::Named(NamedKey::Escape) => {
+                cx.app_state.clear_focus();
+                true
+            }
+            Key::Named(NamedKey::ArrowLeft) | Key::Named(NamedKey::ArrowRight) => {
+                let direction = match event.key.logical_key {
+                    Key::Named(NamedKey::ArrowLeft) => Direction::Left,
+                    _ => Direction::Right,
+                };
+                let selection = self.selection();
+                let target = match (movement, direction) {
+                    // Moving without extending the selection goes to its edge
+                    (Movement::Glyph, Direction::Left) if !extend && !selection.is_empty() => {
+                        selection.start
+                    }
+                    (Movement::Glyph, Direction::Right) if !extend && !selection.is_empty() => {
+                        selection.end
+                    }
+                    _ => self.buffer.with_untracked(|buff| {
+                        movement_target(buff, self.cursor, movement, direction)
+                    }),
+                };
+                self.move_to(target, extend)
+            }
+            Key::Named(NamedKey::ArrowUp) | Key::Named(NamedKey::ArrowDown) => {
+                self.preferred_x = preferred_x;
+                let down = event.key.logical_key == Key::Named(NamedKey::ArrowDown);
+                let target = self.vertical_target(down);
+                self.move_to(target, extend)
+            }
+            Key::Named(NamedKey::Home) | Key::Named(NamedKey::End) => {
+                let direction = match event.key.logical_key {
+                    Key::Named(NamedKey::Home) => Direction::Left,
+                    _ => Direction::Right,
+                };
+                let to_text_edge = event
+                    .modifiers
+                    .intersects(Modifiers::CONTROL | Modifiers::META);
+                let target = self.buffer.with_untracked(|buff| match direction {
+                    Direction::Left if to_text_edge => 0,
+                    Direction::Right if to_text_edge => buff.len(),
+                    _ => movement_target(buff, self.cursor, Movement::Line, direction),
+                });
+                self.move_to(target, extend)
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the cursor to the pointer at `pos` in the view while dragging a selection, and
+    /// keeps scrolling while it's outside of the visible part of the text area.
+    fn drag_to(&mut self, pos: Point) {
+        let index = self.index_at(pos - self.text_origin().to_vec2());
+        if index != self.cursor {
+            self.cursor = index;
+            self.reveal_cursor = true;
+            self.id.request_layout();
+        }
+        if !self.viewport.contains(pos) && !self.drag_tick_scheduled {
+            self.drag_tick_scheduled = true;
+            let id = self.id;
+            exec_after(
+                Duration::from_millis(DRAG_TICK_INTERVAL_MS),
+                Box::new(move |_| id.update_state(DragTick)),
+            );
+        }
+    }
+
+    fn paint_selection(&self, cx: &mut PaintCx, origin: Point) {
+        let selection = self.selection();
+        let color = self.selection_style.selection_color();
+        let radius = self.selection_style.corner_radius();
+        let lines = self.text_layout.lines_range();
+        for run in self.text_layout.layout_runs() {
+            let line_start = lines.get(run.line_i).map_or(0, |range| range.start);
+            let run_start = line_start + run.glyphs.first().map_or(0, |glyph| glyph.start);
+            let run_end = line_start + run.glyphs.last().map_or(0, |glyph| glyph.end);
+            if selection.start > run_end || selection.end < run_start {
+                continue;
+            }
+            let x0 = if selection.start > run_start {
+                x_in_run(run.glyphs, selection.start - line_start)
+            } else {
+                0.0
+            };
+            let x1 = if selection.end < run_end {
+                x_in_run(run.glyphs, selection.end - line_start)
+            } else {
+                run.line_w
+            };
+            let rect = Rect::new(
+                x0 as f64,
+                run.line_top as f64,
+                x1 as f64,
+                (run.line_top + run.line_height) as f64,
+            ) + origin.to_vec2();
+            cx.fill(&rect.to_rounded_rect(radius), &color, 0.0);
+        }
+    }
+}
+
+/// The horizontal position of the index `local` in a line, among the glyphs of a run of it.
+fn x_in_run(glyphs: &[LayoutGlyph], local: usize) -> f32 {
+    match glyphs.iter().find(|glyph| local < glyph.end) {
+        Some(glyph) => glyph.x,
+        None => glyphs.last().map_or(0.0, |glyph| glyph.x + glyph.w),
+    }
+}
+
+/// Where `movement` in `direction` moves the cursor from `index` in `text`. A line movement
+/// goes to the start or end of the line, without the line ending.
+fn movement_target(text: &str, index: usize, movement: Movement, direction: Direction) -> usize {
+    match (movement, direction) {
+        (Movement::Glyph, Direction::Left) => text[..index]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(idx, _)| idx),
+        (Movement::Glyph, Direction::Right) => text[index..]
+            .graphemes(true)
+            .next()
+            .map_or(index, |grapheme| index + grapheme.len()),
+        (Movement::Word, Direction::Left) => text
+            .unicode_word_indices()
+            .map(|(idx, _)| idx)
+            .take_while(|idx| *idx < index)
+            .last()
+            .unwrap_or(0),
+        (Movement::Word, Direction::Right) => text
+            .unicode_word_indices()
+            .map(|(idx, word)| idx + word.len())
+            .find(|end| *end > index)
+            .unwrap_or(text.len()),
+        (Movement::Line, Direction::Left) => text[..index].rfind('\n').map_or(0, |idx| idx + 1),
+        (Movement::Line, Direction::Right) => {
+            let end = text[index..]
+                .find('\n')
+                .map_or(text.len(), |idx| index + idx);
+            if end > index && text[..end].ends_with('\r') {
+                end - 1
+            } else {
+                end
+            }
+        }
+    }
+}
+
+impl View for TextArea {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        format!("TextArea: {:?}", self.buffer.get_untracked()).into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if state.is::<DragTick>() {
+            self.drag_tick_scheduled = false;
+            match self.drag_pos {
+                Some(pos) if cx.app_state.is_active(&self.id) => {
+                    self.drag_to(pos - self.id.layout_rect().origin().to_vec2());
+                }
+                _ => self.drag_pos = None,
+            }
+        } else if let Ok(text) = state.downcast::<String>() {
+            if *text != self.last_buffer {
+                // The text was set from outside, which the undo history doesn't know about
+                self.history.clear();
+                self.last_buffer = *text;
+                let len = self.last_buffer.len();
+                if !self.last_buffer.is_char_boundary(self.cursor.min(len))
+                    || !self.last_buffer.is_char_boundary(self.anchor.min(len))
+                {
+                    self.move_to(len, false);
+                } else {
+                    self.cursor = self.cursor.min(len);
+                    self.anchor = self.anchor.min(len);
+                }
+                self.update_text_layout();
+            }
+        }
+    }
+
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_value(self.buffer.get_untracked());
+        if self.is_read_only() {
+            node.set_read_only();
+        }
+    }
+
+    fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
+        let is_handled = match &event {
+            Event::PointerDown(
+                event @ PointerInputEvent {
+                    button: PointerButton::Primary,
+                    ..
+                },
+            ) => {
+                cx.update_active(self.id);
+                let index = self.index_at(event.pos - self.text_origin().to_vec2());
+                self.buffer.with_untracked(|buff| match event.count {
+                    1 => {
+                        self.cursor = index;
+                        if !event.modifiers.contains(Modifiers::SHIFT) {
+                            self.anchor = index;
+                        }
+                    }
+                    2 => {
+                        let word = get_dbl_click_selection(index, buff);
+                        self.anchor = word.start;
+                        self.cursor = word.end;
+                    }
+                    _ => {
+                        self.anchor = movement_target(buff, index, Movement::Line, Direction::Left);
+                        self.cursor =
+                            movement_target(buff, index, Movement::Line, Direction::Right);
+                    }
+                });
+                self.preferred_x = None;
+                self.drag_pos = Some(event.pos + self.id.layout_rect().origin().to_vec2());
+                true
+            }
+            Event::PointerMove(event) => {
+                if cx.is_active(self.id) && self.drag_pos.is_some() {
+                    self.drag_pos = Some(event.pos + self.id.layout_rect().origin().to_vec2());
+                    self.drag_to(event.pos);
+                }
+                false
+            }
+            Event::PointerUp(_) => {
+                self.drag_pos = None;
+                false
+            }
+            Event::KeyDown(event) => self.handle_key_down(cx, event),
+            _ => false,
+        };
+
+        if is_handled {
+            self.reveal_cursor = true;
+            self.id.request_layout();
+            self.last_cursor_action_on = Instant::now();
+        }
+
+        EventPropagation::Continue
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        let style = cx.style();
+        let font_changed = self.font.read(cx);
+        if self.style.read(cx) || font_changed {
+            self.update_text_layout();
+        }
+        self.selection_style.read_style(cx, &style);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> NodeId {
+        cx.layout_node(self.id(), true, |_cx| {
+            if self.text_node.is_none() {
+                self.text_node = Some(
+                    self.id
+                        .taffy()
+                        .borrow_mut()
+                        .new_leaf(taffy::style::Style::DEFAULT)
+                        .unwrap(),
+                );
+            }
+            let text_node = self.text_node.unwrap();
+
+            let (width, height) = {
+                let view_state = self.id.state();
+                let view_state = view_state.borrow();
+                let style = view_state.combined_style.builtin();
+                (style.width(), style.height())
+            };
+            let line_height = self.line_height();
+            let mut text_height = self.text_layout.size().height.max(line_height);
+            if matches!(height, PxPctAuto::Auto) {
+                text_height = text_height.max(DEFAULT_ROWS as f64 * line_height);
+            }
+            // The text wraps to the width of the text area, which is a number of characters
+            // wide if it isn't set
+            let style = match width {
+                PxPctAuto::Auto => {
+                    let mut glyph = TextLayout::new();
+                    glyph.set_text("W", self.get_text_attrs());
+                    Style::new().width(DEFAULT_COLUMNS as f64 * glyph.size().width)
+                }
+                _ => Style::new().width_full(),
+            }
+            .height(text_height)
+            .to_taffy_style();
+            let _ = self.id.taffy().borrow_mut().set_style(text_node, style);
+
+            vec![text_node]
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        self.viewport = cx.current_viewport();
+
+        let width = self
+            .text_node
+            .and_then(|node| self.id.taffy_layout(node))
+            .map(|layout| layout.size.width);
+        if width.is_some() && width != self.wrap_width {
+            self.wrap_width = width;
+            self.update_text_layout();
+        }
+
+        if std::mem::take(&mut self.reveal_cursor) {
+            let (x, top, height) = self.caret(self.cursor);
+            let rect = Rect::new(x, top, x + 1.0, top + height) + self.text_origin().to_vec2();
+            self.id.scroll_to(Some(rect));
+        }
+
+        None
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let origin = self.text_origin();
+        let is_focused = cx.app_state.is_focused(&self.id);
+
+        if is_focused && self.anchor != self.cursor {
+            self.paint_selection(cx, origin);
+        }
+
+        cx.draw_text(&self.text_layout, origin);
+
+        if !is_focused {
+            return;
+        }
+
+        let is_cursor_visible = self.anchor == self.cursor
+            && (self.last_cursor_action_on.elapsed().as_millis()
+                / CURSOR_BLINK_INTERVAL_MS as u128)
+                % 2
+                == 0;
+        if is_cursor_visible {
+            let cursor_color = self
+                .id
+                .state()
+                .borrow()
+                .combined_style
+                .builtin()
+                .cursor_color();
+            let (x, top, height) = self.caret(self.cursor);
+            let rect = Rect::new(x, top, x + 1.0, top + height) + origin.to_vec2();
+            cx.fill(&rect, &cursor_color, 0.0);
+        }
+
+        let id = self.id();
+        exec_after(
+            Duration::from_millis(CURSOR_BLINK_INTERVAL_MS),
+            Box::new(move |_| {
+                id.request_paint();
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::movement_target;
+    use crate::views::{Direction, Movement};
+
+    #[test]
+    fn glyph_movement() {
+        let text = "aé\nb";
+        assert_eq!(
+            movement_target(text, 0, Movement::Glyph, Direction::Left),
+            0
+        );
+        assert_eq!(
+            movement_target(text, 1, Movement::Glyph, Direction::Right),
+            3
+        );
+        assert_eq!(
+            movement_target(text, 3, Movement::Glyph, Direction::Right),
+            4
+        );
+        assert_eq!(
+            movement_target(text, 3, Movement::Glyph, Direction::Left),
+            1
+        );
+        assert_eq!(
+            movement_target(text, 5, Movement::Glyph, Direction::Right),
+            5
+        );
+    }
+
+    #[test]
+    fn word_movement() {
+        let text = "one two\nthree";
+        assert_eq!(
+            movement_target(text, 0, Movement::Word, Direction::Right),
+            3
+        );
+        assert_eq!(
+            movement_target(text, 3, Movement::Word, Direction::Right),
+            7
+        );
+        assert_eq!(
+            movement_target(text, 7, Movement::Word, Direction::Right),
+            13
+        );
+        assert_eq!(movement_target(text, 8, Movement::Word, Direction::Left), 4);
+        assert_eq!(movement_target(text, 2, Movement::Word, Direction::Left), 0);
+    }
+
+    #[test]
+    fn line_movement() {
+        let text = "one\r\ntwo\n\nthree";
+        assert_eq!(
+            movement_target(text, 1, Movement::Line, Direction::Right),
+            3
+        );
+        assert_eq!(movement_target(text, 1, Movement::Line, Direction::Left), 0);
+        assert_eq!(movement_target(text, 6, Movement::Line, Direction::Left), 5);
+        assert_eq!(
+            movement_target(text, 6, Movement::Line, Direction::Right),
+            8
+        );
+        // An empty line
+        assert_eq!(movement_target(text, 9, Movement::Line, Direction::Left), 9);
+        assert_eq!(
+            movement_target(text, 9, Movement::Line, Direction::Right),
+            9
+        );
+        assert_eq!(
+            movement_target(text, 12, Movement::Line, Direction::Right),
+            15
+        );
+    }
+}
@@ -69,6 +69,9 @@ pub fn text_editor(text: impl Into<Rope>) -> TextEditor {
     }
 }
 
+/// A text editor with a custom key handler. Key presses bound to commands in the
+/// [shortcut registry](crate::shortcut), which include the `editor.*` commands of the default
+/// keymap, run those commands and don't reach the handler.
 pub fn text_editor_keys(
     text: impl Into<Rope>,
     handle_key_event: impl Fn(RwSignal<Editor>, &KeyPress, Modifiers) -> CommandExecuted + 'static,
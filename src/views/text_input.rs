@@ -8,6 +8,8 @@ use crate::style::{FontProps, PaddingLeft, SelectionStyle};
 use crate::style::{FontStyle, FontWeight, TextColor};
 use crate::unit::{PxPct, PxPctAuto};
 use crate::{prop_extractor, style_class, Clipboard};
+#[cfg(feature = "accessibility")]
+use accesskit::{Action, ActionData, NodeBuilder, Role, Toggled};
 use floem_reactive::{create_rw_signal, SignalGet, SignalUpdate, SignalWith};
 use taffy::prelude::{Layout, NodeId};
 
@@ -15,7 +17,11 @@ use floem_renderer::{text::Cursor, Renderer};
 use floem_winit::keyboard::{Key, NamedKey, SmolStr};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{peniko::Color, style::Style, view::View};
+use crate::{
+    peniko::Color,
+    style::Style,
+    view::{IntoView, View},
+};
 
 use std::{any::Any, ops::Range};
 
@@ -32,10 +38,11 @@ use crate::{
     event::Event,
 };
 
-use super::Decorators;
+use super::{svg, Decorators};
 
 style_class!(pub TextInputClass);
 style_class!(pub PlaceholderTextClass);
+style_class!(pub RevealToggleClass);
 
 prop_extractor! {
     Extractor {
@@ -109,8 +116,26 @@ pub struct TextInput {
     cursor_width: f64, // TODO: make this configurable
     is_focused: bool,
     last_cursor_action_on: Instant,
+    history: History,
+    max_length: Option<usize>,
+    read_only: Option<Box<dyn Fn() -> bool>>,
+    on_submit: Option<Box<dyn Fn(&str)>>,
+    secure: bool,
+    revealed: bool,
+    mask: Option<InputMask>,
+    char_filter: Option<Box<dyn Fn(char) -> bool>>,
+    // Where a drag selection started, and where the pointer is while dragging
+    drag_anchor: usize,
+    drag_pos: Option<Point>,
+    drag_tick_scheduled: bool,
 }
 
+/// Sent to a text view to extend a drag selection while the pointer is held past its edge.
+pub(crate) struct DragTick;
+
+/// How often a drag selection is extended while the pointer is held past the edge of the text.
+pub(crate) const DRAG_TICK_INTERVAL_MS: u64 = 30;
+
 #[derive(Clone, Copy, Debug)]
 pub enum Movement {
     Glyph,
@@ -127,6 +152,7 @@ pub enum Direction {
 /// Text Input View
 pub fn text_input(buffer: RwSignal<String>) -> TextInput {
     let id = ViewId::new();
+    id.set_claims_key(is_text_editing_key);
     let is_focused = create_rw_signal(false);
 
     {
@@ -136,6 +162,16 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         });
     }
 
+    #[cfg(feature = "accessibility")]
+    {
+        id.set_accessibility_role(Role::TextInput);
+        id.add_accessibility_action(Action::SetValue, move |data| {
+            if let Some(ActionData::Value(value)) = data {
+                buffer.set(value.to_string());
+            }
+        });
+    }
+
     TextInput {
         id,
         cursor_glyph_idx: 0,
@@ -164,6 +200,17 @@ pub fn text_input(buffer: RwSignal<String>) -> TextInput {
         height: 0.0,
         is_focused: false,
         last_cursor_action_on: Instant::now(),
+        history: History::default(),
+        max_length: None,
+        read_only: None,
+        on_submit: None,
+        secure: false,
+        revealed: false,
+        mask: None,
+        char_filter: None,
+        drag_anchor: 0,
+        drag_pos: None,
+        drag_tick_scheduled: false,
     }
     .keyboard_navigatable()
     .on_event_stop(EventListener::FocusGained, move |_| {
@@ -187,6 +234,8 @@ pub(crate) enum TextCommand {
     Copy,
     Paste,
     Cut,
+    Undo,
+    Redo,
     None,
 }
 
@@ -199,6 +248,8 @@ impl From<(&KeyEvent, &SmolStr)> for TextCommand {
             (Modifiers::META, "c") => Self::Copy,
             (Modifiers::META, "x") => Self::Cut,
             (Modifiers::META, "v") => Self::Paste,
+            (Modifiers::META, "z") => Self::Undo,
+            (modifiers, "z" | "Z") if modifiers == Modifiers::META | Modifiers::SHIFT => Self::Redo,
             _ => Self::None,
         }
         #[cfg(not(target_os = "macos"))]
@@ -207,12 +258,17 @@ impl From<(&KeyEvent, &SmolStr)> for TextCommand {
             (Modifiers::CONTROL, "c") => Self::Copy,
             (Modifiers::CONTROL, "x") => Self::Cut,
             (Modifiers::CONTROL, "v") => Self::Paste,
+            (Modifiers::CONTROL, "z") => Self::Undo,
+            (Modifiers::CONTROL, "y") => Self::Redo,
+            (modifiers, "z" | "Z") if modifiers == Modifiers::CONTROL | Modifiers::SHIFT => {
+                Self::Redo
+            }
             _ => Self::None,
         }
     }
 }
 
-fn get_word_based_motion(event: &KeyEvent) -> Option<Movement> {
+pub(crate) fn get_word_based_motion(event: &KeyEvent) -> Option<Movement> {
     #[cfg(not(target_os = "macos"))]
     return event
         .modifiers
@@ -230,6 +286,29 @@ fn get_word_based_motion(event: &KeyEvent) -> Option<Movement> {
             .then_some(Movement::Line));
 }
 
+/// Whether a text input or text area uses the key itself, for typing, moving the cursor or the
+/// clipboard, so shortcuts without a condition leave it alone.
+pub(crate) fn is_text_editing_key(event: &KeyEvent) -> bool {
+    let mut mods = event.modifiers;
+    mods.remove(Modifiers::SHIFT | Modifiers::ALTGR);
+    match &event.key.logical_key {
+        Key::Named(
+            NamedKey::ArrowLeft
+            | NamedKey::ArrowRight
+            | NamedKey::ArrowUp
+            | NamedKey::ArrowDown
+            | NamedKey::Home
+            | NamedKey::End
+            | NamedKey::Backspace
+            | NamedKey::Delete,
+        ) => true,
+        Key::Character(ch) => {
+            mods.is_empty() || !matches!(TextCommand::from((event, ch)), TextCommand::None)
+        }
+        _ => mods.is_empty(),
+    }
+}
+
 const DEFAULT_FONT_SIZE: f32 = 14.0;
 const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
 /// Specifies approximately how many characters wide the input field should be
@@ -239,15 +318,564 @@ const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
 // TODO: allow this to be set in the styles
 const APPROX_VISIBLE_CHARS_TARGET: f32 = 10.0;
 
+/// Whether `event` is the platform's primary shortcut modifier with `Enter`, which submits a
+/// multi-line text view.
+pub(crate) fn is_submit_shortcut(event: &KeyEvent) -> bool {
+    #[cfg(target_os = "macos")]
+    let modifier = Modifiers::META;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Modifiers::CONTROL;
+    event.modifiers == modifier
+}
+
+/// The undo and redo stacks of a text editing view, as snapshots of the text and cursor.
+#[derive(Default)]
+pub(crate) struct History {
+    undo: Vec<(String, usize)>,
+    redo: Vec<(String, usize)>,
+    // The cursor after the last edit if it was typing a character, so that typing a word is
+    // undone at once
+    typing_at: Option<usize>,
+}
+
+/// The most edits that can be undone.
+const MAX_UNDO: usize = 100;
+
+impl History {
+    /// Records the text before `range` of it is replaced with `inserted`.
+    pub(crate) fn record(&mut self, before: &str, range: Range<usize>, inserted: &str) {
+        self.redo.clear();
+        let typing = range.is_empty()
+            && inserted.graphemes(true).count() == 1
+            && !inserted.trim().is_empty();
+        let merge = typing && self.typing_at == Some(range.start);
+        self.typing_at = typing.then_some(range.start + inserted.len());
+        if !merge {
+            if self.undo.len() == MAX_UNDO {
+                self.undo.remove(0);
+            }
+            self.undo.push((before.to_string(), range.end));
+        }
+    }
+
+    /// Returns the text and cursor to restore, given the current ones to redo.
+    pub(crate) fn undo(&mut self, text: &str, cursor: usize) -> Option<(String, usize)> {
+        let prev = self.undo.pop()?;
+        self.redo.push((text.to_string(), cursor));
+        self.typing_at = None;
+        Some(prev)
+    }
+
+    /// Returns the text and cursor to restore, given the current ones to undo.
+    pub(crate) fn redo(&mut self, text: &str, cursor: usize) -> Option<(String, usize)> {
+        let next = self.redo.pop()?;
+        self.undo.push((text.to_string(), cursor));
+        self.typing_at = None;
+        Some(next)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing_at = None;
+    }
+}
+
+/// The part of `text` that fits when replacing `range` of `buffer`, with at most `max_length`
+/// characters in total.
+pub(crate) fn limit_insertion<'a>(
+    buffer: &str,
+    range: Range<usize>,
+    text: &'a str,
+    max_length: Option<usize>,
+) -> &'a str {
+    let Some(max_length) = max_length else {
+        return text;
+    };
+    let kept = buffer.chars().count() - buffer[range].chars().count();
+    let available = max_length.saturating_sub(kept);
+    match text.char_indices().nth(available) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// The character shown for each character of a [secure](TextInput::secure) input.
+const SECURE_BULLET: char = '•';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MaskSlot {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl MaskSlot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            MaskSlot::Digit => c.is_ascii_digit(),
+            MaskSlot::Letter => c.is_alphabetic(),
+            MaskSlot::Alphanumeric => c.is_alphanumeric(),
+            MaskSlot::Literal(_) => false,
+        }
+    }
+}
+
+/// A pattern that the text of a [`TextInput`] is formatted with as it's typed, such as
+/// `(999) 999-9999` for phone numbers or `99/99/9999` for dates.
+///
+/// In the pattern, `9` stands for a digit, `a` for a letter and `*` for a letter or digit. Any
+/// other character is a literal that's filled in as the text is typed, and `\` makes the
+/// character after it a literal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    slots: Vec<MaskSlot>,
+}
+
+impl InputMask {
+    pub fn new(pattern: &str) -> Self {
+        let mut slots = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '9' => MaskSlot::Digit,
+                'a' => MaskSlot::Letter,
+                '*' => MaskSlot::Alphanumeric,
+                '\\' => MaskSlot::Literal(chars.next().unwrap_or('\\')),
+                c => MaskSlot::Literal(c),
+            });
+        }
+        Self { slots }
+    }
+
+    /// Formats the characters in `raw` with the mask, skipping those that don't fit. Literals
+    /// are filled in up to the last character.
+    pub fn format(&self, raw: &str) -> String {
+        let mut text = String::new();
+        let mut literals = String::new();
+        let mut chars = raw.chars();
+        for slot in &self.slots {
+            if let MaskSlot::Literal(c) = slot {
+                literals.push(*c);
+                continue;
+            }
+            let Some(c) = chars.by_ref().find(|c| slot.accepts(*c)) else {
+                break;
+            };
+            text.push_str(&literals);
+            literals.clear();
+            text.push(c);
+        }
+        text
+    }
+
+    /// The characters typed into `text`, without the literals of the mask.
+    pub fn raw(&self, text: &str) -> String {
+        let conforms = text.chars().count() <= self.slots.len()
+            && text.chars().zip(&self.slots).all(|(c, slot)| match slot {
+                MaskSlot::Literal(literal) => c == *literal,
+                slot => slot.accepts(c),
+            });
+        if !conforms {
+            return self.raw(&self.format(text));
+        }
+        text.chars()
+            .zip(&self.slots)
+            .filter(|(_, slot)| !matches!(slot, MaskSlot::Literal(_)))
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Whether every character of the mask has been typed in `text`.
+    pub fn is_complete(&self, text: &str) -> bool {
+        let inputs = self
+            .slots
+            .iter()
+            .filter(|slot| !matches!(slot, MaskSlot::Literal(_)))
+            .count();
+        self.raw(text).chars().count() == inputs
+    }
+
+    /// How many typed characters are before the byte index `index` of the formatted `text`.
+    fn raw_before(&self, text: &str, index: usize) -> usize {
+        text.char_indices()
+            .zip(&self.slots)
+            .take_while(|((idx, _), _)| *idx < index)
+            .filter(|(_, slot)| !matches!(slot, MaskSlot::Literal(_)))
+            .count()
+    }
+
+    /// The byte index in the formatted `text` after its first `count` typed characters and the
+    /// literals that follow them.
+    fn position_after(&self, text: &str, count: usize) -> usize {
+        let mut seen = 0;
+        for ((idx, _), slot) in text.char_indices().zip(&self.slots) {
+            if !matches!(slot, MaskSlot::Literal(_)) {
+                if seen == count {
+                    return idx;
+                }
+                seen += 1;
+            }
+        }
+        text.len()
+    }
+
+    /// Replaces `range` of the formatted `text` with `inserted`, returning the new formatted
+    /// text and cursor. Deleting only literals deletes the typed character before them if
+    /// `backward`, or after them otherwise.
+    pub(crate) fn edit(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        inserted: &str,
+        backward: bool,
+    ) -> (String, usize) {
+        let raw: Vec<char> = self.raw(text).chars().collect();
+        let mut start = self.raw_before(text, range.start).min(raw.len());
+        let mut end = self.raw_before(text, range.end).min(raw.len());
+        if inserted.is_empty() && !range.is_empty() && start == end {
+            if backward {
+                start = start.saturating_sub(1);
+            } else {
+                end = (end + 1).min(raw.len());
+            }
+        }
+        let before: String = raw[..start].iter().collect();
+        let after: String = raw[end..].iter().collect();
+        let typed = self
+            .raw(&self.format(&format!("{before}{inserted}")))
+            .chars()
+            .count();
+        let new_text = self.format(&format!("{before}{inserted}{after}"));
+        let cursor = self.position_after(&new_text, typed);
+        (new_text, cursor)
+    }
+
+    /// Moves `index` in the formatted `text` past the literals next to it, forward or
+    /// backward, so the cursor skips them.
+    pub(crate) fn skip_literals(&self, text: &str, mut index: usize, forward: bool) -> usize {
+        let slot_at = |index: usize| {
+            let position = text[..index].chars().count();
+            self.slots.get(position).copied()
+        };
+        if forward {
+            while let Some(c) = text[index..].chars().next() {
+                if !matches!(slot_at(index), Some(MaskSlot::Literal(_))) {
+                    break;
+                }
+                index += c.len_utf8();
+            }
+        } else {
+            while let Some(c) = text[..index].chars().next_back() {
+                if !matches!(slot_at(index - c.len_utf8()), Some(MaskSlot::Literal(_))) {
+                    break;
+                }
+                index -= c.len_utf8();
+            }
+        }
+        index
+    }
+}
+
+impl From<&str> for InputMask {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+/// Sent to a secure text input when whether its text is revealed changes.
+struct Revealed(bool);
+
+const EYE_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M1 8s2.5-5 7-5 7 5 7 5-2.5 5-7 5-7-5-7-5z" stroke="currentColor" stroke-width="1.2" fill="none"/><circle cx="8" cy="8" r="2" stroke="currentColor" stroke-width="1.2" fill="none"/></svg>"#;
+const EYE_OFF_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M1 8s2.5-5 7-5 7 5 7 5-2.5 5-7 5-7-5-7-5z" stroke="currentColor" stroke-width="1.2" fill="none"/><circle cx="8" cy="8" r="2" stroke="currentColor" stroke-width="1.2" fill="none"/><path d="M2 2l12 12" stroke="currentColor" stroke-width="1.2"/></svg>"#;
+
+/// An eye button that toggles `revealed`, to place next to a [secure](TextInput::secure) input
+/// revealing its text with the same signal.
+///
+/// ```rust
+/// # use floem::reactive::{create_rw_signal, SignalGet};
+/// # use floem::views::{h_stack, reveal_toggle, text_input, Decorators};
+/// let password = create_rw_signal(String::new());
+/// let revealed = create_rw_signal(false);
+/// h_stack((
+///     text_input(password).secure(move || revealed.get()),
+///     reveal_toggle(revealed),
+/// ))
+/// .style(|s| s.items_center().gap(4.0));
+/// ```
+pub fn reveal_toggle(revealed: RwSignal<bool>) -> impl IntoView {
+    let icon = move || if revealed.get() { EYE_OFF_SVG } else { EYE_SVG };
+    let toggle = svg(EYE_SVG)
+        .update_value(icon)
+        .class(RevealToggleClass)
+        .keyboard_navigatable()
+        .on_click_stop(move |_| revealed.update(|revealed| *revealed = !*revealed));
+    #[cfg(feature = "accessibility")]
+    {
+        let id = toggle.id();
+        id.set_accessibility_role(Role::Button);
+        id.set_accessibility_label(Some("Show text".to_string()));
+        id.add_accessibility_properties(move |node| {
+            node.set_toggled(if revealed.get_untracked() {
+                Toggled::True
+            } else {
+                Toggled::False
+            })
+        });
+    }
+    toggle
+}
+
 impl TextInput {
     pub fn placeholder(mut self, text: impl Into<String>) -> Self {
         self.placeholder_text = Some(text.into());
         self
     }
+
+    /// Limits the text to `max_length` characters. Typing or pasting past it is cut short, but
+    /// the buffer isn't truncated if it's set to something longer.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Prevents editing the text while `read_only` returns true. The text can still be
+    /// selected and copied.
+    pub fn read_only(mut self, read_only: impl Fn() -> bool + 'static) -> Self {
+        self.read_only = Some(Box::new(read_only));
+        self
+    }
+
+    /// Calls `on_submit` with the text when Enter is pressed.
+    pub fn on_submit(mut self, on_submit: impl Fn(&str) + 'static) -> Self {
+        self.on_submit = Some(Box::new(on_submit));
+        self
+    }
+
+    /// Shows the text as bullets unless `reveal` returns true, and prevents copying it, for
+    /// passwords and other secrets. See [`reveal_toggle`] for a button that reveals the text.
+    pub fn secure(mut self, reveal: impl Fn() -> bool + 'static) -> Self {
+        self.secure = true;
+        let id = self.id;
+        create_effect(move |_| {
+            id.update_state(Revealed(reveal()));
+        });
+        #[cfg(feature = "accessibility")]
+        id.set_accessibility_role(Role::PasswordInput);
+        self
+    }
+
+    /// Formats the text with `mask` as it's typed, and keeps `raw` set to the typed characters
+    /// without the literals of the mask. Setting `raw` formats it into the buffer.
+    pub fn mask(mut self, mask: impl Into<InputMask>, raw: RwSignal<String>) -> Self {
+        let mask = mask.into();
+        let buffer = self.buffer.buffer;
+        // Whichever of the two already has text is kept
+        let initial = match raw.get_untracked() {
+            value if value.is_empty() => buffer.with_untracked(|buff| mask.raw(buff)),
+            value => mask.raw(&value),
+        };
+        let text = mask.format(&initial);
+        if raw.with_untracked(|raw| *raw != initial) {
+            raw.set(initial);
+        }
+        if buffer.with_untracked(|buff| *buff != text) {
+            buffer.set(text);
+        }
+        {
+            let mask = mask.clone();
+            create_effect(move |_| {
+                let value = buffer.with(|text| mask.raw(text));
+                if raw.with_untracked(|raw| *raw != value) {
+                    raw.set(value);
+                }
+            });
+        }
+        {
+            let mask = mask.clone();
+            create_effect(move |_| {
+                let text = raw.with(|raw| mask.format(raw));
+                if buffer.with_untracked(|buff| mask.raw(buff) != mask.raw(&text)) {
+                    buffer.set(text);
+                }
+            });
+        }
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Drops typed and pasted characters that `filter` returns false for.
+    pub fn char_filter(mut self, filter: impl Fn(char) -> bool + 'static) -> Self {
+        self.char_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Only allows digits to be typed.
+    pub fn numeric(self) -> Self {
+        self.char_filter(|c| c.is_ascii_digit())
+    }
 }
 
 impl TextInput {
+    fn is_read_only(&self) -> bool {
+        self.read_only.as_ref().is_some_and(|read_only| read_only())
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.secure && !self.revealed
+    }
+
+    /// The text as it's shown, which is bullets if it's hidden.
+    fn display_text(&self) -> String {
+        self.buffer.with_untracked(|buff| {
+            if self.is_hidden() {
+                buff.graphemes(true).map(|_| SECURE_BULLET).collect()
+            } else {
+                buff.clone()
+            }
+        })
+    }
+
+    /// Maps an index into the buffer to one into the displayed text.
+    fn to_display(&self, index: usize) -> usize {
+        if !self.is_hidden() {
+            return index;
+        }
+        self.buffer.with_untracked(|buff| {
+            buff[..index.min(buff.len())].graphemes(true).count() * SECURE_BULLET.len_utf8()
+        })
+    }
+
+    /// Maps an index into the displayed text to one into the buffer.
+    fn from_display(&self, index: usize) -> usize {
+        if !self.is_hidden() {
+            return index;
+        }
+        self.buffer.with_untracked(|buff| {
+            buff.grapheme_indices(true)
+                .nth(index / SECURE_BULLET.len_utf8())
+                .map_or(buff.len(), |(idx, _)| idx)
+        })
+    }
+
+    /// Replaces `range` of the text with `text`, cut short to the maximum length, and moves the
+    /// cursor after it. Returns false if nothing changed.
+    fn edit(&mut self, range: Range<usize>, text: &str) -> bool {
+        if self.is_read_only() {
+            return false;
+        }
+        let filtered: String;
+        let text = match &self.char_filter {
+            Some(filter) => {
+                filtered = text.chars().filter(|c| filter(*c)).collect();
+                &filtered
+            }
+            None => text,
+        };
+        if let Some(mask) = self.mask.clone() {
+            return self.edit_masked(&mask, range, text);
+        }
+        let max_length = self.max_length;
+        let text = self
+            .buffer
+            .with_untracked(|buff| limit_insertion(buff, range.clone(), text, max_length));
+        if range.is_empty() && text.is_empty() {
+            return false;
+        }
+        let history = &mut self.history;
+        self.buffer
+            .with_untracked(|buff| history.record(buff, range.clone(), text));
+        self.buffer
+            .update(|buff| replace_range(buff, range.clone(), Some(text)));
+        self.cursor_glyph_idx = range.start + text.len();
+        self.selection = None;
+        true
+    }
+
+    fn edit_masked(&mut self, mask: &InputMask, range: Range<usize>, text: &str) -> bool {
+        let backward = self.cursor_glyph_idx == range.end;
+        let (new_text, cursor) = self
+            .buffer
+            .with_untracked(|buff| mask.edit(buff, range.clone(), text, backward));
+        if self.buffer.with_untracked(|buff| *buff == new_text) {
+            return false;
+        }
+        let history = &mut self.history;
+        self.buffer
+            .with_untracked(|buff| history.record(buff, range, text));
+        self.buffer.update(|buff| *buff = new_text);
+        self.cursor_glyph_idx = cursor;
+        self.selection = None;
+        true
+    }
+
+    /// Replaces the selection, or inserts at the cursor if there's none.
+    fn insert(&mut self, text: &str) -> bool {
+        let range = self
+            .selection
+            .clone()
+            .unwrap_or(self.cursor_glyph_idx..self.cursor_glyph_idx);
+        self.edit(range, text)
+    }
+
+    /// Deletes the selection, or from the cursor to where `movement` moves it if there's none.
+    fn delete(&mut self, movement: Movement, direction: Direction) -> bool {
+        if let Some(selection) = self.selection.clone() {
+            return self.edit(selection, "");
+        }
+        let prev_cursor_idx = self.cursor_glyph_idx;
+        self.move_cursor(movement, direction);
+        let moved_to = self.cursor_glyph_idx;
+        self.cursor_glyph_idx = prev_cursor_idx;
+        self.edit(
+            prev_cursor_idx.min(moved_to)..prev_cursor_idx.max(moved_to),
+            "",
+        )
+    }
+
+    fn undo(&mut self, redo: bool) -> bool {
+        if self.is_read_only() {
+            return false;
+        }
+        let text = self.buffer.get_untracked();
+        let restored = if redo {
+            self.history.redo(&text, self.cursor_glyph_idx)
+        } else {
+            self.history.undo(&text, self.cursor_glyph_idx)
+        };
+        let Some((text, cursor)) = restored else {
+            return false;
+        };
+        self.buffer.update(|buff| *buff = text);
+        self.cursor_glyph_idx = cursor;
+        self.selection = None;
+        true
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let selection = self.selection.clone()?;
+        self.buffer
+            .with_untracked(|buff| buff.get(selection).map(|text| text.to_string()))
+    }
+
     fn move_cursor(&mut self, move_kind: Movement, direction: Direction) -> bool {
+        // Word boundaries would give away hidden text
+        let move_kind = match move_kind {
+            Movement::Word if self.is_hidden() => Movement::Line,
+            move_kind => move_kind,
+        };
+        let moved = self.move_cursor_by(move_kind, direction);
+        if let Some(mask) = &self.mask {
+            let forward = matches!(direction, Direction::Right);
+            self.cursor_glyph_idx = self.buffer.with_untracked(|buff| {
+                mask.skip_literals(buff, self.cursor_glyph_idx.min(buff.len()), forward)
+            });
+        }
+        moved
+    }
+
+    fn move_cursor_by(&mut self, move_kind: Movement, direction: Direction) -> bool {
         match (move_kind, direction) {
             (Movement::Glyph, Direction::Left) => {
                 let untracked_buffer = self.buffer.get_untracked();
@@ -314,9 +942,10 @@ impl TextInput {
     }
 
     fn clip_text(&mut self, node_layout: &Layout) {
+        let display_text = self.display_text();
+        let cursor_text_loc = Cursor::new(0, self.to_display(self.cursor_glyph_idx));
         let virt_text = self.text_buf.as_mut().unwrap();
         let node_width = node_layout.size.width as f64;
-        let cursor_text_loc = Cursor::new(0, self.cursor_glyph_idx);
         let layout_cursor = virt_text.layout_cursor(cursor_text_loc);
         let cursor_glyph_pos = virt_text.hit_position(layout_cursor.glyph);
         let cursor_x = cursor_glyph_pos.point.x;
@@ -342,13 +971,10 @@ impl TextInput {
             .hit_point(Point::new(clip_start_x + node_width, 0.0))
             .index;
 
-        let new_text = self
-            .buffer
-            .get_untracked()
-            .chars()
-            .skip(clip_start)
-            .take(clip_end - clip_start)
-            .collect();
+        let new_text = display_text
+            .get(clip_start..clip_end)
+            .unwrap_or_default()
+            .to_string();
 
         self.cursor_x -= clip_start_x;
         self.clip_start_idx = clip_start;
@@ -384,9 +1010,68 @@ impl TextInput {
         )
     }
 
+    /// Extends the selection being dragged to `pos`, and scrolls along while it's past either
+    /// edge of the input.
+    fn drag_to(&mut self, pos: Point) {
+        let width = self.id.get_size().unwrap_or_default().width;
+        let selection_stop = self.get_box_position(pos.x.clamp(0.0, width), pos.y);
+        if selection_stop != self.cursor_glyph_idx {
+            self.cursor_glyph_idx = selection_stop;
+            self.update_drag_selection();
+        }
+        if pos.x < 0.0 || pos.x > width {
+            self.schedule_drag_tick();
+        }
+    }
+
+    fn schedule_drag_tick(&mut self) {
+        if !self.drag_tick_scheduled {
+            self.drag_tick_scheduled = true;
+            let id = self.id;
+            exec_after(
+                Duration::from_millis(DRAG_TICK_INTERVAL_MS),
+                Box::new(move |_| id.update_state(DragTick)),
+            );
+        }
+    }
+
+    /// Moves the cursor a glyph towards the pointer held past the edge of the input.
+    fn drag_tick(&mut self) {
+        self.drag_tick_scheduled = false;
+        let Some(pos) = self.drag_pos else {
+            return;
+        };
+        let width = self.id.get_size().unwrap_or_default().width;
+        if pos.x < 0.0 {
+            self.move_cursor(Movement::Glyph, Direction::Left);
+        } else if pos.x > width {
+            self.move_cursor(Movement::Glyph, Direction::Right);
+        } else {
+            return;
+        }
+        self.update_drag_selection();
+        self.id.request_layout();
+        self.schedule_drag_tick();
+    }
+
+    fn update_drag_selection(&mut self) {
+        if self.cursor_glyph_idx == self.drag_anchor {
+            self.selection = None;
+        } else {
+            self.update_selection(self.drag_anchor, self.cursor_glyph_idx);
+        }
+    }
+
     fn handle_double_click(&mut self, pos_x: f64, pos_y: f64) {
         let clicked_glyph_idx = self.get_box_position(pos_x, pos_y);
 
+        if self.is_hidden() {
+            let len = self.buffer.with_untracked(|buff| buff.len());
+            self.cursor_glyph_idx = len;
+            self.selection = Some(0..len);
+            return;
+        }
+
         self.buffer.with_untracked(|buff| {
             let selection = get_dbl_click_selection(clicked_glyph_idx, buff);
             self.cursor_glyph_idx = selection.end;
@@ -408,7 +1093,8 @@ impl TextInput {
             PxPct::Px(padding) => padding as f32,
             PxPct::Pct(pct) => pct as f32 * layout.size.width,
         };
-        self.text_buf
+        let index = self
+            .text_buf
             .as_ref()
             .unwrap()
             .hit_point(Point::new(
@@ -417,7 +1103,8 @@ impl TextInput {
                 // slightly below the text
                 pos_y - padding_top as f64,
             ))
-            .index
+            .index;
+        self.from_display(index)
     }
 
     fn get_selection_rect(&self, node_layout: &Layout, left_padding: f64) -> Rect {
@@ -430,12 +1117,19 @@ impl TextInput {
         let virtual_text = self.text_buf.as_ref().unwrap();
         let text_height = virtual_text.size().height;
 
-        let selection_start_x =
-            virtual_text.hit_position(selection.start).point.x - self.clip_start_x;
+        let selection_start_x = virtual_text
+            .hit_position(self.to_display(selection.start))
+            .point
+            .x
+            - self.clip_start_x;
         let selection_start_x = selection_start_x.max(node_layout.location.x as f64 - left_padding);
 
-        let selection_end_x =
-            virtual_text.hit_position(selection.end).point.x + left_padding - self.clip_start_x;
+        let selection_end_x = virtual_text
+            .hit_position(self.to_display(selection.end))
+            .point
+            .x
+            + left_padding
+            - self.clip_start_x;
         let selection_end_x =
             selection_end_x.min(selection_start_x + self.width as f64 + left_padding);
 
@@ -478,8 +1172,7 @@ impl TextInput {
         let mut text_layout = TextLayout::new();
         let attrs_list = self.get_text_attrs();
 
-        self.buffer
-            .with_untracked(|buff| text_layout.set_text(buff, attrs_list.clone()));
+        text_layout.set_text(&self.display_text(), attrs_list.clone());
 
         let glyph_max_size = self.get_font_glyph_max_size();
         self.height = glyph_max_size.height as f32;
@@ -579,35 +1272,18 @@ impl TextInput {
                 self.select_all();
                 true
             }
+            // Secure text is kept off the clipboard
+            TextCommand::Copy | TextCommand::Cut if self.secure => true,
             TextCommand::Copy => {
-                if let Some(selection) = &self.selection {
-                    let selection_txt = self
-                        .buffer
-                        .get_untracked()
-                        .chars()
-                        .skip(selection.start)
-                        .take(selection.end - selection.start)
-                        .collect();
+                if let Some(selection_txt) = self.selected_text() {
                     let _ = Clipboard::set_contents(selection_txt);
                 }
                 true
             }
             TextCommand::Cut => {
-                if let Some(selection) = &self.selection {
-                    let selection_txt = self
-                        .buffer
-                        .get_untracked()
-                        .chars()
-                        .skip(selection.start)
-                        .take(selection.end - selection.start)
-                        .collect();
+                if let Some(selection_txt) = self.selected_text() {
                     let _ = Clipboard::set_contents(selection_txt);
-
-                    self.buffer
-                        .update(|buf| replace_range(buf, selection.clone(), None));
-
-                    self.cursor_glyph_idx = selection.start;
-                    self.selection = None;
+                    self.insert("");
                 }
 
                 true
@@ -617,30 +1293,23 @@ impl TextInput {
                     Ok(content) => content,
                     Err(_) => return false,
                 };
-                if clipboard_content.is_empty() {
-                    return false;
-                }
-
-                if let Some(selection) = &self.selection {
-                    self.buffer.update(|buf| {
-                        replace_range(buf, selection.clone(), Some(&clipboard_content))
-                    });
-
-                    self.cursor_glyph_idx +=
-                        clipboard_content.len() - selection.len().min(clipboard_content.len());
-                    self.selection = None;
-                } else {
-                    self.buffer
-                        .update(|buf| buf.insert_str(self.cursor_glyph_idx, &clipboard_content));
-                    self.cursor_glyph_idx += clipboard_content.len();
+                if !clipboard_content.is_empty() {
+                    // The input is a single line, so pasted lines are joined
+                    let clipboard_content = clipboard_content.replace(['\r', '\n'], " ");
+                    self.insert(&clipboard_content);
                 }
 
                 true
             }
-            TextCommand::None => {
-                self.selection = None;
-                false
+            TextCommand::Undo => {
+                self.undo(false);
+                true
             }
+            TextCommand::Redo => {
+                self.undo(true);
+                true
+            }
+            TextCommand::None => false,
         }
     }
 
@@ -652,72 +1321,22 @@ impl TextInput {
                 .text
                 .as_ref()
                 .map_or(false, |ch| self.insert_text(event, ch)),
-            Key::Named(NamedKey::Space) => {
-                if let Some(selection) = &self.selection {
-                    self.buffer
-                        .update(|buf| replace_range(buf, selection.clone(), None));
-                    self.cursor_glyph_idx = selection.start;
-                    self.selection = None;
-                } else {
-                    self.buffer
-                        .update(|buf| buf.insert(self.cursor_glyph_idx, ' '));
-                }
-                self.move_cursor(Movement::Glyph, Direction::Right)
-            }
-            Key::Named(NamedKey::Backspace) => {
-                let selection = self.selection.clone();
-                if let Some(selection) = selection {
-                    self.cursor_glyph_idx = selection.start;
-                    self.buffer
-                        .update(|buf| replace_range(buf, selection, None));
-                    self.selection = None;
-                    true
-                } else {
-                    let prev_cursor_idx = self.cursor_glyph_idx;
-
-                    self.move_cursor(
-                        get_word_based_motion(event).unwrap_or(Movement::Glyph),
-                        Direction::Left,
-                    );
-
-                    if self.cursor_glyph_idx == prev_cursor_idx {
-                        return false;
-                    }
-
-                    self.buffer.update(|buf| {
-                        replace_range(buf, self.cursor_glyph_idx..prev_cursor_idx, None);
-                    });
+            Key::Named(NamedKey::Space) => self.insert(" "),
+            Key::Named(NamedKey::Backspace) => self.delete(
+                get_word_based_motion(event).unwrap_or(Movement::Glyph),
+                Direction::Left,
+            ),
+            Key::Named(NamedKey::Delete) => self.delete(
+                get_word_based_motion(event).unwrap_or(Movement::Glyph),
+                Direction::Right,
+            ),
+            Key::Named(NamedKey::Enter) => match &self.on_submit {
+                Some(on_submit) => {
+                    self.buffer.with_untracked(|buff| on_submit(buff));
                     true
                 }
-            }
-            Key::Named(NamedKey::Delete) => {
-                let selection = self.selection.clone();
-                if let Some(selection) = selection {
-                    self.cursor_glyph_idx = selection.start;
-                    self.buffer
-                        .update(|buf| replace_range(buf, selection, None));
-                    self.selection = None;
-                    return true;
-                }
-
-                let prev_cursor_idx = self.cursor_glyph_idx;
-
-                self.move_cursor(
-                    get_word_based_motion(event).unwrap_or(Movement::Glyph),
-                    Direction::Right,
-                );
-
-                if self.cursor_glyph_idx == prev_cursor_idx {
-                    return false;
-                }
-
-                self.buffer.update(|buf| {
-                    replace_range(buf, prev_cursor_idx..self.cursor_glyph_idx, None);
-                });
-
-                self.cursor_glyph_idx = prev_cursor_idx;
-                true
-            }
+                None => false,
+            },
             Key::Named(NamedKey::Escape) => {
                 cx.app_state.clear_focus();
                 true
@@ -802,17 +1421,7 @@ impl TextInput {
             return true;
         }
 
-        let selection = self.selection.clone();
-        if let Some(selection) = selection {
-            self.buffer
-                .update(|buf| replace_range(buf, selection.clone(), None));
-            self.cursor_glyph_idx = selection.start;
-            self.selection = None;
-        }
-
-        self.buffer
-            .update(|buf| buf.insert_str(self.cursor_glyph_idx, &ch.clone()));
-        self.move_cursor(Movement::Glyph, Direction::Right)
+        self.insert(ch)
     }
 
     fn move_selection(
@@ -896,7 +1505,7 @@ impl TextInput {
     }
 }
 
-fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option<&str>) {
+pub(crate) fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option<&str>) {
     assert!(del_range.start <= del_range.end);
     if !buff.is_char_boundary(del_range.end) {
         eprintln!(
@@ -927,7 +1536,7 @@ fn replace_range(buff: &mut String, del_range: Range<usize>, replacement: Option
     buff.push_str(&after_del_range);
 }
 
-fn get_dbl_click_selection(glyph_idx: usize, buffer: &String) -> Range<usize> {
+pub(crate) fn get_dbl_click_selection(glyph_idx: usize, buffer: &String) -> Range<usize> {
     let mut selectable_ranges: Vec<Range<usize>> = Vec::new();
     let glyph_idx = usize::min(glyph_idx, buffer.len().saturating_sub(1));
 
@@ -970,15 +1579,32 @@ impl View for TextInput {
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
-        format!("TextInput: {:?}", self.buffer.get_untracked()).into()
+        format!("TextInput: {:?}", self.display_text()).into()
     }
 
-    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) {
-        if let Ok(state) = state.downcast::<(String, bool)>() {
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) {
+        if state.is::<DragTick>() {
+            if cx.app_state.is_active(&self.id) {
+                self.drag_tick();
+            } else {
+                self.drag_pos = None;
+                self.drag_tick_scheduled = false;
+            }
+        } else if let Some(Revealed(revealed)) = state.downcast_ref::<Revealed>() {
+            if self.revealed != *revealed {
+                self.revealed = *revealed;
+                self.id.request_layout();
+            }
+        } else if let Ok(state) = state.downcast::<(String, bool)>() {
             let (value, is_focused) = *state;
 
             // Only update recomputation if the state has actually changed
             if self.is_focused != is_focused || value != self.buffer.last_buffer {
+                if value != self.buffer.last_buffer {
+                    // The text was set from outside, which the undo history doesn't know about
+                    self.history.clear();
+                    self.buffer.last_buffer = value;
+                }
                 if is_focused {
                     self.cursor_glyph_idx = self.buffer.with_untracked(|buf| buf.len());
                 }
@@ -990,6 +1616,17 @@ impl View for TextInput {
         }
     }
 
+    #[cfg(feature = "accessibility")]
+    fn accessibility(&self, node: &mut NodeBuilder) {
+        node.set_value(self.display_text());
+        if let Some(placeholder) = &self.placeholder_text {
+            node.set_placeholder(placeholder.clone());
+        }
+        if self.is_read_only() {
+            node.set_read_only();
+        }
+    }
+
     fn event_before_children(&mut self, cx: &mut EventCx, event: &Event) -> EventPropagation {
         let buff_len = self.buffer.with_untracked(|buff| buff.len());
         // Workaround for cursor going out of bounds when text buffer is modified externally
@@ -1011,20 +1648,27 @@ impl View for TextInput {
 
                 if event.count == 2 {
                     self.handle_double_click(event.pos.x, event.pos.y);
+                    self.drag_anchor = self.selection.as_ref().map_or(0, |s| s.start);
                 } else {
                     self.cursor_glyph_idx = self.get_box_position(event.pos.x, event.pos.y);
                     self.selection = None;
+                    self.drag_anchor = self.cursor_glyph_idx;
                 }
+                self.drag_pos = Some(event.pos);
                 true
             }
             Event::PointerMove(event) => {
                 self.id.request_layout();
-                if cx.is_active(self.id) {
-                    let selection_stop = self.get_box_position(event.pos.x, event.pos.y);
-                    self.update_selection(self.cursor_glyph_idx, selection_stop);
+                if cx.is_active(self.id) && self.drag_pos.is_some() {
+                    self.drag_pos = Some(event.pos);
+                    self.drag_to(event.pos);
                 }
                 false
             }
+            Event::PointerUp(_) => {
+                self.drag_pos = None;
+                false
+            }
             Event::KeyDown(event) => self.handle_key_down(cx, event),
             _ => false,
         };
@@ -1158,7 +1802,7 @@ impl View for TextInput {
                 .text_buf
                 .as_ref()
                 .unwrap()
-                .hit_position(self.cursor_glyph_idx);
+                .hit_position(self.to_display(self.cursor_glyph_idx));
             self.cursor_x = hit_pos.point.x;
         }
 
@@ -1233,7 +1877,105 @@ impl View for TextInput {
 mod tests {
     use crate::views::text_input::get_dbl_click_selection;
 
-    use super::replace_range;
+    use super::{limit_insertion, replace_range, History, InputMask};
+
+    #[test]
+    fn mask_format_and_raw() {
+        let mask = InputMask::new("(999) 999-9999");
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+        assert_eq!(mask.format("555"), "(555");
+        assert_eq!(mask.format("55512"), "(555) 12");
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.raw("(555) 123-4567"), "5551234567");
+        assert_eq!(mask.raw("555-123"), "555123");
+
+        let date = InputMask::new("99/99/9999");
+        assert!(date.is_complete("12/31/2024"));
+        assert!(!date.is_complete("12/31"));
+
+        let escaped = InputMask::new(r"\9-99");
+        assert_eq!(escaped.format("12"), "9-12");
+    }
+
+    #[test]
+    fn mask_edit() {
+        let mask = InputMask::new("(999) 999-9999");
+        assert_eq!(
+            mask.edit("(555", 4..4, "1", true),
+            ("(555) 1".to_string(), 7)
+        );
+        // Deleting only literals deletes the digit before them
+        assert_eq!(
+            mask.edit("(555) 1", 4..6, "", true),
+            ("(551".to_string(), 3)
+        );
+        assert_eq!(
+            mask.edit("", 0..0, "(555) 123-4567", true),
+            ("(555) 123-4567".to_string(), 14)
+        );
+        assert_eq!(mask.edit("(555", 4..4, "x", true), ("(555".to_string(), 4));
+    }
+
+    #[test]
+    fn mask_skip_literals() {
+        let mask = InputMask::new("(999) 999-9999");
+        assert_eq!(mask.skip_literals("(555) 1", 5, true), 6);
+        assert_eq!(mask.skip_literals("(555) 1", 6, false), 4);
+        assert_eq!(mask.skip_literals("(555) 1", 2, true), 2);
+    }
+
+    #[test]
+    fn limit_insertion_to_max_length() {
+        assert_eq!(limit_insertion("abc", 3..3, "def", None), "def");
+        assert_eq!(limit_insertion("abc", 3..3, "def", Some(5)), "de");
+        assert_eq!(limit_insertion("abc", 0..2, "def", Some(5)), "def");
+        assert_eq!(limit_insertion("abcde", 5..5, "f", Some(5)), "");
+        // Characters, not bytes, are counted.
+        assert_eq!(limit_insertion("äö", 2..2, "üß", Some(3)), "ü");
+    }
+
+    #[test]
+    fn undo_typing_by_word() {
+        let mut history = History::default();
+        let mut text = String::new();
+        for ch in ["a", "b", " ", "c"] {
+            let at = text.len();
+            history.record(&text, at..at, ch);
+            text.push_str(ch);
+        }
+        assert_eq!(text, "ab c");
+
+        let (text, cursor) = history.undo("ab c", 4).unwrap();
+        assert_eq!((text.as_str(), cursor), ("ab ", 3));
+        let (text, _) = history.undo(&text, cursor).unwrap();
+        assert_eq!(text, "ab");
+        let (text, _) = history.undo(&text, 2).unwrap();
+        assert_eq!(text, "");
+        assert!(history.undo(&text, 0).is_none());
+
+        let (text, cursor) = history.redo("", 0).unwrap();
+        assert_eq!((text.as_str(), cursor), ("ab", 2));
+    }
+
+    #[test]
+    fn undo_after_moving_cursor() {
+        let mut history = History::default();
+        history.record("", 0..0, "a");
+        // Typing somewhere else isn't merged with the previous typing.
+        history.record("a", 0..0, "b");
+        let (text, _) = history.undo("ba", 1).unwrap();
+        assert_eq!(text, "a");
+    }
+
+    #[test]
+    fn edit_clears_redo() {
+        let mut history = History::default();
+        history.record("abc", 0..3, "");
+        let (text, cursor) = history.undo("", 0).unwrap();
+        assert_eq!((text.as_str(), cursor), ("abc", 3));
+        history.record(&text, 3..3, "d");
+        assert!(history.redo("abcd", 4).is_none());
+    }
 
     #[test]
     fn replace_range_start() {
@@ -1,4 +1,5 @@
-use peniko::kurbo::Point;
+use floem_reactive::{create_rw_signal, RwSignal, SignalGet, SignalUpdate};
+use peniko::kurbo::{Point, Rect};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -8,10 +9,11 @@ use std::time::Duration;
 use web_time::Duration;
 
 use crate::style::{Style, StyleClass as _};
+use crate::views::popover::{place, Align, Side, WINDOW_MARGIN};
 use crate::views::Decorators;
 use crate::{
-    action::{add_overlay, exec_after, remove_overlay, TimerToken},
-    context::{EventCx, UpdateCx},
+    action::{add_layer, exec_after, remove_overlay, TimerToken},
+    context::{ComputeLayoutCx, EventCx, UpdateCx},
     event::{Event, EventPropagation},
     id::ViewId,
     prop, prop_extractor, style_class,
@@ -80,13 +82,31 @@ impl View for Tooltip {
                     let tip = self.tip.clone();
 
                     let tip_style = self.tip_style.clone();
-                    let overlay_id = add_overlay(
-                        window_origin
-                            + self.hover.unwrap().0.to_vec2()
-                            + (10. / self.scale, 10. / self.scale),
-                        move |_| tip().style(move |_| tip_style.clone()),
-                    );
-                    // overlay_id.request_all();
+                    // The tip goes below and to the right of the pointer, flipping above it and
+                    // shifting left at the edges of the window.
+                    let pos = window_origin + self.hover.unwrap().0.to_vec2();
+                    let offset = 10. / self.scale;
+                    let anchor = Rect::new(pos.x + offset, pos.y, pos.x + offset, pos.y + offset);
+                    let overlay_id = add_layer(move |_| {
+                        let origin = create_rw_signal(Point::new(anchor.x0, anchor.y1));
+                        let tip = tip().style(move |_| {
+                            let origin = origin.get();
+                            tip_style
+                                .clone()
+                                .absolute()
+                                .inset_left(origin.x)
+                                .inset_top(origin.y)
+                        });
+                        let id = ViewId::new();
+                        let tip_id = tip.id();
+                        id.set_children(vec![tip.into_any()]);
+                        TooltipLayer {
+                            id,
+                            tip: tip_id,
+                            anchor,
+                            origin,
+                        }
+                    });
                     *self.overlay.borrow_mut() = Some(overlay_id);
                 }
             }
@@ -133,15 +153,49 @@ impl View for Tooltip {
         EventPropagation::Continue
     }
 
-    fn compute_layout(
-        &mut self,
-        cx: &mut crate::context::ComputeLayoutCx,
-    ) -> Option<peniko::kurbo::Rect> {
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
         self.window_origin = Some(cx.window_origin);
         default_compute_layout(self.id, cx)
     }
 }
 
+/// A layer covering the window that keeps a tooltip next to the pointer and inside the window.
+struct TooltipLayer {
+    id: ViewId,
+    tip: ViewId,
+    anchor: Rect,
+    origin: RwSignal<Point>,
+}
+
+impl View for TooltipLayer {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::new().size_full())
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tooltip".into()
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        let bounds = self
+            .id
+            .get_size()
+            .unwrap_or_default()
+            .to_rect()
+            .inset(-WINDOW_MARGIN);
+        let size = self.tip.get_size().unwrap_or_default();
+        let origin = place(self.anchor, size, bounds, Side::Bottom, Align::Start, 0.0).origin;
+        if self.origin.get_untracked() != origin {
+            self.origin.set(origin);
+        }
+        default_compute_layout(self.id, cx)
+    }
+}
+
 pub trait TooltipExt {
     fn tooltip<V: IntoView + 'static>(self, tip: impl Fn() -> V + 'static) -> Tooltip;
 }
@@ -0,0 +1,999 @@
+//! A virtualized tree, such as a file explorer or an outline.
+//!
+//! The tree is flattened into the rows of the expanded nodes, and only the rows inside the
+//! viewport get a view, so it scales to very large trees. The children of a node are loaded the
+//! first time it's expanded, either right away or in the background with
+//! [`TreeChildren::Pending`] and [`TreeState::set_children`].
+//!
+//! The arrow keys move between rows, Left and Right collapse and expand nodes, `*` expands all
+//! siblings of the focused node and typing the start of a row's text jumps to it once
+//! [`TreeView::search_text`] is set.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Range,
+    rc::Rc,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+#[cfg(feature = "accessibility")]
+use accesskit::Role;
+use floem_reactive::{
+    create_effect, create_rw_signal, create_trigger, RwSignal, SignalGet, SignalUpdate, SignalWith,
+    Trigger,
+};
+use peniko::{
+    kurbo::{Line, Rect},
+    Color,
+};
+
+use crate::{
+    context::{ComputeLayoutCx, EventCx, PaintCx, StyleCx},
+    drag::DropEffect,
+    event::{Event, EventPropagation},
+    id::ViewId,
+    keyboard::{Key, Modifiers, NamedKey},
+    prop, prop_extractor, style_class,
+    view::{default_compute_layout, IntoView, View},
+    views::{
+        h_stack, highlight, svg, virtual_stack, Decorators, VirtualDirection, VirtualItemSize,
+        VirtualVector,
+    },
+};
+
+style_class!(pub TreeViewClass);
+style_class!(pub TreeRowClass);
+style_class!(pub TreeExpanderClass);
+
+prop!(pub TreeIndent: f64 {} = 16.0);
+prop!(pub TreeRowHeight: f64 {} = 22.0);
+prop!(pub TreeGuideColor: Option<Color> {} = None);
+
+prop_extractor! {
+    TreeStyle {
+        indent: TreeIndent,
+        row_height: TreeRowHeight,
+        guide_color: TreeGuideColor,
+    }
+}
+
+const COLLAPSED_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M6 4l4 4-4 4" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+const EXPANDED_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><path d="M4 6l4 4 4-4" stroke="currentColor" stroke-width="1.5" fill="none"/></svg>"#;
+
+/// The time after which typed characters start a new search instead of extending it.
+const SEARCH_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// The children of a tree node, returned by the children function of [`tree_view`].
+pub enum TreeChildren<T> {
+    /// The children are available right away.
+    Ready(Vec<T>),
+    /// The children are loaded in the background. Pass them to [`TreeState::set_children`]
+    /// once they're ready.
+    Pending,
+}
+
+/// Where a drop on a row goes, relative to the node of the row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeDropPosition {
+    /// Before the node, as its previous sibling.
+    Before,
+    /// Inside the node, as one of its children.
+    Inside,
+    /// After the node, as its next sibling.
+    After,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum RowKind {
+    Leaf,
+    Collapsed,
+    Expanded,
+    /// Expanded, with the children still loading.
+    Loading,
+}
+
+#[derive(Clone)]
+struct TreeRow<T, K> {
+    item: T,
+    key: K,
+    depth: usize,
+    /// The index of the row of the parent node.
+    parent: Option<usize>,
+    kind: RowKind,
+}
+
+/// The rows of the expanded nodes, in order, and the index of the row of each key.
+fn flatten<T: Clone, K: Eq + Hash + Clone>(
+    roots: &[T],
+    key_fn: &dyn Fn(&T) -> K,
+    has_children: &dyn Fn(&T) -> bool,
+    expanded: &HashSet<K>,
+    children: &mut dyn FnMut(&T, &K) -> Option<Vec<T>>,
+) -> (Vec<TreeRow<T, K>>, HashMap<K, usize>) {
+    let mut rows = Vec::new();
+    let mut indices = HashMap::new();
+    let mut stack: Vec<(T, usize, Option<usize>)> = roots
+        .iter()
+        .rev()
+        .map(|root| (root.clone(), 0, None))
+        .collect();
+    while let Some((item, depth, parent)) = stack.pop() {
+        let key = key_fn(&item);
+        let index = rows.len();
+        let kind = if !has_children(&item) {
+            RowKind::Leaf
+        } else if !expanded.contains(&key) {
+            RowKind::Collapsed
+        } else {
+            match children(&item, &key) {
+                Some(children) if children.is_empty() => RowKind::Leaf,
+                Some(children) => {
+                    stack.extend(
+                        children
+                            .into_iter()
+                            .rev()
+                            .map(|child| (child, depth + 1, Some(index))),
+                    );
+                    RowKind::Expanded
+                }
+                None => RowKind::Loading,
+            }
+        };
+        indices.insert(key.clone(), index);
+        rows.push(TreeRow {
+            item,
+            key,
+            depth,
+            parent,
+            kind,
+        });
+    }
+    (rows, indices)
+}
+
+struct TreeRows<T, K>(Rc<Vec<TreeRow<T, K>>>);
+
+impl<T: Clone, K: Clone> VirtualVector<TreeRow<T, K>> for TreeRows<T, K> {
+    fn total_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn slice(&mut self, range: Range<usize>) -> impl Iterator<Item = TreeRow<T, K>> {
+        let end = range.end.min(self.0.len());
+        let start = range.start.min(end);
+        self.0[start..end].iter().cloned()
+    }
+}
+
+/// The expansion, selection and loaded children of a [`tree_view`].
+pub struct TreeState<T: 'static, K: 'static> {
+    expanded: RwSignal<HashSet<K>>,
+    selection: RwSignal<HashSet<K>>,
+    cursor: RwSignal<Option<K>>,
+    /// The loaded children of the nodes that have been expanded, `None` while loading.
+    children: Rc<RefCell<HashMap<K, Option<Vec<T>>>>>,
+    children_changed: Trigger,
+}
+
+impl<T, K> Clone for TreeState<T, K> {
+    fn clone(&self) -> Self {
+        Self {
+            expanded: self.expanded,
+            selection: self.selection,
+            cursor: self.cursor,
+            children: self.children.clone(),
+            children_changed: self.children_changed,
+        }
+    }
+}
+
+impl<T: 'static, K: Eq + Hash + Clone + 'static> TreeState<T, K> {
+    /// The keys of the expanded nodes.
+    pub fn expanded(&self) -> RwSignal<HashSet<K>> {
+        self.expanded
+    }
+
+    /// The keys of the selected nodes.
+    pub fn selection(&self) -> RwSignal<HashSet<K>> {
+        self.selection
+    }
+
+    /// The key of the node the keyboard navigation starts from, usually the last one clicked.
+    pub fn cursor(&self) -> RwSignal<Option<K>> {
+        self.cursor
+    }
+
+    pub fn is_expanded(&self, key: &K) -> bool {
+        self.expanded.with(|expanded| expanded.contains(key))
+    }
+
+    /// Returns `true` if the node is expanded and its children are still loading.
+    pub fn is_loading(&self, key: &K) -> bool {
+        self.children_changed.track();
+        self.is_expanded(key) && matches!(self.children.borrow().get(key), Some(None))
+    }
+
+    pub fn expand(&self, key: K) {
+        if !self.is_expanded(&key) {
+            self.expanded.update(|expanded| {
+                expanded.insert(key);
+            });
+        }
+    }
+
+    pub fn collapse(&self, key: &K) {
+        if self.is_expanded(key) {
+            self.expanded.update(|expanded| {
+                expanded.remove(key);
+            });
+        }
+    }
+
+    pub fn toggle(&self, key: K) {
+        if self.is_expanded(&key) {
+            self.collapse(&key);
+        } else {
+            self.expand(key);
+        }
+    }
+
+    /// Sets the children of a node, for nodes whose children were [`TreeChildren::Pending`] or
+    /// have changed.
+    pub fn set_children(&self, key: K, children: Vec<T>) {
+        self.children.borrow_mut().insert(key, Some(children));
+        self.children_changed.notify();
+    }
+
+    /// Forgets the children of a node, so they're loaded again the next time it's shown
+    /// expanded.
+    pub fn reload(&self, key: &K) {
+        if self.children.borrow_mut().remove(key).is_some() {
+            self.children_changed.notify();
+        }
+    }
+}
+
+struct TreeCore<T: 'static, K: 'static> {
+    state: TreeState<T, K>,
+    rows: RwSignal<Rc<Vec<TreeRow<T, K>>>>,
+    /// The index of the row of each key, rebuilt with the rows.
+    indices: Rc<RefCell<HashMap<K, usize>>>,
+    row_height: RwSignal<f64>,
+    /// The row a range selection with Shift starts from.
+    anchor: RefCell<Option<K>>,
+    multi_select: Cell<bool>,
+    search_text: RefCell<Option<Box<dyn Fn(&T) -> String>>>,
+    search: RefCell<(String, Option<Instant>)>,
+    on_activate: RefCell<Option<Box<dyn Fn(&T)>>>,
+    on_drop: RefCell<Option<Box<dyn Fn(K, &T, TreeDropPosition)>>>,
+    has_children: Rc<RefCell<Box<dyn Fn(&T) -> bool>>>,
+}
+
+impl<T: Clone + 'static, K: Eq + Hash + Clone + 'static> TreeCore<T, K> {
+    fn index_of(&self, key: &K) -> Option<usize> {
+        self.indices.borrow().get(key).copied()
+    }
+
+    fn row(&self, index: usize) -> Option<TreeRow<T, K>> {
+        self.rows.with_untracked(|rows| rows.get(index).cloned())
+    }
+
+    fn cursor_index(&self) -> Option<usize> {
+        self.state
+            .cursor
+            .with_untracked(|cursor| cursor.as_ref().and_then(|key| self.index_of(key)))
+    }
+
+    /// Selects the row at `index` the way a click with `modifiers` does.
+    fn select(&self, index: usize, modifiers: Modifiers) {
+        let Some(row) = self.row(index) else {
+            return;
+        };
+        let multi = self.multi_select.get();
+        let anchor = self
+            .anchor
+            .borrow()
+            .as_ref()
+            .and_then(|key| self.index_of(key));
+        if let Some(anchor) = anchor.filter(|_| multi && modifiers.shift()) {
+            let range = anchor.min(index)..=anchor.max(index);
+            let keys = self
+                .rows
+                .with_untracked(|rows| rows[range].iter().map(|row| row.key.clone()).collect());
+            self.state.selection.set(keys);
+        } else if multi && (modifiers.control() || modifiers.meta()) {
+            self.state.selection.update(|selection| {
+                if !selection.remove(&row.key) {
+                    selection.insert(row.key.clone());
+                }
+            });
+            *self.anchor.borrow_mut() = Some(row.key.clone());
+        } else {
+            self.state.selection.set(HashSet::from([row.key.clone()]));
+            *self.anchor.borrow_mut() = Some(row.key.clone());
+        }
+        self.state.cursor.set(Some(row.key));
+    }
+
+    fn activate(&self, index: usize) {
+        if let (Some(row), Some(on_activate)) = (self.row(index), &*self.on_activate.borrow()) {
+            on_activate(&row.item);
+        }
+    }
+
+    /// Expands every collapsed sibling of the row at `index`.
+    fn expand_siblings(&self, index: usize) {
+        let keys: Vec<K> = self.rows.with_untracked(|rows| {
+            let parent = rows[index].parent;
+            rows.iter()
+                .filter(|row| row.parent == parent && row.kind == RowKind::Collapsed)
+                .map(|row| row.key.clone())
+                .collect()
+        });
+        if !keys.is_empty() {
+            self.state.expanded.update(|expanded| expanded.extend(keys));
+        }
+    }
+
+    /// Returns `true` if typed characters extend a search rather than starting a new one.
+    fn is_searching(&self) -> bool {
+        let search = self.search.borrow();
+        self.search_text.borrow().is_some()
+            && !search.0.is_empty()
+            && search
+                .1
+                .is_some_and(|last| Instant::now().duration_since(last) <= SEARCH_TIMEOUT)
+    }
+
+    /// Finds the next row whose search text starts with the typed characters.
+    fn type_ahead(&self, text: &str) -> Option<usize> {
+        let search_text = self.search_text.borrow();
+        let search_text = search_text.as_ref()?;
+        let mut search = self.search.borrow_mut();
+        let now = Instant::now();
+        if search
+            .1
+            .map_or(true, |last| now.duration_since(last) > SEARCH_TIMEOUT)
+        {
+            search.0.clear();
+        }
+        search.0.push_str(&text.to_lowercase());
+        search.1 = Some(now);
+
+        self.rows.with_untracked(|rows| {
+            let len = rows.len();
+            // A new search starts after the focused row, a longer one includes it.
+            let start = self.cursor_index().map_or(0, |i| {
+                if search.0.chars().count() == 1 {
+                    i + 1
+                } else {
+                    i
+                }
+            });
+            (0..len).map(|i| (start + i) % len).find(|&i| {
+                search_text(&rows[i].item)
+                    .to_lowercase()
+                    .starts_with(&search.0)
+            })
+        })
+    }
+
+    /// Returns `true` if `dragged` can be dropped on the row of `target` without moving a node
+    /// inside itself.
+    fn can_drop(&self, dragged: &K, target: &K) -> bool {
+        self.rows.with_untracked(|rows| {
+            let mut index = rows.iter().position(|row| row.key == *target);
+            while let Some(i) = index {
+                if rows[i].key == *dragged {
+                    return false;
+                }
+                index = rows[i].parent;
+            }
+            true
+        })
+    }
+}
+
+/// The payload of a row being dragged.
+struct TreeDrag<K>(K);
+
+/// A virtualized tree. See [`tree_view`].
+pub struct TreeView<T: 'static, K: 'static> {
+    id: ViewId,
+    child: ViewId,
+    core: Rc<TreeCore<T, K>>,
+    style: TreeStyle,
+    indent: RwSignal<f64>,
+    viewport: Rect,
+}
+
+/// A virtualized tree of the nodes returned by `roots` and their descendants.
+///
+/// `key_fn` identifies nodes, `children_fn` loads the children of a node the first time it's
+/// expanded and `view_fn` builds the content of a row. By default every node can be expanded
+/// until its children turn out to be empty, use [`TreeView::has_children`] to tell leaves apart
+/// up front.
+///
+/// The tree has to be inside a [`scroll`](super::scroll()) view.
+///
+/// ```rust
+/// # use floem::views::{scroll, text, tree_view, TreeChildren};
+/// scroll(tree_view(
+///     || vec![1u32],
+///     |n| *n,
+///     |n| TreeChildren::Ready((0..10).map(|i| n * 10 + i).collect()),
+///     |n| text(n),
+/// ));
+/// ```
+pub fn tree_view<T, K, RF, KF, CF, VF, V>(
+    roots: RF,
+    key_fn: KF,
+    children_fn: CF,
+    view_fn: VF,
+) -> TreeView<T, K>
+where
+    T: Clone + 'static,
+    K: Eq + Hash + Clone + 'static,
+    RF: Fn() -> Vec<T> + 'static,
+    KF: Fn(&T) -> K + 'static,
+    CF: Fn(&T) -> TreeChildren<T> + 'static,
+    VF: Fn(&T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+    let state = TreeState {
+        expanded: create_rw_signal(HashSet::new()),
+        selection: create_rw_signal(HashSet::new()),
+        cursor: create_rw_signal(None),
+        children: Rc::new(RefCell::new(HashMap::new())),
+        children_changed: create_trigger(),
+    };
+    let rows = create_rw_signal(Rc::new(Vec::new()));
+    let indices = Rc::new(RefCell::new(HashMap::new()));
+    let row_height = create_rw_signal(TreeRowHeight::default_value());
+    let indent = create_rw_signal(TreeIndent::default_value());
+    let has_children: Rc<RefCell<Box<dyn Fn(&T) -> bool>>> =
+        Rc::new(RefCell::new(Box::new(|_| true)));
+    let core = Rc::new(TreeCore {
+        state: state.clone(),
+        rows,
+        indices: indices.clone(),
+        row_height,
+        anchor: RefCell::new(None),
+        multi_select: Cell::new(false),
+        search_text: RefCell::new(None),
+        search: RefCell::new((String::new(), None)),
+        on_activate: RefCell::new(None),
+        on_drop: RefCell::new(None),
+        has_children: has_children.clone(),
+    });
+
+    {
+        let state = state.clone();
+        let has_children = has_children.clone();
+        create_effect(move |_| {
+            let roots = roots();
+            state.children_changed.track();
+            let has_children = has_children.borrow();
+            let (new_rows, new_indices) = state.expanded.with(|expanded| {
+                flatten(
+                    &roots,
+                    &key_fn,
+                    &**has_children,
+                    expanded,
+                    &mut |item, key| {
+                        state
+                            .children
+                            .borrow_mut()
+                            .entry(key.clone())
+                            .or_insert_with(|| match children_fn(item) {
+                                TreeChildren::Ready(children) => Some(children),
+                                TreeChildren::Pending => None,
+                            })
+                            .clone()
+                    },
+                )
+            });
+            *indices.borrow_mut() = new_indices;
+            rows.set(Rc::new(new_rows));
+        });
+    }
+
+    let stack = {
+        let core = core.clone();
+        virtual_stack(
+            VirtualDirection::Vertical,
+            VirtualItemSize::Fixed(Box::new(move || row_height.get())),
+            move || TreeRows(rows.get()),
+            // Rows are rebuilt when a node moves to another depth or is expanded.
+            |row: &TreeRow<T, K>| (row.key.clone(), row.depth, row.kind),
+            move |row| tree_row(row, &core, indent, &view_fn),
+        )
+        .style(|s| s.flex_col().width_full())
+    };
+    let child = stack.id();
+    id.set_children(vec![stack]);
+    #[cfg(feature = "accessibility")]
+    id.set_accessibility_role(Role::Tree);
+
+    TreeView {
+        id,
+        child,
+        core,
+        style: Default::default(),
+        indent,
+        viewport: Rect::ZERO,
+    }
+    .class(TreeViewClass)
+    .keyboard_navigatable()
+}
+
+fn tree_row<T, K, V>(
+    row: TreeRow<T, K>,
+    core: &Rc<TreeCore<T, K>>,
+    indent: RwSignal<f64>,
+    view_fn: &impl Fn(&T) -> V,
+) -> impl IntoView
+where
+    T: Clone + 'static,
+    K: Eq + Hash + Clone + 'static,
+    V: IntoView + 'static,
+{
+    let TreeRow {
+        item,
+        key,
+        depth,
+        kind,
+        ..
+    } = row;
+    let state = core.state.clone();
+    let row_height = core.row_height;
+
+    let expander = svg(match kind {
+        RowKind::Leaf => "",
+        RowKind::Collapsed => COLLAPSED_SVG,
+        RowKind::Expanded | RowKind::Loading => EXPANDED_SVG,
+    })
+    .class(TreeExpanderClass)
+    .on_click_stop({
+        let state = state.clone();
+        let key = key.clone();
+        move |_| {
+            if kind != RowKind::Leaf {
+                state.toggle(key.clone());
+            }
+        }
+    });
+
+    let content = h_stack((expander, view_fn(&item))).class(TreeRowClass);
+    #[cfg(feature = "accessibility")]
+    let content = content.accessibility_role(Role::TreeItem).accessibility({
+        let state = state.clone();
+        let key = key.clone();
+        move |node| {
+            node.set_level(depth + 1);
+            node.set_selected(state.selection.with_untracked(|s| s.contains(&key)));
+            if kind != RowKind::Leaf {
+                node.set_expanded(kind != RowKind::Collapsed);
+            }
+        }
+    });
+    let content = content
+        .on_click_stop({
+            let core = core.clone();
+            let key = key.clone();
+            move |e| {
+                let modifiers = match e {
+                    Event::PointerUp(e) => e.modifiers,
+                    _ => Modifiers::empty(),
+                };
+                if let Some(index) = core.index_of(&key) {
+                    core.select(index, modifiers);
+                }
+            }
+        })
+        .on_double_click_stop({
+            let core = core.clone();
+            let key = key.clone();
+            move |_| {
+                if kind != RowKind::Leaf {
+                    core.state.toggle(key.clone());
+                }
+                if let Some(index) = core.index_of(&key) {
+                    core.activate(index);
+                }
+            }
+        })
+        .style(move |s| {
+            s.items_center()
+                .width_full()
+                .height(row_height.get())
+                .padding_left(depth as f64 * indent.get())
+        });
+
+    let content = if core.on_drop.borrow().is_some() {
+        let dragged = key.clone();
+        let target = key.clone();
+        let drop_core = core.clone();
+        let drop_key = key.clone();
+        let core = core.clone();
+        content
+            .drag_data(move || TreeDrag(dragged.clone()))
+            .on_drag_over_data(move |drag: &TreeDrag<K>| {
+                if core.can_drop(&drag.0, &target) {
+                    DropEffect::Move
+                } else {
+                    DropEffect::None
+                }
+            })
+            .on_drop_data(move |drag: Rc<TreeDrag<K>>, pos| {
+                let height = row_height.get_untracked();
+                let position = if pos.y < height / 4.0 {
+                    TreeDropPosition::Before
+                } else if pos.y > height * 3.0 / 4.0 {
+                    TreeDropPosition::After
+                } else {
+                    TreeDropPosition::Inside
+                };
+                let target = drop_core.index_of(&drop_key).and_then(|i| drop_core.row(i));
+                if let (Some(target), Some(on_drop)) = (target, &*drop_core.on_drop.borrow()) {
+                    if drop_core.can_drop(&drag.0, &drop_key) {
+                        on_drop(drag.0.clone(), &target.item, position);
+                    }
+                }
+            })
+    } else {
+        content
+    };
+
+    highlight(content, move || {
+        state.selection.with(|selection| selection.contains(&key))
+    })
+}
+
+impl<T: Clone + 'static, K: Eq + Hash + Clone + 'static> TreeView<T, K> {
+    /// The expansion, selection and loaded children of the tree.
+    pub fn state(&self) -> TreeState<T, K> {
+        self.core.state.clone()
+    }
+
+    /// Tells which nodes can have children. Nodes for which `has_children` returns `false` are
+    /// shown as leaves without loading their children.
+    pub fn has_children(self, has_children: impl Fn(&T) -> bool + 'static) -> Self {
+        *self.core.has_children.borrow_mut() = Box::new(has_children);
+        // Rebuild the rows with the new function.
+        self.core.state.children_changed.notify();
+        self
+    }
+
+    /// Allows selecting several rows with Ctrl or Cmd and Shift clicks, and Shift with the arrow
+    /// keys.
+    pub fn multi_select(self, multi_select: bool) -> Self {
+        self.core.multi_select.set(multi_select);
+        self
+    }
+
+    /// Enables type-ahead search, matching the start of the text returned for each row.
+    pub fn search_text(self, search_text: impl Fn(&T) -> String + 'static) -> Self {
+        *self.core.search_text.borrow_mut() = Some(Box::new(search_text));
+        self
+    }
+
+    /// Calls `on_activate` with the node of a row that is double clicked or focused when Enter
+    /// is pressed.
+    pub fn on_activate(self, on_activate: impl Fn(&T) + 'static) -> Self {
+        *self.core.on_activate.borrow_mut() = Some(Box::new(on_activate));
+        self
+    }
+
+    /// Makes rows draggable and calls `on_drop` with the key of the dragged node, the node it's
+    /// dropped on and where it goes relative to that node. The tree itself doesn't change, the
+    /// handler moves the node in the data returned by `roots` and the children function.
+    ///
+    /// Dropping a node on itself or one of its descendants is refused.
+    pub fn on_drop(self, on_drop: impl Fn(K, &T, TreeDropPosition) + 'static) -> Self {
+        *self.core.on_drop.borrow_mut() = Some(Box::new(on_drop));
+        self
+    }
+
+    fn scroll_to_row(&self, index: usize) {
+        let height = self.core.row_height.get_untracked();
+        let width = self.child.get_size().unwrap_or_default().width;
+        let y = index as f64 * height;
+        self.child
+            .scroll_to(Some(Rect::new(0.0, y, width, y + height)));
+    }
+
+    fn key_down(&mut self, key: &Key, modifiers: Modifiers) -> bool {
+        let core = &self.core;
+        let len = core.rows.with_untracked(|rows| rows.len());
+        if len == 0 {
+            return false;
+        }
+        let cursor = core.cursor_index();
+        let extend = if modifiers.shift() {
+            Modifiers::SHIFT
+        } else {
+            Modifiers::empty()
+        };
+        let target = match key {
+            Key::Named(NamedKey::ArrowDown) => Some(cursor.map_or(0, |i| (i + 1).min(len - 1))),
+            Key::Named(NamedKey::ArrowUp) => Some(cursor.map_or(len - 1, |i| i.saturating_sub(1))),
+            Key::Named(NamedKey::Home) => Some(0),
+            Key::Named(NamedKey::End) => Some(len - 1),
+            Key::Named(NamedKey::ArrowRight) => {
+                let Some(i) = cursor else {
+                    return false;
+                };
+                let row = core.row(i).unwrap();
+                match row.kind {
+                    RowKind::Collapsed => {
+                        core.state.expand(row.key);
+                        None
+                    }
+                    RowKind::Expanded => core
+                        .row(i + 1)
+                        .filter(|child| child.parent == Some(i))
+                        .map(|_| i + 1),
+                    RowKind::Leaf | RowKind::Loading => None,
+                }
+            }
+            Key::Named(NamedKey::ArrowLeft) => {
+                let Some(i) = cursor else {
+                    return false;
+                };
+                let row = core.row(i).unwrap();
+                match row.kind {
+                    RowKind::Expanded | RowKind::Loading => {
+                        core.state.collapse(&row.key);
+                        None
+                    }
+                    RowKind::Leaf | RowKind::Collapsed => row.parent,
+                }
+            }
+            Key::Named(NamedKey::Enter) => {
+                if let Some(i) = cursor {
+                    core.activate(i);
+                }
+                None
+            }
+            // A space continues a search, so rows with several words can be found.
+            Key::Named(NamedKey::Space) if core.is_searching() => core.type_ahead(" "),
+            Key::Named(NamedKey::Space) if core.multi_select.get() => {
+                if let Some(i) = cursor {
+                    core.select(i, Modifiers::CONTROL);
+                }
+                None
+            }
+            Key::Character(c) if c.as_str() == "*" => {
+                if let Some(i) = cursor {
+                    core.expand_siblings(i);
+                }
+                None
+            }
+            Key::Character(c) if !(modifiers.control() || modifiers.meta() || modifiers.alt()) => {
+                if core.search_text.borrow().is_none() {
+                    return false;
+                }
+                core.type_ahead(c.as_str())
+            }
+            _ => return false,
+        };
+        if let Some(index) = target {
+            core.select(index, extend);
+            self.scroll_to_row(index);
+        }
+        true
+    }
+}
+
+impl<T: Clone + 'static, K: Eq + Hash + Clone + 'static> View for TreeView<T, K> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Tree View".into()
+    }
+
+    fn style_pass(&mut self, cx: &mut StyleCx<'_>) {
+        if self.style.read(cx) {
+            if self.indent.get_untracked() != self.style.indent() {
+                self.indent.set(self.style.indent());
+            }
+            if self.core.row_height.get_untracked() != self.style.row_height() {
+                self.core.row_height.set(self.style.row_height());
+            }
+            cx.app_state_mut().request_paint(self.id);
+        }
+        for child in self.id.children() {
+            cx.style_view(child);
+        }
+    }
+
+    fn event_before_children(&mut self, _cx: &mut EventCx, event: &Event) -> EventPropagation {
+        if let Event::KeyDown(e) = event {
+            if self.key_down(&e.key.logical_key, e.modifiers) {
+                return EventPropagation::Stop;
+            }
+        }
+        EventPropagation::Continue
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
+        self.viewport = cx.current_viewport();
+        default_compute_layout(self.id, cx)
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if let Some(color) = self.style.guide_color() {
+            let indent = self.style.indent();
+            let height = self.style.row_height();
+            let origin = self
+                .child
+                .get_layout()
+                .map(|layout| (layout.location.x as f64, layout.location.y as f64))
+                .unwrap_or_default();
+            self.core.rows.with_untracked(|rows| {
+                if height <= 0.0 || rows.is_empty() {
+                    return;
+                }
+                // Only the rows in the viewport get guides.
+                let first = ((self.viewport.y0 - origin.1) / height).floor().max(0.0) as usize;
+                let last = ((self.viewport.y1 - origin.1) / height).ceil().max(0.0) as usize;
+                for (i, row) in rows.iter().enumerate().take(last).skip(first) {
+                    let y = origin.1 + i as f64 * height;
+                    for level in 0..row.depth {
+                        let x = origin.0 + level as f64 * indent + indent / 2.0;
+                        cx.stroke(&Line::new((x, y), (x, y + height)), color, 1.0);
+                    }
+                }
+            });
+        }
+        cx.paint_children(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use floem_reactive::SignalGet;
+
+    use crate::{
+        keyboard::{Key, Modifiers, NamedKey},
+        views::text,
+    };
+
+    use super::{flatten, tree_view, RowKind, TreeChildren, TreeView};
+
+    /// A tree of numbers where the children of `n` are `n * 10 + 1` and `n * 10 + 2`, down to
+    /// three digits.
+    fn rows(expanded: &[u32], pending: &[u32]) -> Vec<(u32, usize, Option<usize>, RowKind)> {
+        let expanded: HashSet<u32> = expanded.iter().copied().collect();
+        flatten(&[1, 2], &|n| *n, &|n| *n < 100, &expanded, &mut |n, _| {
+            (!pending.contains(n)).then(|| vec![n * 10 + 1, n * 10 + 2])
+        })
+        .0
+        .into_iter()
+        .map(|row| (row.key, row.depth, row.parent, row.kind))
+        .collect()
+    }
+
+    #[test]
+    fn flattens_expanded_nodes() {
+        assert_eq!(
+            rows(&[], &[]),
+            vec![
+                (1, 0, None, RowKind::Collapsed),
+                (2, 0, None, RowKind::Collapsed)
+            ]
+        );
+        assert_eq!(
+            rows(&[1, 12], &[]),
+            vec![
+                (1, 0, None, RowKind::Expanded),
+                (11, 1, Some(0), RowKind::Collapsed),
+                (12, 1, Some(0), RowKind::Expanded),
+                (121, 2, Some(2), RowKind::Leaf),
+                (122, 2, Some(2), RowKind::Leaf),
+                (2, 0, None, RowKind::Collapsed),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsed_parent_hides_expanded_children() {
+        assert_eq!(
+            rows(&[12], &[]),
+            vec![
+                (1, 0, None, RowKind::Collapsed),
+                (2, 0, None, RowKind::Collapsed)
+            ]
+        );
+    }
+
+    #[test]
+    fn pending_children_are_loading() {
+        assert_eq!(
+            rows(&[2], &[2]),
+            vec![
+                (1, 0, None, RowKind::Collapsed),
+                (2, 0, None, RowKind::Loading)
+            ]
+        );
+    }
+
+    /// A tree view of the same tree, with the roots `1`, `2` and `3` named for type-ahead.
+    fn view() -> TreeView<u32, u32> {
+        tree_view(
+            || vec![1, 2, 3],
+            |n| *n,
+            |n| TreeChildren::Ready(vec![n * 10 + 1, n * 10 + 2]),
+            |n| text(*n),
+        )
+        .has_children(|n| *n < 100)
+        .search_text(|n| match n {
+            1 => "apple pie".to_string(),
+            2 => "apple tart".to_string(),
+            3 => "banana".to_string(),
+            n => n.to_string(),
+        })
+    }
+
+    fn press(view: &mut TreeView<u32, u32>, key: Key) {
+        assert!(view.key_down(&key, Modifiers::empty()));
+    }
+
+    #[test]
+    fn arrows_expand_and_collapse() {
+        let mut view = view();
+        let state = view.state();
+
+        press(&mut view, Key::Named(NamedKey::ArrowDown));
+        assert_eq!(state.cursor().get_untracked(), Some(1));
+        // Right expands a collapsed node, then moves to its first child.
+        press(&mut view, Key::Named(NamedKey::ArrowRight));
+        assert!(state.is_expanded(&1));
+        assert_eq!(state.cursor().get_untracked(), Some(1));
+        press(&mut view, Key::Named(NamedKey::ArrowRight));
+        assert_eq!(state.cursor().get_untracked(), Some(11));
+        // Left moves from a collapsed node to its parent, then collapses it.
+        press(&mut view, Key::Named(NamedKey::ArrowLeft));
+        assert_eq!(state.cursor().get_untracked(), Some(1));
+        press(&mut view, Key::Named(NamedKey::ArrowLeft));
+        assert!(!state.is_expanded(&1));
+        press(&mut view, Key::Named(NamedKey::ArrowDown));
+        assert_eq!(state.cursor().get_untracked(), Some(2));
+        view.id.remove();
+    }
+
+    #[test]
+    fn type_ahead_finds_rows() {
+        let mut view = view();
+        let state = view.state();
+        let mut type_text = |text: &str| {
+            for c in text.chars() {
+                let key = if c == ' ' {
+                    Key::Named(NamedKey::Space)
+                } else {
+                    Key::Character(c.to_string().into())
+                };
+                press(&mut view, key);
+            }
+        };
+
+        type_text("apple");
+        assert_eq!(state.cursor().get_untracked(), Some(1));
+        // A space continues the search.
+        type_text(" t");
+        assert_eq!(state.cursor().get_untracked(), Some(2));
+        view.id.remove();
+    }
+}
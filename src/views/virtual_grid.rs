@@ -0,0 +1,485 @@
+use std::{collections::HashMap, hash::Hash, marker::PhantomData};
+
+use floem_reactive::{
+    as_child_of_current_scope, create_effect, create_rw_signal, create_signal, RwSignal, Scope,
+    SignalGet, SignalUpdate, WriteSignal,
+};
+use peniko::kurbo::{Rect, Size};
+
+use crate::{
+    context::ComputeLayoutCx,
+    id::ViewId,
+    style::Style,
+    view::{self, AnyView, IntoView, View},
+    views::Decorators,
+};
+
+use super::{container, VirtualItemSize, VirtualVector};
+
+type GridViewFn<T> = Box<dyn Fn((T, Rect)) -> ((AnyView, RwSignal<Rect>), Scope)>;
+
+/// A trait that can be implemented on a two dimensional data source so that it can be used in
+/// a [`virtual_grid`].
+pub trait VirtualGridVector<T> {
+    fn rows(&self) -> usize;
+
+    fn columns(&self) -> usize;
+
+    /// The item at a row and column, or `None` if that cell is empty.
+    fn get(&mut self, row: usize, column: usize) -> Option<T>;
+}
+
+impl<T: Clone> VirtualGridVector<T> for im::Vector<im::Vector<T>> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn columns(&self) -> usize {
+        self.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    fn get(&mut self, row: usize, column: usize) -> Option<T> {
+        im::Vector::get(self, row)?.get(column).cloned()
+    }
+}
+
+impl<T: Clone> VirtualGridVector<T> for Vec<Vec<T>> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn columns(&self) -> usize {
+        self.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    fn get(&mut self, row: usize, column: usize) -> Option<T> {
+        self.as_slice().get(row)?.get(column).cloned()
+    }
+}
+
+struct GridChild {
+    id: ViewId,
+    scope: Scope,
+    rect: RwSignal<Rect>,
+}
+
+struct VirtualGridState<T> {
+    /// The visible items in order, with the slot identifying their view and their rect. The item
+    /// is only set for slots that don't have a view yet.
+    items: Vec<(u64, Option<T>, Rect)>,
+    content_size: Size,
+}
+
+/// The slots of the visible items by key, and the next free slot.
+struct GridSlots<K>(HashMap<K, u64>, u64);
+
+/// A View that lazily creates views for the items of a two dimensional data source that are
+/// visible in a [scroll view](super::scroll()), in both directions. See [`virtual_grid`] and
+/// [`virtual_grid_wrapped`].
+pub struct VirtualGrid<T>
+where
+    T: 'static,
+{
+    id: ViewId,
+    children: HashMap<u64, GridChild>,
+    viewport: Rect,
+    set_viewport: WriteSignal<Rect>,
+    view_fn: GridViewFn<T>,
+    content_size: Size,
+    /// Wrapped grids take the width they're given and only set their height.
+    wrapped: bool,
+    phantom: PhantomData<T>,
+}
+
+/// The items of a track of `len` rows or columns that intersect `min..max`, as their index,
+/// offset and size, and the size of the whole track.
+fn visible_track(
+    size: &VirtualItemSize<usize>,
+    len: usize,
+    min: f64,
+    max: f64,
+) -> (Vec<(usize, f64, f64)>, f64) {
+    match size {
+        VirtualItemSize::Fixed(size) | VirtualItemSize::Measured(size) => {
+            let size = size();
+            if size <= 0.0 {
+                return (Vec::new(), 0.0);
+            }
+            let start = ((min / size).floor().max(0.0) as usize).min(len);
+            let end = ((max / size).ceil().max(0.0) as usize).min(len);
+            let visible = (start..end).map(|i| (i, i as f64 * size, size)).collect();
+            (visible, size * len as f64)
+        }
+        VirtualItemSize::Fn(size_fn) => {
+            let mut visible = Vec::new();
+            let mut offset = 0.0;
+            for i in 0..len {
+                let size = size_fn(&i);
+                if offset + size > min && offset < max {
+                    visible.push((i, offset, size));
+                }
+                offset += size;
+            }
+            (visible, offset)
+        }
+    }
+}
+
+/// The number of columns of items `item_width` wide that fit in `width`, at least one.
+fn wrapped_columns(width: f64, item_width: f64) -> usize {
+    if item_width > 0.0 {
+        ((width / item_width).floor() as usize).max(1)
+    } else {
+        1
+    }
+}
+
+/// A View that lazily creates views for the cells of a two dimensional data source, such as a
+/// spreadsheet, as they appear in a [scroll view](super::scroll()).
+///
+/// Like [`virtual_stack`](super::virtual_stack()), it doesn't use the flexbox or grid layout
+/// algorithms: the rows are `row_height` high and the columns `column_width` wide, either all
+/// the same size with [`VirtualItemSize::Fixed`] or sized per row or column index with
+/// [`VirtualItemSize::Fn`]. Each cell view is sized to fill its cell, so a
+/// [`VirtualItemSize::Measured`] track uses its estimate as a fixed size.
+///
+/// ## Example
+/// ```
+/// use floem::{reactive::*, views::*};
+///
+/// let cells: Vec<Vec<(usize, usize)>> = (0..1000)
+///     .map(|row| (0..100).map(|column| (row, column)).collect())
+///     .collect();
+/// let (cells, _set_cells) = create_signal(cells);
+///
+/// scroll(virtual_grid(
+///     VirtualItemSize::Fixed(Box::new(|| 20.0)),
+///     VirtualItemSize::Fn(Box::new(|column| if *column == 0 { 40.0 } else { 80.0 })),
+///     move || cells.get(),
+///     |cell| *cell,
+///     |(row, column)| text(format!("{row}:{column}")),
+/// ))
+/// .style(|s| s.size_full());
+/// ```
+pub fn virtual_grid<T, IF, I, KF, K, VF, V>(
+    row_height: VirtualItemSize<usize>,
+    column_width: VirtualItemSize<usize>,
+    each_fn: IF,
+    key_fn: KF,
+    view_fn: VF,
+) -> VirtualGrid<T>
+where
+    T: 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualGridVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    virtual_grid_with(
+        false,
+        move |viewport| {
+            let mut items_vector = each_fn();
+            let (rows, total_height) =
+                visible_track(&row_height, items_vector.rows(), viewport.y0, viewport.y1);
+            let (columns, total_width) = visible_track(
+                &column_width,
+                items_vector.columns(),
+                viewport.x0,
+                viewport.x1,
+            );
+            let mut items = Vec::with_capacity(rows.len() * columns.len());
+            for &(row, y, height) in &rows {
+                for &(column, x, width) in &columns {
+                    if let Some(item) = items_vector.get(row, column) {
+                        items.push((item, Rect::new(x, y, x + width, y + height)));
+                    }
+                }
+            }
+            (items, Size::new(total_width, total_height))
+        },
+        key_fn,
+        view_fn,
+    )
+}
+
+/// A View that lazily creates views for a list of items laid out in rows, like a photo
+/// gallery, as they appear in a vertical [scroll view](super::scroll()).
+///
+/// Items are `item_width` wide and as many fit on a row as the width of the grid allows, so the
+/// number of columns follows the width of the scroll view. Their height is `item_height`,
+/// either the same for all items with [`VirtualItemSize::Fixed`] or per item with
+/// [`VirtualItemSize::Fn`], in which case each row is as high as its highest item. A
+/// [`VirtualItemSize::Measured`] height is treated as a fixed height of its estimate.
+///
+/// The grid should be given a width, such as with `width_full`, since its content wraps to it.
+///
+/// ## Example
+/// ```
+/// use floem::{reactive::*, views::*};
+///
+/// let photos: im::Vector<u32> = (0..100000).collect();
+/// let (photos, _set_photos) = create_signal(photos);
+///
+/// scroll(
+///     virtual_grid_wrapped(
+///         || 120.0,
+///         VirtualItemSize::Fixed(Box::new(|| 90.0)),
+///         move || photos.get(),
+///         |photo| *photo,
+///         |photo| text(photo),
+///     )
+///     .style(|s| s.width_full()),
+/// )
+/// .style(|s| s.size_full());
+/// ```
+pub fn virtual_grid_wrapped<T, WF, IF, I, KF, K, VF, V>(
+    item_width: WF,
+    item_height: VirtualItemSize<T>,
+    each_fn: IF,
+    key_fn: KF,
+    view_fn: VF,
+) -> VirtualGrid<T>
+where
+    T: 'static,
+    WF: Fn() -> f64 + 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    virtual_grid_with(
+        true,
+        move |viewport| {
+            let mut items_vector = each_fn();
+            let len = items_vector.total_len();
+            let item_width = item_width();
+            let columns = wrapped_columns(viewport.width(), item_width);
+            let rows = len.div_ceil(columns);
+            let width = columns as f64 * item_width;
+            let mut items = Vec::new();
+            let height = match &item_height {
+                VirtualItemSize::Fixed(item_height) | VirtualItemSize::Measured(item_height) => {
+                    let item_height = item_height();
+                    let (visible, height) = visible_track(
+                        &VirtualItemSize::Fixed(Box::new(move || item_height)),
+                        rows,
+                        viewport.y0,
+                        viewport.y1,
+                    );
+                    if let (Some(first), Some(last)) = (visible.first(), visible.last()) {
+                        let start = first.0 * columns;
+                        let end = ((last.0 + 1) * columns).min(len);
+                        for (i, item) in (start..end).zip(items_vector.slice(start..end)) {
+                            let x = (i % columns) as f64 * item_width;
+                            let y = (i / columns) as f64 * item_height;
+                            items.push((item, Rect::new(x, y, x + item_width, y + item_height)));
+                        }
+                    }
+                    height
+                }
+                VirtualItemSize::Fn(size_fn) => {
+                    // The height of every row depends on its items, so all of them are measured.
+                    let mut y = 0.0;
+                    let mut row: Vec<(T, f64)> = Vec::with_capacity(columns);
+                    let mut all = items_vector.slice(0..len).peekable();
+                    while all.peek().is_some() {
+                        row.clear();
+                        row.extend(all.by_ref().take(columns).map(|item| {
+                            let height = size_fn(&item);
+                            (item, height)
+                        }));
+                        let row_height = row.iter().map(|(_, h)| *h).fold(0.0, f64::max);
+                        if y + row_height > viewport.y0 && y < viewport.y1 {
+                            for (column, (item, height)) in row.drain(..).enumerate() {
+                                let x = column as f64 * item_width;
+                                items.push((item, Rect::new(x, y, x + item_width, y + height)));
+                            }
+                        }
+                        y += row_height;
+                    }
+                    y
+                }
+            };
+            (items, Size::new(width, height))
+        },
+        key_fn,
+        view_fn,
+    )
+}
+
+fn virtual_grid_with<T, LF, KF, K, VF, V>(
+    wrapped: bool,
+    layout_fn: LF,
+    key_fn: KF,
+    view_fn: VF,
+) -> VirtualGrid<T>
+where
+    T: 'static,
+    LF: Fn(Rect) -> (Vec<(T, Rect)>, Size) + 'static,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: IntoView + 'static,
+{
+    let id = ViewId::new();
+
+    let (viewport, set_viewport) = create_signal(Rect::ZERO);
+
+    create_effect(move |prev: Option<GridSlots<K>>| {
+        let (items, content_size) = layout_fn(viewport.get());
+        let GridSlots(mut prev_slots, mut next_slot) =
+            prev.unwrap_or_else(|| GridSlots(HashMap::new(), 0));
+        let mut slots = HashMap::with_capacity(items.len());
+        let items = items
+            .into_iter()
+            .map(|(item, rect)| {
+                let key = key_fn(&item);
+                match prev_slots.remove(&key) {
+                    Some(slot) => {
+                        slots.insert(key, slot);
+                        (slot, None, rect)
+                    }
+                    None => {
+                        let slot = next_slot;
+                        next_slot += 1;
+                        slots.insert(key, slot);
+                        (slot, Some(item), rect)
+                    }
+                }
+            })
+            .collect();
+        id.update_state(VirtualGridState {
+            items,
+            content_size,
+        });
+        GridSlots(slots, next_slot)
+    });
+
+    let view_fn = Box::new(as_child_of_current_scope(move |(item, rect): (T, Rect)| {
+        let rect = create_rw_signal(rect);
+        let view = container(view_fn(item)).style(move |s| {
+            let rect = rect.get();
+            s.absolute()
+                .inset_left(rect.x0)
+                .inset_top(rect.y0)
+                .width(rect.width())
+                .height(rect.height())
+        });
+        (view.into_any(), rect)
+    }));
+
+    VirtualGrid {
+        id,
+        children: HashMap::new(),
+        viewport: Rect::ZERO,
+        set_viewport,
+        view_fn,
+        content_size: Size::ZERO,
+        wrapped,
+        phantom: PhantomData,
+    }
+}
+
+impl<T> View for VirtualGrid<T> {
+    fn id(&self) -> ViewId {
+        self.id
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "VirtualGrid".into()
+    }
+
+    fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
+        if let Ok(state) = state.downcast::<VirtualGridState<T>>() {
+            let mut children = HashMap::with_capacity(state.items.len());
+            let mut children_ids = Vec::with_capacity(state.items.len());
+            for (slot, item, rect) in state.items {
+                let child = match (self.children.remove(&slot), item) {
+                    (Some(child), _) => {
+                        if child.rect.get_untracked() != rect {
+                            child.rect.set(rect);
+                        }
+                        child
+                    }
+                    (None, Some(item)) => {
+                        let ((view, rect), scope) = (self.view_fn)((item, rect));
+                        let id = view.id();
+                        id.set_view(view);
+                        id.set_parent(self.id);
+                        GridChild { id, scope, rect }
+                    }
+                    (None, None) => continue,
+                };
+                children_ids.push(child.id);
+                children.insert(slot, child);
+            }
+            for (_, child) in self.children.drain() {
+                cx.app_state.remove_view(child.id);
+                child.scope.dispose();
+            }
+            self.children = children;
+            self.id.set_children_ids(children_ids);
+            self.content_size = state.content_size;
+            self.id.request_all();
+        }
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        let style = Style::new().height(self.content_size.height);
+        Some(if self.wrapped {
+            style
+        } else {
+            style.width(self.content_size.width)
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx<'_>) -> Option<Rect> {
+        let viewport = cx.current_viewport();
+        if self.viewport != viewport {
+            self.viewport = viewport;
+            self.set_viewport.set(viewport);
+        }
+
+        view::default_compute_layout(self.id, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{visible_track, wrapped_columns};
+    use crate::views::VirtualItemSize;
+
+    #[test]
+    fn fixed_track() {
+        let size = VirtualItemSize::Fixed(Box::new(|| 10.0));
+        let (visible, total) = visible_track(&size, 100, 25.0, 45.0);
+        assert_eq!(total, 1000.0);
+        assert_eq!(
+            visible,
+            vec![(2, 20.0, 10.0), (3, 30.0, 10.0), (4, 40.0, 10.0)]
+        );
+        // The end is clamped to the length.
+        let (visible, _) = visible_track(&size, 3, 0.0, 100.0);
+        assert_eq!(visible.len(), 3);
+    }
+
+    #[test]
+    fn sized_track() {
+        let size = VirtualItemSize::Fn(Box::new(|i: &usize| (*i + 1) as f64 * 10.0));
+        // Offsets are 0, 10, 30, 60, 100.
+        let (visible, total) = visible_track(&size, 4, 15.0, 60.0);
+        assert_eq!(total, 100.0);
+        assert_eq!(visible, vec![(1, 10.0, 20.0), (2, 30.0, 30.0)]);
+    }
+
+    #[test]
+    fn wrapped_column_count() {
+        assert_eq!(wrapped_columns(500.0, 120.0), 4);
+        assert_eq!(wrapped_columns(100.0, 120.0), 1);
+        assert_eq!(wrapped_columns(500.0, 0.0), 1);
+    }
+}
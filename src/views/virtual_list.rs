@@ -1,8 +1,9 @@
 use super::{
-    container, virtual_stack, Decorators, Item, ListClass, ListItemClass, VirtualDirection,
-    VirtualItemSize, VirtualVector,
+    container, virtual_stack,
+    virtual_stack::{EdgeHooks, VirtualStackScroll},
+    Decorators, Item, ListClass, ListItemClass, ScrollAlign, VirtualDirection, VirtualItemSize,
+    VirtualVector,
 };
-use crate::context::ComputeLayoutCx;
 use crate::event::EventPropagation;
 use crate::id::ViewId;
 use crate::reactive::create_effect;
@@ -12,10 +13,8 @@ use crate::{
     keyboard::{Key, NamedKey},
     view::View,
 };
-use floem_reactive::{
-    create_rw_signal, RwSignal, SignalGet, SignalTrack, SignalUpdate, SignalWith,
-};
-use peniko::kurbo::{Rect, Size};
+use floem_reactive::{create_rw_signal, RwSignal, SignalGet, SignalTrack, SignalUpdate};
+use std::cell::RefCell;
 use std::hash::Hash;
 use std::rc::Rc;
 
@@ -29,10 +28,9 @@ enum ListUpdate {
 pub struct VirtualList {
     id: ViewId,
     direction: VirtualDirection,
-    child_size: Size,
     selection: RwSignal<Option<usize>>,
-    offsets: RwSignal<Vec<f64>>,
     child: ViewId,
+    edges: Rc<RefCell<EdgeHooks>>,
 }
 
 impl VirtualList {
@@ -47,6 +45,33 @@ impl VirtualList {
         });
         self
     }
+
+    /// Calls `cb` when the end of the viewport gets within `threshold` of the end of the list.
+    /// See [`VirtualStack::on_near_end`](super::VirtualStack::on_near_end).
+    pub fn on_near_end(self, threshold: f64, cb: impl Fn() + 'static) -> Self {
+        self.edges.borrow_mut().set_near_end(threshold, cb);
+        self
+    }
+
+    /// Calls `cb` when the start of the viewport gets within `threshold` of the start of the
+    /// list, keeping the scroll position when items are added before the visible ones.
+    /// See [`VirtualStack::on_near_start`](super::VirtualStack::on_near_start).
+    pub fn on_near_start(self, threshold: f64, cb: impl Fn() + 'static) -> Self {
+        self.edges.borrow_mut().set_near_start(threshold, cb);
+        self
+    }
+
+    /// Shows `footer` after the last item while `loading` returns true.
+    pub fn loading_footer(
+        self,
+        loading: impl Fn() -> bool + 'static,
+        footer: impl IntoView + 'static,
+    ) -> Self {
+        self.edges
+            .borrow_mut()
+            .set_footer(self.child, self.direction, loading, footer);
+        self
+    }
 }
 
 /// A view that is like a [`virtual_stack`](super::virtual_stack()) but also supports item selection.
@@ -72,52 +97,28 @@ where
     let id = ViewId::new();
     let selection = create_rw_signal(None);
     let length = create_rw_signal(0);
-    let offsets = create_rw_signal(Vec::new());
     create_effect(move |_| {
         selection.track();
         id.update_state(ListUpdate::SelectionChanged);
     });
 
     let shared = Rc::new((each_fn, item_size));
-    let shared_ = shared.clone();
-
-    create_effect(move |_| {
-        let mut items = (shared_.0)();
-
-        let mut new_offsets = Vec::with_capacity(items.total_len());
-        let mut current = 0.0;
-
-        match &shared_.1 {
-            VirtualItemSize::Fixed(item_size) => {
-                let item_size = item_size();
-                for _ in 0..items.total_len() {
-                    new_offsets.push(current);
-                    current += item_size;
-                }
-            }
-            VirtualItemSize::Fn(size_fn) => {
-                for item in items.slice(0..(items.total_len())) {
-                    new_offsets.push(current);
-                    current += size_fn(&item);
-                }
-            }
-        };
-
-        new_offsets.push(current);
-
-        offsets.set(new_offsets);
-    });
-
     let shared_ = shared.clone();
     let item_size = match shared.1 {
         VirtualItemSize::Fixed(..) => VirtualItemSize::Fixed(Box::new(move || match shared_.1 {
             VirtualItemSize::Fixed(ref f) => f(),
-            VirtualItemSize::Fn(..) => panic!(),
+            VirtualItemSize::Fn(..) | VirtualItemSize::Measured(..) => panic!(),
         })),
         VirtualItemSize::Fn(..) => VirtualItemSize::Fn(Box::new(move |(_, e)| match shared_.1 {
-            VirtualItemSize::Fixed(..) => panic!(),
             VirtualItemSize::Fn(ref f) => f(e),
+            VirtualItemSize::Fixed(..) | VirtualItemSize::Measured(..) => panic!(),
         })),
+        VirtualItemSize::Measured(..) => {
+            VirtualItemSize::Measured(Box::new(move || match shared_.1 {
+                VirtualItemSize::Measured(ref f) => f(),
+                VirtualItemSize::Fixed(..) | VirtualItemSize::Fn(..) => panic!(),
+            }))
+        }
     };
     let stack = virtual_stack(
         direction,
@@ -158,14 +159,14 @@ where
         VirtualDirection::Vertical => s.flex_col(),
     });
     let child = stack.id();
+    let edges = stack.edge_hooks();
     id.set_children(vec![stack.into_view()]);
     VirtualList {
         id,
         selection,
         direction,
-        offsets,
-        child_size: Size::ZERO,
         child,
+        edges,
     }
     .class(ListClass)
     .keyboard_navigatable()
@@ -244,33 +245,15 @@ impl View for VirtualList {
                     self.id.request_style_recursive();
                 }
                 ListUpdate::ScrollToSelected => {
+                    // The stack knows the measured sizes of the items
                     if let Some(index) = self.selection.get_untracked() {
-                        self.offsets.with_untracked(|offsets| {
-                            if let Some([before, after]) = offsets.get(index..index + 2) {
-                                let rect = match self.direction {
-                                    VirtualDirection::Vertical => {
-                                        Rect::new(0.0, *before, self.child_size.width, *after)
-                                    }
-                                    VirtualDirection::Horizontal => {
-                                        Rect::new(*before, 0.0, *after, self.child_size.height)
-                                    }
-                                };
-                                self.child.scroll_to(Some(rect));
-                            }
+                        self.child.update_state(VirtualStackScroll {
+                            index,
+                            align: ScrollAlign::Nearest,
                         });
                     }
                 }
             }
         }
     }
-
-    fn compute_layout(&mut self, cx: &mut ComputeLayoutCx) -> Option<Rect> {
-        self.child_size = self
-            .child
-            .get_layout()
-            .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
-            .unwrap();
-
-        cx.compute_view_layout(self.child)
-    }
 }
@@ -1,21 +1,27 @@
-use std::{hash::Hash, marker::PhantomData, ops::Range};
+use std::{
+    cell::RefCell, collections::HashMap, hash::Hash, marker::PhantomData, ops::Range, rc::Rc,
+};
 
 use floem_reactive::{
-    as_child_of_current_scope, create_effect, create_signal, Scope, SignalGet, SignalUpdate,
-    WriteSignal,
+    as_child_of_current_scope, create_effect, create_signal, create_trigger, Scope, SignalGet,
+    SignalUpdate, Trigger, WriteSignal,
 };
-use peniko::kurbo::Rect;
+use peniko::kurbo::{Rect, Vec2};
 use smallvec::SmallVec;
 use taffy::{style::Dimension, tree::NodeId};
 
 use crate::{
     context::ComputeLayoutCx,
     id::ViewId,
-    style::Style,
+    style::{Style, StyleClass as _},
     view::{self, IntoView, View},
 };
 
-use super::{apply_diff, diff, Diff, DiffOpAdd, FxIndexSet, HashRun};
+use super::{
+    apply_diff, container, diff,
+    scroll::{ScrollClass, ScrollState},
+    Decorators, Diff, DiffOpAdd, FxIndexSet, HashRun,
+};
 
 type ViewFn<T> = Box<dyn Fn(T) -> (Box<dyn View>, Scope)>;
 
@@ -28,6 +34,27 @@ pub enum VirtualDirection {
 pub enum VirtualItemSize<T> {
     Fn(Box<dyn Fn(&T) -> f64>),
     Fixed(Box<dyn Fn() -> f64>),
+    /// Measures the items once they're laid out, for items whose size isn't known up front,
+    /// such as wrapped text. The function returns the estimated size of the items that haven't
+    /// been shown yet.
+    ///
+    /// The measured sizes are cached by key, and when items above the viewport turn out larger
+    /// or smaller than estimated, the enclosing scroll view is adjusted so the visible items
+    /// don't move.
+    Measured(Box<dyn Fn() -> f64>),
+}
+
+/// Where [`VirtualStack::scroll_to_index`] puts the item in the viewport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollAlign {
+    /// At the start of the viewport.
+    Start,
+    /// In the middle of the viewport.
+    Center,
+    /// At the end of the viewport.
+    End,
+    /// Scroll as little as possible to make the item visible.
+    Nearest,
 }
 
 /// A trait that can be implemented on a type so that the type can be used in a [`virtual_stack`] or [`virtual_list`](super::virtual_list()).
@@ -66,12 +93,171 @@ where
     before_size: f64,
     content_size: f64,
     before_node: Option<NodeId>,
+    /// The offset and size along the direction of the item at an index.
+    item_span: Box<dyn Fn(usize) -> Option<(f64, f64)>>,
+    /// Records the size of a child in [`VirtualItemSize::Measured`] mode, returning the
+    /// previous size if it changed.
+    measure: Option<Box<dyn Fn(ViewId, f64) -> Option<f64>>>,
+    measured: Trigger,
+    edges: Rc<RefCell<EdgeHooks>>,
+    /// The scroll adjustment for items inserted or removed before the visible ones, applied
+    /// after the next layout.
+    pending_shift: f64,
 }
 
 struct VirtualStackState<T> {
     diff: Diff<T>,
     before_size: f64,
     content_size: f64,
+    shift: f64,
+}
+
+/// The callbacks for when the viewport gets near either end of a virtual stack, and the
+/// loading footer.
+#[derive(Default)]
+pub(crate) struct EdgeHooks {
+    near_start: Option<(f64, Box<dyn Fn()>)>,
+    near_end: Option<(f64, Box<dyn Fn()>)>,
+    /// The content size when the callbacks were last called, so they're called once for each
+    /// page of items rather than on every layout.
+    near_start_at: Option<f64>,
+    near_end_at: Option<f64>,
+    footer: Option<ViewId>,
+}
+
+impl EdgeHooks {
+    pub(crate) fn set_near_start(&mut self, threshold: f64, cb: impl Fn() + 'static) {
+        self.near_start = Some((threshold, Box::new(cb)));
+        self.near_start_at = None;
+    }
+
+    pub(crate) fn set_near_end(&mut self, threshold: f64, cb: impl Fn() + 'static) {
+        self.near_end = Some((threshold, Box::new(cb)));
+        self.near_end_at = None;
+    }
+
+    /// Adds `footer` to the end of the stack `id`, shown while `loading` returns true.
+    pub(crate) fn set_footer(
+        &mut self,
+        id: ViewId,
+        direction: VirtualDirection,
+        loading: impl Fn() -> bool + 'static,
+        footer: impl IntoView + 'static,
+    ) {
+        if let Some(footer) = self.footer.take() {
+            footer.remove();
+        }
+        let footer = container(footer).style(move |s| {
+            let s = match direction {
+                VirtualDirection::Vertical => s.width_full(),
+                VirtualDirection::Horizontal => s.height_full(),
+            };
+            s.flex_shrink(0.0).apply_if(!loading(), |s| s.hide())
+        });
+        self.footer = Some(footer.id());
+        id.add_child(footer.into_any());
+        id.request_all();
+    }
+
+    /// Which of the callbacks are due, with the viewport at `start..end`. They're called
+    /// separately as they may update the items, which reads the hooks.
+    fn due(&mut self, start: f64, end: f64, content_size: f64) -> (bool, bool) {
+        fn due(near: bool, at: &mut Option<f64>, content_size: f64) -> bool {
+            if !near {
+                *at = None;
+                false
+            } else if *at != Some(content_size) {
+                *at = Some(content_size);
+                true
+            } else {
+                false
+            }
+        }
+        let near_start = match &self.near_start {
+            Some((threshold, _)) => due(start <= *threshold, &mut self.near_start_at, content_size),
+            None => false,
+        };
+        let near_end = match &self.near_end {
+            Some((threshold, _)) => due(
+                end + *threshold >= content_size,
+                &mut self.near_end_at,
+                content_size,
+            ),
+            None => false,
+        };
+        (near_start, near_end)
+    }
+}
+
+/// Sent to a virtual stack to scroll the item at `index` into view.
+pub(crate) struct VirtualStackScroll {
+    pub(crate) index: usize,
+    pub(crate) align: ScrollAlign,
+}
+
+/// The measured sizes of the items by key, and the keys of the item views.
+struct Measurements<K> {
+    sizes: HashMap<Rc<K>, f64>,
+    views: HashMap<ViewId, Rc<K>>,
+}
+
+/// The size of an item, as given by `item_size` or measured.
+fn item_size_of<T, K: Eq + Hash>(
+    item_size: &VirtualItemSize<T>,
+    item: &T,
+    key_fn: &impl Fn(&T) -> K,
+    measurements: &Measurements<K>,
+) -> f64 {
+    match item_size {
+        VirtualItemSize::Fixed(size) => size(),
+        VirtualItemSize::Fn(size_fn) => size_fn(item),
+        VirtualItemSize::Measured(estimate) => measurements
+            .sizes
+            .get(&key_fn(item))
+            .copied()
+            .unwrap_or_else(estimate),
+    }
+}
+
+/// The size before the visible items, the content size, the number of items, the index of the
+/// first visible item and the keys of the visible items.
+type VisibleItems<K> = (f64, f64, usize, usize, HashRun<FxIndexSet<K>>);
+
+/// The offset and size along the direction of the item at `index`.
+fn item_span<T, K: Eq + Hash>(
+    mut items: impl VirtualVector<T>,
+    index: usize,
+    item_size: &VirtualItemSize<T>,
+    key_fn: &impl Fn(&T) -> K,
+    measurements: &Measurements<K>,
+) -> Option<(f64, f64)> {
+    if index >= items.total_len() {
+        return None;
+    }
+    if let VirtualItemSize::Fixed(item_size) = item_size {
+        let item_size = item_size();
+        return Some((item_size * index as f64, item_size));
+    }
+    let mut offset = 0.0;
+    let mut size = 0.0;
+    for item in items.slice(0..index + 1) {
+        offset += size;
+        size = item_size_of(item_size, &item, key_fn, measurements);
+    }
+    Some((offset, size))
+}
+
+/// Where the viewport at `start` with length `len` has to start to show the item at `offset`
+/// with `size` at the `align` position.
+fn scroll_target(offset: f64, size: f64, start: f64, len: f64, align: ScrollAlign) -> f64 {
+    match align {
+        ScrollAlign::Start => offset,
+        ScrollAlign::Center => offset - (len - size) / 2.0,
+        ScrollAlign::End => offset + size - len,
+        ScrollAlign::Nearest if offset < start => offset,
+        ScrollAlign::Nearest if offset + size > start + len => offset + size - len,
+        ScrollAlign::Nearest => start,
+    }
 }
 
 /// A View that is like a [`dyn_stack`](super::dyn_stack()) but also lazily loads the items as they appear in a [scroll view](super::scroll()) and does not support the flexbox nor grid layout algorithms.
@@ -124,9 +310,20 @@ where
     let id = ViewId::new();
 
     let (viewport, set_viewport) = create_signal(Rect::ZERO);
-
-    create_effect(move |prev| {
+    let measured = create_trigger();
+    let measurements = Rc::new(RefCell::new(Measurements {
+        sizes: HashMap::new(),
+        views: HashMap::new(),
+    }));
+    let shared = Rc::new((each_fn, item_size, key_fn));
+    let edges = Rc::new(RefCell::new(EdgeHooks::default()));
+
+    let shared_ = shared.clone();
+    let measurements_ = measurements.clone();
+    create_effect(move |prev: Option<VisibleItems<K>>| {
+        let (each_fn, item_size, key_fn) = &*shared_;
         let mut items_vector = each_fn();
+        let total_len = items_vector.total_len();
         let viewport = viewport.get();
         let min = match direction {
             VirtualDirection::Vertical => viewport.y0,
@@ -138,12 +335,30 @@ where
         };
         let mut items = Vec::new();
 
+        // When items are inserted or removed before the first visible item, as when loading
+        // older items at the start, the view scrolls by as much as that item moved so it stays
+        // in place. The item is looked up by its key where it is if the length changed at the
+        // start, or else where it was before.
+        let anchor =
+            prev.as_ref()
+                .and_then(|(_, _, prev_len, prev_first, HashRun(prev_hash_run))| {
+                    let key = prev_hash_run.first().filter(|_| *prev_len != total_len)?;
+                    let moved = (*prev_first + total_len).checked_sub(*prev_len);
+                    Some((key, [moved, Some(*prev_first)]))
+                });
+        let is_anchor = |index: usize, item: &T| {
+            anchor.as_ref().is_some_and(|(key, indices)| {
+                indices.contains(&Some(index)) && key_fn(item) == **key
+            })
+        };
+        let mut anchor_offset = None;
+
         let mut before_size = 0.0;
         let mut content_size = 0.0;
-        match &item_size {
+        let mut first_index = total_len;
+        match item_size {
             VirtualItemSize::Fixed(item_size) => {
                 let item_size = item_size();
-                let total_len = items_vector.total_len();
                 let start = if item_size > 0.0 {
                     (min / item_size).floor() as usize
                 } else {
@@ -155,36 +370,57 @@ where
                     usize::MAX
                 };
                 before_size = item_size * (start.min(total_len)) as f64;
+                first_index = start.min(total_len);
 
                 for item in items_vector.slice(start..end) {
                     items.push(item);
                 }
 
                 content_size = item_size * total_len as f64;
+
+                if let Some((_, indices)) = &anchor {
+                    anchor_offset = indices.iter().flatten().find_map(|&index| {
+                        let item = items_vector.slice(index..index + 1).next()?;
+                        is_anchor(index, &item).then_some(item_size * index as f64)
+                    });
+                }
             }
-            VirtualItemSize::Fn(size_fn) => {
+            VirtualItemSize::Fn(_) | VirtualItemSize::Measured(_) => {
+                if let VirtualItemSize::Measured(_) = item_size {
+                    measured.track();
+                }
+                let measurements = measurements_.borrow();
                 let mut main_axis = 0.0;
-                let total_len = items_vector.total_len();
-                for item in items_vector.slice(0..total_len) {
-                    let item_size = size_fn(&item);
-                    content_size += item_size;
-                    if main_axis + item_size < min {
-                        main_axis += item_size;
-                        before_size += item_size;
+                for (index, item) in items_vector.slice(0..total_len).enumerate() {
+                    let size = item_size_of(item_size, &item, key_fn, &measurements);
+                    if anchor_offset.is_none() && is_anchor(index, &item) {
+                        anchor_offset = Some(content_size);
+                    }
+                    content_size += size;
+                    if main_axis + size < min {
+                        main_axis += size;
+                        before_size += size;
                         continue;
                     }
 
                     if main_axis <= max {
-                        main_axis += item_size;
+                        main_axis += size;
+                        first_index = first_index.min(index);
                         items.push(item);
                     }
                 }
             }
         };
 
-        let hashed_items = items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
+        let shift = match (&prev, anchor_offset) {
+            (Some((prev_before_size, ..)), Some(offset)) => offset - prev_before_size,
+            _ => 0.0,
+        };
+
+        let hashed_items = items.iter().map(key_fn).collect::<FxIndexSet<_>>();
         let (prev_before_size, prev_content_size, diff) =
-            if let Some((prev_before_size, prev_content_size, HashRun(prev_hash_run))) = prev {
+            if let Some((prev_before_size, prev_content_size, _, _, HashRun(prev_hash_run))) = prev
+            {
                 let mut diff = diff(&prev_hash_run, &hashed_items);
                 let mut items = items
                     .into_iter()
@@ -205,18 +441,66 @@ where
                 (0.0, 0.0, diff)
             };
 
-        if !diff.is_empty() || prev_before_size != before_size || prev_content_size != content_size
+        if !diff.is_empty()
+            || prev_before_size != before_size
+            || prev_content_size != content_size
+            || shift != 0.0
         {
             id.update_state(VirtualStackState {
                 diff,
                 before_size,
                 content_size,
+                shift,
             });
         }
-        (before_size, content_size, HashRun(hashed_items))
+        (
+            before_size,
+            content_size,
+            total_len,
+            first_index,
+            HashRun(hashed_items),
+        )
     });
 
-    let view_fn = Box::new(as_child_of_current_scope(move |e| view_fn(e).into_any()));
+    let item_span = {
+        let shared = shared.clone();
+        let measurements = measurements.clone();
+        Box::new(move |index: usize| {
+            let (each_fn, item_size, key_fn) = &*shared;
+            item_span(each_fn(), index, item_size, key_fn, &measurements.borrow())
+        })
+    };
+
+    let is_measured = matches!(shared.1, VirtualItemSize::Measured(_));
+    let measure = is_measured.then(|| {
+        let shared = shared.clone();
+        let measurements = measurements.clone();
+        Box::new(move |id: ViewId, size: f64| {
+            let (_, item_size, _) = &*shared;
+            let VirtualItemSize::Measured(estimate) = item_size else {
+                return None;
+            };
+            let mut measurements = measurements.borrow_mut();
+            let key = measurements.views.get(&id)?.clone();
+            let old = measurements
+                .sizes
+                .insert(key, size)
+                .unwrap_or_else(estimate);
+            ((old - size).abs() > 0.5).then_some(old)
+        }) as Box<dyn Fn(ViewId, f64) -> Option<f64>>
+    });
+
+    let view_fn = Box::new(as_child_of_current_scope(move |item: T| {
+        let key = is_measured.then(|| (shared.2)(&item));
+        let view = view_fn(item).into_any();
+        if let Some(key) = key {
+            let views = &mut measurements.borrow_mut().views;
+            // Forget the views that have been removed.
+            views.retain(|id, _| id.parent().is_some());
+            views.insert(view.id(), Rc::new(key));
+        }
+        view
+    }));
 
     VirtualStack {
         id,
@@ -229,6 +513,101 @@ where
         before_size: 0.0,
         content_size: 0.0,
         before_node: None,
+        item_span,
+        measure,
+        measured,
+        edges,
+        pending_shift: 0.0,
+    }
+}
+
+impl<T> VirtualStack<T> {
+    /// Scrolls the enclosing [scroll view](super::scroll()) so the item at the index returned
+    /// by `index` is at the `align` position of the viewport, whenever it returns `Some`.
+    ///
+    /// In [`VirtualItemSize::Measured`] mode, the position of items that haven't been shown yet
+    /// is based on the estimated size.
+    pub fn scroll_to_index(
+        self,
+        index: impl Fn() -> Option<usize> + 'static,
+        align: ScrollAlign,
+    ) -> Self {
+        let id = self.id;
+        create_effect(move |_| {
+            if let Some(index) = index() {
+                id.update_state_deferred(VirtualStackScroll { index, align });
+            }
+        });
+        self
+    }
+
+    /// Calls `cb` when the end of the viewport gets within `threshold` of the end of the stack,
+    /// such as to load the next page of items. It's called once for each time the end is
+    /// reached, and again if the viewport is still near the end once more items are added.
+    pub fn on_near_end(self, threshold: f64, cb: impl Fn() + 'static) -> Self {
+        self.edges.borrow_mut().set_near_end(threshold, cb);
+        self
+    }
+
+    /// Calls `cb` when the start of the viewport gets within `threshold` of the start of the
+    /// stack, such as to load older messages in a chat history. The visible items stay in place
+    /// when the older items are added before them.
+    pub fn on_near_start(self, threshold: f64, cb: impl Fn() + 'static) -> Self {
+        self.edges.borrow_mut().set_near_start(threshold, cb);
+        self
+    }
+
+    /// Shows `footer` after the last item while `loading` returns true, such as a spinner while
+    /// the next page of items is being loaded.
+    pub fn loading_footer(
+        self,
+        loading: impl Fn() -> bool + 'static,
+        footer: impl IntoView + 'static,
+    ) -> Self {
+        self.edges
+            .borrow_mut()
+            .set_footer(self.id, self.direction, loading, footer);
+        self
+    }
+
+    pub(crate) fn edge_hooks(&self) -> Rc<RefCell<EdgeHooks>> {
+        self.edges.clone()
+    }
+
+    /// The start and length of the viewport along the direction of the stack.
+    fn viewport_span(&self) -> (f64, f64) {
+        match self.direction {
+            VirtualDirection::Vertical => (self.viewport.y0, self.viewport.height()),
+            VirtualDirection::Horizontal => (self.viewport.x0, self.viewport.width()),
+        }
+    }
+
+    fn scroll_to_item(&self, index: usize, align: ScrollAlign) {
+        let Some((offset, size)) = (self.item_span)(index) else {
+            return;
+        };
+        let (start, len) = self.viewport_span();
+        let target = scroll_target(offset, size, start, len, align);
+        if target != start {
+            self.scroll_by(target - start);
+        }
+    }
+
+    /// Scrolls the nearest enclosing scroll view by `delta` along the direction of the stack.
+    fn scroll_by(&self, delta: f64) {
+        let Some(scroll) = self
+            .id
+            .ancestors()
+            .into_iter()
+            .find(|id| id.has_class(ScrollClass::class_ref()))
+        else {
+            return;
+        };
+        let delta = match self.direction {
+            VirtualDirection::Vertical => Vec2::new(0.0, delta),
+            VirtualDirection::Horizontal => Vec2::new(delta, 0.0),
+        };
+        scroll.update_state(ScrollState::ScrollDelta(delta));
     }
 }
 
@@ -242,29 +621,49 @@ impl<T> View for VirtualStack<T> {
     }
 
     fn update(&mut self, cx: &mut crate::context::UpdateCx, state: Box<dyn std::any::Any>) {
-        if let Ok(state) = state.downcast::<VirtualStackState<T>>() {
-            if self.before_size == state.before_size
-                && self.content_size == state.content_size
-                && state.diff.is_empty()
-            {
+        let state = match state.downcast::<VirtualStackState<T>>() {
+            Ok(state) => state,
+            Err(state) => {
+                if let Ok(scroll) = state.downcast::<VirtualStackScroll>() {
+                    self.scroll_to_item(scroll.index, scroll.align);
+                }
                 return;
             }
-            self.before_size = state.before_size;
-            self.content_size = state.content_size;
-            apply_diff(
-                self.id(),
-                cx.app_state,
-                state.diff,
-                &mut self.children,
-                &self.view_fn,
-            );
-            self.id.request_all();
+        };
+        self.pending_shift += state.shift;
+        if self.before_size == state.before_size
+            && self.content_size == state.content_size
+            && state.diff.is_empty()
+        {
+            if state.shift != 0.0 {
+                self.id.request_layout();
+            }
+            return;
         }
+        self.before_size = state.before_size;
+        self.content_size = state.content_size;
+        apply_diff(
+            self.id(),
+            cx.app_state,
+            state.diff,
+            &mut self.children,
+            &self.view_fn,
+        );
+        if let Some(footer) = self.edges.borrow().footer {
+            let mut children = self.id.children();
+            children.push(footer);
+            self.id.set_children_ids(children);
+        }
+        self.id.request_all();
     }
 
     fn view_style(&self) -> Option<crate::style::Style> {
+        // The footer is laid out after the items, so the stack may need to grow to fit it.
+        let grow = self.edges.borrow().footer.is_some();
         let style = match self.direction {
+            VirtualDirection::Vertical if grow => Style::new().min_height(self.content_size),
             VirtualDirection::Vertical => Style::new().height(self.content_size),
+            VirtualDirection::Horizontal if grow => Style::new().min_width(self.content_size),
             VirtualDirection::Horizontal => Style::new().width(self.content_size),
         };
         Some(style)
@@ -318,7 +717,56 @@ impl<T> View for VirtualStack<T> {
             self.set_viewport.set(viewport);
         }
 
-        view::default_compute_layout(self.id, cx)
+        let layout = view::default_compute_layout(self.id, cx);
+
+        let (start, len) = self.viewport_span();
+        let mut shift = std::mem::take(&mut self.pending_shift);
+        if let Some(measure) = &self.measure {
+            let mut changed = false;
+            for child in self.id.children() {
+                let Some(child_layout) = child.get_layout() else {
+                    continue;
+                };
+                let (offset, size) = match self.direction {
+                    VirtualDirection::Vertical => {
+                        (child_layout.location.y, child_layout.size.height)
+                    }
+                    VirtualDirection::Horizontal => {
+                        (child_layout.location.x, child_layout.size.width)
+                    }
+                };
+                if let Some(old) = measure(child, size as f64) {
+                    changed = true;
+                    // Items starting above the viewport push the visible ones along as they
+                    // grow.
+                    if (offset as f64) < start {
+                        shift += size as f64 - old;
+                    }
+                }
+            }
+            if changed {
+                self.measured.notify();
+            }
+        }
+
+        if shift != 0.0 {
+            // The viewport is about to move, so the edges are checked once it has.
+            self.scroll_by(shift);
+        } else {
+            let (near_start, near_end) =
+                self.edges
+                    .borrow_mut()
+                    .due(start, start + len, self.content_size);
+            let edges = self.edges.borrow();
+            if let Some((_, cb)) = edges.near_start.as_ref().filter(|_| near_start) {
+                cb();
+            }
+            if let Some((_, cb)) = edges.near_end.as_ref().filter(|_| near_end) {
+                cb();
+            }
+        }
+
+        layout
     }
 }
 
@@ -350,3 +798,81 @@ impl<V: VirtualVector<T>, T> VirtualVector<(usize, T)> for Enumerate<V, T> {
             .map(move |(i, e)| (i + start, e))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, rc::Rc};
+
+    use super::{item_span, scroll_target, EdgeHooks, Measurements, ScrollAlign, VirtualItemSize};
+
+    #[test]
+    fn near_end_once_per_page() {
+        let mut edges = EdgeHooks::default();
+        edges.set_near_end(50.0, || {});
+        // Far from the end.
+        assert_eq!(edges.due(0.0, 100.0, 1000.0), (false, false));
+        // Near the end, once until more items are loaded.
+        assert_eq!(edges.due(860.0, 960.0, 1000.0), (false, true));
+        assert_eq!(edges.due(870.0, 970.0, 1000.0), (false, false));
+        assert_eq!(edges.due(870.0, 970.0, 2000.0), (false, false));
+        // Reaching the end again with the same items calls it again after leaving.
+        assert_eq!(edges.due(0.0, 100.0, 1000.0), (false, false));
+        assert_eq!(edges.due(900.0, 1000.0, 1000.0), (false, true));
+    }
+
+    #[test]
+    fn near_start() {
+        let mut edges = EdgeHooks::default();
+        edges.set_near_start(20.0, || {});
+        assert_eq!(edges.due(10.0, 110.0, 1000.0), (true, false));
+        assert_eq!(edges.due(0.0, 100.0, 1000.0), (false, false));
+        // Still near the start with more items loaded.
+        assert_eq!(edges.due(0.0, 100.0, 1500.0), (true, false));
+    }
+
+    #[test]
+    fn measured_spans() {
+        let items: im::Vector<i32> = (0..5).collect();
+        let item_size = VirtualItemSize::Measured(Box::new(|| 10.0));
+        let mut measurements = Measurements {
+            sizes: HashMap::new(),
+            views: HashMap::new(),
+        };
+        let span = |measurements: &Measurements<i32>, index| {
+            item_span(
+                items.clone(),
+                index,
+                &item_size,
+                &|i: &i32| *i,
+                measurements,
+            )
+        };
+        // Before measuring, every item has the estimated size.
+        assert_eq!(span(&measurements, 3), Some((30.0, 10.0)));
+        measurements.sizes.insert(Rc::new(1), 30.0);
+        measurements.sizes.insert(Rc::new(3), 25.0);
+        assert_eq!(span(&measurements, 1), Some((10.0, 30.0)));
+        assert_eq!(span(&measurements, 3), Some((50.0, 25.0)));
+        assert_eq!(span(&measurements, 4), Some((75.0, 10.0)));
+        assert_eq!(span(&measurements, 5), None);
+    }
+
+    #[test]
+    fn scroll_alignment() {
+        // An item at 300..320 with the viewport at 100..200.
+        let target = |align| scroll_target(300.0, 20.0, 100.0, 100.0, align);
+        assert_eq!(target(ScrollAlign::Start), 300.0);
+        assert_eq!(target(ScrollAlign::Center), 260.0);
+        assert_eq!(target(ScrollAlign::End), 220.0);
+        assert_eq!(target(ScrollAlign::Nearest), 220.0);
+        // Nearest scrolls back to items before the viewport and leaves visible ones alone.
+        assert_eq!(
+            scroll_target(40.0, 20.0, 100.0, 100.0, ScrollAlign::Nearest),
+            40.0
+        );
+        assert_eq!(
+            scroll_target(150.0, 20.0, 100.0, 100.0, ScrollAlign::Nearest),
+            100.0
+        );
+    }
+}
@@ -10,7 +10,7 @@ use floem_renderer::gpu_resources::GpuResources;
 use floem_renderer::Renderer;
 use floem_winit::{
     dpi::{LogicalPosition, LogicalSize},
-    event::{ElementState, Ime, MouseButton, MouseScrollDelta},
+    event::{ElementState, Force, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase},
     event_loop::EventLoopProxy,
     keyboard::{Key, ModifiersState, NamedKey},
     window::{CursorIcon, WindowId},
@@ -18,6 +18,8 @@ use floem_winit::{
 use image::DynamicImage;
 use peniko::kurbo::{Affine, Point, Rect, Size, Vec2};
 
+#[cfg(feature = "accessibility")]
+use crate::accessibility::{self, AccessibilityEvent};
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::reactive::SignalWith;
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
@@ -25,29 +27,39 @@ use crate::unit::UnitExt;
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::views::{container, stack};
 use crate::{
-    app::UserEvent,
-    app_state::AppState,
+    action::exec_after,
+    app::{add_app_update_event, AppUpdateEvent, UserEvent},
+    app_state::{AppState, MenuAccelerator},
     context::{
         ComputeLayoutCx, EventCx, FrameUpdate, LayoutCx, PaintCx, PaintState, StyleCx, UpdateCx,
     },
-    dropped_file::DroppedFileEvent,
+    drag::{self, DropEffect},
+    dropped_file::{DroppedFileEvent, ExternalDragData, ExternalDragEvent},
     event::{Event, EventListener},
+    hit_test::next_paint_frame,
     id::ViewId,
-    inspector::{self, Capture, CaptureState, CapturedView},
+    inspector::{self, Capture, CaptureState, CapturedView, PickState},
     keyboard::{KeyEvent, Modifiers},
     menu::Menu,
-    nav::view_arrow_navigation,
-    pointer::{PointerButton, PointerInputEvent, PointerMoveEvent, PointerWheelEvent},
-    profiler::Profile,
+    nav::{view_arrow_navigation, view_roving_navigation},
+    pointer::{
+        PointerButton, PointerId, PointerInputEvent, PointerMoveEvent, PointerType,
+        PointerWheelEvent,
+    },
+    shortcut::{self, KeyDown, Shortcut},
     style::{CursorStyle, Style, StyleSelector},
     theme::{default_theme, Theme},
+    touch::{
+        GesturePhase, GestureRecognizer, PinchEvent, RotateEvent, TouchEvent, TouchPointerFilter,
+        LONG_PRESS_DURATION,
+    },
     update::{
         UpdateMessage, CENTRAL_DEFERRED_UPDATE_MESSAGES, CENTRAL_UPDATE_MESSAGES,
         CURRENT_RUNNING_VIEW_HANDLE, DEFERRED_UPDATE_MESSAGES, UPDATE_MESSAGES,
     },
     view::{default_compute_layout, view_tab_navigation, IntoView, View},
     view_state::ChangeFlags,
-    views::Decorators,
+    views::{Decorators, MenuBarUpdate, PopoverDismiss, TabsUpdate},
     window_tracking::{remove_window_id_mapping, store_window_id_mapping},
 };
 
@@ -64,11 +76,10 @@ pub(crate) struct WindowHandle {
     main_view: ViewId,
     /// Reactive Scope for this WindowHandle
     scope: Scope,
-    app_state: AppState,
+    pub(crate) app_state: AppState,
     paint_state: PaintState,
     size: RwSignal<Size>,
     theme: Option<Theme>,
-    pub(crate) profile: Option<Profile>,
     os_theme: RwSignal<Option<floem_winit::window::Theme>>,
     is_maximized: bool,
     transparent: bool,
@@ -77,9 +88,20 @@ pub(crate) struct WindowHandle {
     pub(crate) cursor_position: Point,
     pub(crate) window_position: Point,
     pub(crate) last_pointer_down: Option<(u8, Point, Instant)>,
+    gestures: GestureRecognizer,
+    touch_pointers: TouchPointerFilter,
+    touchpad_scale: f64,
+    touchpad_rotation: f64,
+    touchpad_pressure: Option<f64>,
+    pressed_buttons: u8,
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     pub(crate) context_menu: RwSignal<Option<(Menu, Point)>>,
-    dropper_file: Option<PathBuf>,
+    /// Files of an external drag over the window.
+    hovered_files: Vec<PathBuf>,
+    /// Dropped files waiting for a pointer position to be delivered.
+    dropped_files: Vec<PathBuf>,
+    #[cfg(feature = "accessibility")]
+    accessibility: Option<accessibility::Adapter>,
 }
 
 impl WindowHandle {
@@ -127,6 +149,8 @@ impl WindowHandle {
         let view = WindowView { id };
         id.set_view(view.into_any());
 
+        #[cfg(feature = "accessibility")]
+        let accessibility = accessibility::Adapter::new(&window, event_proxy.clone());
         let window = Arc::new(window);
         store_window_id_mapping(id, window_id, &window);
         let gpu_resources = GpuResources::request(
@@ -157,7 +181,6 @@ impl WindowHandle {
             os_theme: theme,
             is_maximized,
             transparent,
-            profile: None,
             scale,
             modifiers: Modifiers::default(),
             cursor_position: Point::ZERO,
@@ -165,7 +188,16 @@ impl WindowHandle {
             #[cfg(any(target_os = "linux", target_os = "freebsd"))]
             context_menu,
             last_pointer_down: None,
-            dropper_file: None,
+            gestures: GestureRecognizer::default(),
+            touch_pointers: TouchPointerFilter::default(),
+            touchpad_scale: 1.0,
+            touchpad_rotation: 0.0,
+            touchpad_pressure: None,
+            pressed_buttons: 0,
+            hovered_files: Vec::new(),
+            dropped_files: Vec::new(),
+            #[cfg(feature = "accessibility")]
+            accessibility,
         };
         window_handle.app_state.set_root_size(size.get_untracked());
         if let Some(theme) = theme.get_untracked() {
@@ -197,9 +229,45 @@ impl WindowHandle {
     }
 
     pub fn event(&mut self, event: Event) {
+        self.dispatch_event(event);
+    }
+
+    /// Sends an event to the views. Returns `true` if a view handled it.
+    fn dispatch_event(&mut self, event: Event) -> bool {
         set_current_view(self.id);
         let event = event.scale(self.app_state.scale);
 
+        if self.app_state.picking.is_some()
+            && inspector::pick_event(self.id, &mut self.app_state, &event)
+        {
+            self.process_update();
+            return true;
+        }
+
+        if let Event::KeyDown(key_event) = &event {
+            let accelerators = self.app_state.accelerators();
+            let shortcuts: Vec<&Shortcut> =
+                accelerators.iter().map(|(shortcut, _)| *shortcut).collect();
+            match shortcut::handle_key_down(key_event, self.app_state.focus, &shortcuts) {
+                KeyDown::Handled => {
+                    self.process_update();
+                    return true;
+                }
+                KeyDown::Accelerator(i) => match accelerators[i].1 {
+                    MenuAccelerator::MenuBar(bar, item) => {
+                        bar.update_state(MenuBarUpdate::Activate(item));
+                        self.process_update();
+                        return true;
+                    }
+                    MenuAccelerator::WindowMenu(id) => {
+                        self.menu_action(id);
+                        return true;
+                    }
+                },
+                KeyDown::Unhandled => {}
+            }
+        }
+
         let mut cx = EventCx {
             app_state: &mut self.app_state,
         };
@@ -207,6 +275,9 @@ impl WindowHandle {
         let is_pointer_move = matches!(&event, Event::PointerMove(_));
         let (was_hovered, was_dragging_over) = if is_pointer_move {
             cx.app_state.cursor = None;
+            if cx.app_state.is_dragging() {
+                drag::reset_drop_effect();
+            }
             let was_hovered = std::mem::take(&mut cx.app_state.hovered);
             let was_dragging_over = std::mem::take(&mut cx.app_state.dragging_over);
 
@@ -215,6 +286,9 @@ impl WindowHandle {
             (None, None)
         };
 
+        let was_external_drag_over = matches!(&event, Event::ExternalDragOver(_))
+            .then(|| std::mem::take(&mut cx.app_state.external_drag_over));
+
         let is_pointer_down = matches!(&event, Event::PointerDown(_));
         let was_focused = if is_pointer_down {
             cx.app_state.clicking.clear();
@@ -223,9 +297,8 @@ impl WindowHandle {
             cx.app_state.focus
         };
 
+        let mut processed = false;
         if event.needs_focus() {
-            let mut processed = false;
-
             if !processed {
                 if let Some(id) = cx.app_state.focus {
                     processed |= cx
@@ -244,27 +317,53 @@ impl WindowHandle {
 
                 if !processed {
                     if let Event::KeyDown(KeyEvent { key, modifiers }) = &event {
-                        if key.logical_key == Key::Named(NamedKey::Tab)
+                        let escape_dismiss = (key.logical_key == Key::Named(NamedKey::Escape)
+                            && modifiers.is_empty())
+                        .then(|| cx.app_state.dismiss_on_escape.last().copied())
+                        .flatten();
+                        if let Some(popover) = escape_dismiss {
+                            popover.update_state(PopoverDismiss);
+                        } else if key.logical_key == Key::Named(NamedKey::Tab)
                             && (modifiers.is_empty() || *modifiers == Modifiers::SHIFT)
                         {
                             let backwards = modifiers.contains(Modifiers::SHIFT);
                             view_tab_navigation(self.id, cx.app_state, backwards);
                             // view_debug_tree(&self.view);
+                        } else if key.logical_key == Key::Named(NamedKey::Tab)
+                            && (*modifiers == Modifiers::CONTROL
+                                || *modifiers == Modifiers::CONTROL | Modifiers::SHIFT)
+                        {
+                            let tabs = cx.app_state.focus.and_then(|focus| {
+                                std::iter::once(focus)
+                                    .chain(focus.ancestors())
+                                    .find(|id| id.cycles_tabs())
+                            });
+                            if let Some(tabs) = tabs {
+                                tabs.update_state(TabsUpdate::Cycle {
+                                    backwards: modifiers.contains(Modifiers::SHIFT),
+                                });
+                            }
+                        } else if let Key::Named(
+                            name @ (NamedKey::ArrowUp
+                            | NamedKey::ArrowDown
+                            | NamedKey::ArrowLeft
+                            | NamedKey::ArrowRight
+                            | NamedKey::Home
+                            | NamedKey::End),
+                        ) = key.logical_key
+                        {
+                            if modifiers.is_empty() {
+                                view_roving_navigation(name, cx.app_state);
+                            } else if *modifiers == Modifiers::ALT
+                                && !matches!(name, NamedKey::Home | NamedKey::End)
+                            {
+                                view_arrow_navigation(name, cx.app_state, self.id);
+                            }
                         } else if let Key::Character(character) = &key.logical_key {
                             // 'I' displays some debug information
                             if character.eq_ignore_ascii_case("i") {
                                 // view_debug_tree(&self.view);
                             }
-                        } else if *modifiers == Modifiers::ALT {
-                            if let Key::Named(
-                                name @ (NamedKey::ArrowUp
-                                | NamedKey::ArrowDown
-                                | NamedKey::ArrowLeft
-                                | NamedKey::ArrowRight),
-                            ) = key.logical_key
-                            {
-                                view_arrow_navigation(name, cx.app_state, self.id);
-                            }
                         }
                     }
 
@@ -283,6 +382,23 @@ impl WindowHandle {
                     }
                 }
             }
+        } else if let Some(id) = event
+            .pointer_id()
+            .and_then(|pointer_id| cx.app_state.pointer_captures.get(&pointer_id).copied())
+        {
+            let window_origin = id.state().borrow().window_origin;
+            let layout = id.get_layout().unwrap_or_default();
+            let viewport = id.state().borrow().viewport.unwrap_or_default();
+            processed = cx
+                .unconditional_view_event(
+                    id,
+                    event.clone().offset((
+                        window_origin.x - layout.location.x as f64 + viewport.x0,
+                        window_origin.y - layout.location.y as f64 + viewport.y0,
+                    )),
+                    true,
+                )
+                .is_processed();
         } else if cx.app_state.active.is_some() && event.is_pointer() {
             if cx.app_state.is_dragging() {
                 cx.unconditional_view_event(self.id, event.clone(), false);
@@ -294,14 +410,16 @@ impl WindowHandle {
                 let window_origin = id.state().borrow().window_origin;
                 let layout = id.get_layout().unwrap_or_default();
                 let viewport = id.state().borrow().viewport.unwrap_or_default();
-                cx.unconditional_view_event(
-                    id,
-                    event.clone().offset((
-                        window_origin.x - layout.location.x as f64 + viewport.x0,
-                        window_origin.y - layout.location.y as f64 + viewport.y0,
-                    )),
-                    true,
-                );
+                processed = cx
+                    .unconditional_view_event(
+                        id,
+                        event.clone().offset((
+                            window_origin.x - layout.location.x as f64 + viewport.x0,
+                            window_origin.y - layout.location.y as f64 + viewport.y0,
+                        )),
+                        true,
+                    )
+                    .is_processed();
             }
 
             if let Event::PointerUp(_) = &event {
@@ -313,11 +431,35 @@ impl WindowHandle {
                 cx.app_state.active = None;
             }
         } else {
-            cx.unconditional_view_event(self.id, event.clone(), false);
+            processed = cx
+                .unconditional_view_event(self.id, event.clone(), false)
+                .is_processed();
         }
 
         if let Event::PointerUp(_) = &event {
             cx.app_state.drag_start = None;
+            drag::clear_drag_data();
+        }
+        // Captures end when the captured pointer is lifted.
+        match &event {
+            Event::PointerUp(pointer_event) => {
+                cx.app_state
+                    .pointer_captures
+                    .remove(&pointer_event.pointer_id);
+            }
+            Event::TouchEnd(touch) => {
+                cx.app_state.pointer_captures.remove(&touch.pointer_id());
+            }
+            Event::TouchCancel(touch) => {
+                cx.app_state.pointer_captures.remove(&touch.pointer_id());
+                // A cancelled touch has no pointer up to end the press it started.
+                if let Some(id) = cx.app_state.active.take() {
+                    if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
+                        id.request_style_recursive();
+                    }
+                }
+            }
+            _ => {}
         }
         if is_pointer_move {
             let hovered = &cx.app_state.hovered.clone();
@@ -353,6 +495,16 @@ impl WindowHandle {
                 }
             }
         }
+        if let Some(was_external_drag_over) = was_external_drag_over {
+            let external_drag_over = &cx.app_state.external_drag_over.clone();
+            for id in was_external_drag_over.symmetric_difference(external_drag_over) {
+                if external_drag_over.contains(id) {
+                    id.apply_event(&EventListener::ExternalDragEnter, &event);
+                } else {
+                    id.apply_event(&EventListener::ExternalDragLeave, &Event::ExternalDragLeave);
+                }
+            }
+        }
         if was_focused != cx.app_state.focus {
             cx.app_state.focus_changed(was_focused, cx.app_state.focus);
         }
@@ -374,6 +526,7 @@ impl WindowHandle {
         }
 
         self.process_update();
+        processed
     }
 
     pub(crate) fn scale(&mut self, scale: f64) {
@@ -434,19 +587,64 @@ impl WindowHandle {
         }
     }
 
+    pub(crate) fn hovered_file(&mut self, path: PathBuf) {
+        // The platform reports each file of a drag separately.
+        self.hovered_files.push(path);
+        self.external_drag_over(self.cursor_position);
+    }
+
+    pub(crate) fn hovered_file_cancelled(&mut self) {
+        self.hovered_files.clear();
+        self.external_drag_leave();
+    }
+
     pub(crate) fn dropped_file(&mut self, path: PathBuf) {
-        self.dropper_file = Some(path.clone());
+        self.hovered_files.clear();
+        self.dropped_files.push(path);
+    }
+
+    fn external_drag_over(&mut self, pos: Point) {
+        self.event(Event::ExternalDragOver(ExternalDragEvent {
+            data: ExternalDragData::Files(self.hovered_files.clone()),
+            pos,
+        }));
+    }
+
+    /// Sends [`EventListener::ExternalDragLeave`] to all views under the external drag.
+    fn external_drag_leave(&mut self) {
+        for id in std::mem::take(&mut self.app_state.external_drag_over) {
+            id.apply_event(&EventListener::ExternalDragLeave, &Event::ExternalDragLeave);
+        }
+        self.process_update();
     }
 
     pub(crate) fn pointer_move(&mut self, pos: Point) {
-        if let Some(path) = self.dropper_file.take() {
-            self.event(Event::DroppedFile(DroppedFileEvent { path, pos }));
+        // The position of a drop is only known with the next pointer move.
+        if !self.dropped_files.is_empty() {
+            let paths = std::mem::take(&mut self.dropped_files);
+            for path in paths.iter().cloned() {
+                self.event(Event::DroppedFile(DroppedFileEvent { path, pos }));
+            }
+            self.event(Event::ExternalDrop(ExternalDragEvent {
+                data: ExternalDragData::Files(paths),
+                pos,
+            }));
+            self.external_drag_leave();
+        } else if !self.hovered_files.is_empty() {
+            self.external_drag_over(pos);
         }
         if self.cursor_position != pos {
             self.cursor_position = pos;
+            if !self.touch_pointers.mouse_move(Instant::now()) {
+                return;
+            }
             let event = PointerMoveEvent {
                 pos,
                 modifiers: self.modifiers,
+                pointer_id: PointerId::Mouse,
+                pointer_type: PointerType::Mouse,
+                pressure: self.mouse_pressure(),
+                tilt: None,
             };
             self.event(Event::PointerMove(event));
         }
@@ -494,6 +692,12 @@ impl WindowHandle {
     }
 
     pub(crate) fn mouse_input(&mut self, button: MouseButton, state: ElementState) {
+        if !self
+            .touch_pointers
+            .mouse_input(state.is_pressed(), Instant::now())
+        {
+            return;
+        }
         let button: PointerButton = button.into();
         let count = if state.is_pressed() && button.is_primary() {
             if let Some((count, last_pos, instant)) = self.last_pointer_down.as_mut() {
@@ -516,11 +720,26 @@ impl WindowHandle {
         } else {
             0
         };
+        if state.is_pressed() {
+            self.pressed_buttons = self.pressed_buttons.saturating_add(1);
+        } else {
+            self.pressed_buttons = self.pressed_buttons.saturating_sub(1);
+        }
+        let pressure = self.mouse_pressure();
+        if self.pressed_buttons == 0 {
+            // Not every touchpad reports its last stage, so stop using the pressure once the
+            // buttons are released.
+            self.touchpad_pressure = None;
+        }
         let event = PointerInputEvent {
             pos: self.cursor_position,
             button,
             modifiers: self.modifiers,
             count,
+            pointer_id: PointerId::Mouse,
+            pointer_type: PointerType::Mouse,
+            pressure,
+            tilt: None,
         };
         match state {
             ElementState::Pressed => {
@@ -532,7 +751,166 @@ impl WindowHandle {
         }
     }
 
+    /// The pressure of the mouse, which is only known for force touch touchpads.
+    fn mouse_pressure(&self) -> f64 {
+        self.touchpad_pressure
+            .unwrap_or(if self.pressed_buttons > 0 { 0.5 } else { 0.0 })
+    }
+
+    /// Updates the pressure of a force touch touchpad. Stage `0` means the touchpad isn't
+    /// pressed.
+    pub(crate) fn touchpad_pressure(&mut self, pressure: f32, stage: i64) {
+        self.touchpad_pressure = (stage > 0).then_some(pressure as f64);
+    }
+
+    pub(crate) fn touch(&mut self, touch: Touch) {
+        let position: LogicalPosition<f64> = touch.location.to_logical(self.scale);
+        let pos = Point::new(position.x, position.y);
+        let (force, tilt) = match touch.force {
+            Some(force @ Force::Calibrated { altitude_angle, .. }) => {
+                (Some(force.normalized()), altitude_angle)
+            }
+            Some(force @ Force::Normalized(_)) => (Some(force.normalized()), None),
+            None => (None, None),
+        };
+        // Only pens report an altitude angle.
+        let pointer_type = if tilt.is_some() {
+            PointerType::Pen
+        } else {
+            PointerType::Touch
+        };
+        let event = TouchEvent {
+            id: touch.id,
+            pos,
+            force,
+            pointer_type,
+            tilt,
+            modifiers: self.modifiers,
+        };
+        // Touches are also sent as pointer events, so views handling pointers work with them too,
+        // unless a view handled the start of the touch. A cancelled touch has no pointer event,
+        // since it shouldn't act like a release.
+        let pressure = match touch.phase {
+            TouchPhase::Started | TouchPhase::Moved => force.unwrap_or(0.5),
+            TouchPhase::Ended | TouchPhase::Cancelled => 0.0,
+        };
+        let pointer_event = PointerInputEvent {
+            pos,
+            button: PointerButton::Primary,
+            modifiers: self.modifiers,
+            count: 1,
+            pointer_id: PointerId::Touch(touch.id),
+            pointer_type,
+            pressure,
+            tilt,
+        };
+        let now = Instant::now();
+        let gestures = match touch.phase {
+            TouchPhase::Started => {
+                let handled = self.dispatch_event(Event::TouchStart(event));
+                if self.touch_pointers.touch_start(touch.id, handled, now) {
+                    self.event(Event::PointerDown(pointer_event));
+                }
+                let (gestures, long_press) = self.gestures.touch_start(touch.id, pos, now);
+                if let Some(generation) = long_press {
+                    let window_id = self.window_id;
+                    exec_after(LONG_PRESS_DURATION, move |_| {
+                        add_app_update_event(AppUpdateEvent::LongPressTimeout {
+                            window_id,
+                            generation,
+                        });
+                    });
+                }
+                gestures
+            }
+            TouchPhase::Moved => {
+                self.event(Event::TouchMove(event));
+                if self.touch_pointers.touch_move(touch.id, now) {
+                    self.event(Event::PointerMove(PointerMoveEvent {
+                        pos,
+                        modifiers: self.modifiers,
+                        pointer_id: pointer_event.pointer_id,
+                        pointer_type,
+                        pressure,
+                        tilt,
+                    }));
+                }
+                self.gestures.touch_move(touch.id, pos, now)
+            }
+            TouchPhase::Ended => {
+                self.event(Event::TouchEnd(event));
+                if self.touch_pointers.touch_end(touch.id, now) {
+                    self.event(Event::PointerUp(pointer_event));
+                }
+                self.gestures.touch_end(touch.id, pos, false, now)
+            }
+            TouchPhase::Cancelled => {
+                self.event(Event::TouchCancel(event));
+                self.touch_pointers.touch_end(touch.id, now);
+                self.gestures.touch_end(touch.id, pos, true, now)
+            }
+        };
+        for gesture in gestures {
+            self.event(gesture);
+        }
+    }
+
+    pub(crate) fn long_press_timeout(&mut self, generation: u64) {
+        if let Some(event) = self.gestures.long_press_timeout(generation) {
+            self.event(event);
+        }
+    }
+
+    /// Sends a touchpad pinch as a pinch gesture at the cursor position.
+    pub(crate) fn touchpad_magnify(&mut self, delta: f64, phase: TouchPhase) {
+        let (scale, phase) = match phase {
+            TouchPhase::Started => {
+                self.touchpad_scale = 1.0;
+                (1.0, GesturePhase::Began)
+            }
+            TouchPhase::Moved => (self.touchpad_scale * (1.0 + delta), GesturePhase::Changed),
+            TouchPhase::Ended => (self.touchpad_scale, GesturePhase::Ended),
+            TouchPhase::Cancelled => (self.touchpad_scale, GesturePhase::Cancelled),
+        };
+        let scale_delta = scale / self.touchpad_scale;
+        self.touchpad_scale = scale;
+        self.event(Event::Pinch(PinchEvent {
+            origin: self.cursor_position,
+            center: self.cursor_position,
+            scale,
+            scale_delta,
+            phase,
+        }));
+    }
+
+    /// Sends a touchpad rotation as a rotate gesture at the cursor position.
+    pub(crate) fn touchpad_rotate(&mut self, delta: f32, phase: TouchPhase) {
+        // Touchpad rotation is in degrees, counterclockwise.
+        let delta = -(delta as f64).to_radians();
+        let (delta, phase) = match phase {
+            TouchPhase::Started => {
+                self.touchpad_rotation = 0.0;
+                (0.0, GesturePhase::Began)
+            }
+            TouchPhase::Moved => (delta, GesturePhase::Changed),
+            TouchPhase::Ended => (0.0, GesturePhase::Ended),
+            TouchPhase::Cancelled => (0.0, GesturePhase::Cancelled),
+        };
+        self.touchpad_rotation += delta;
+        self.event(Event::Rotate(RotateEvent {
+            origin: self.cursor_position,
+            center: self.cursor_position,
+            rotation: self.touchpad_rotation,
+            delta,
+            phase,
+        }));
+    }
+
     pub(crate) fn focused(&mut self, focused: bool) {
+        #[cfg(feature = "accessibility")]
+        if let Some(adapter) = self.accessibility.as_mut() {
+            adapter.window_focused(focused);
+        }
         if focused {
             self.event(Event::WindowGotFocus);
         } else {
@@ -585,6 +963,8 @@ impl WindowHandle {
 
         self.process_update_no_paint();
         self.paint();
+        #[cfg(feature = "accessibility")]
+        self.update_accessibility();
 
         // Request a new frame if there's any scheduled updates.
         if !self.app_state.scheduled_updates.is_empty() {
@@ -602,6 +982,7 @@ impl WindowHandle {
             saved_transforms: Vec::new(),
             saved_clips: Vec::new(),
             saved_z_indexes: Vec::new(),
+            paint_frame: next_paint_frame(),
         };
         cx.paint_state
             .renderer_mut()
@@ -626,6 +1007,9 @@ impl WindowHandle {
             );
         }
         cx.paint_view(self.id);
+        if let Some(id) = cx.app_state.picking.as_ref().and_then(|p| p.hovered) {
+            inspector::paint_box_model(&mut cx, id);
+        }
         if let Some(window) = self.window.as_ref() {
             if cx.app_state.capture.is_none() {
                 window.pre_present_notify();
@@ -634,6 +1018,29 @@ impl WindowHandle {
         cx.paint_state.renderer_mut().finish()
     }
 
+    /// Sends the accessibility tree to the platform if an assistive technology is listening.
+    #[cfg(feature = "accessibility")]
+    fn update_accessibility(&mut self) {
+        let scale = self.scale * self.app_state.scale;
+        if let Some(adapter) = self.accessibility.as_mut() {
+            adapter
+                .update_if_active(|| accessibility::tree_update(&self.app_state, self.id, scale));
+        }
+    }
+
+    #[cfg(feature = "accessibility")]
+    pub(crate) fn accessibility_event(&mut self, event: AccessibilityEvent) {
+        match event {
+            AccessibilityEvent::InitialTreeRequested => self.update_accessibility(),
+            AccessibilityEvent::ActionRequested(request) => {
+                set_current_view(self.id);
+                accessibility::handle_action(&mut self.app_state, request);
+                self.process_update();
+            }
+            AccessibilityEvent::Deactivated => {}
+        }
+    }
+
     pub(crate) fn capture(&mut self) -> Capture {
         // Capture the view before we run `style` and `layout` to catch missing `request_style`` or
         // `request_layout` flags.
@@ -703,6 +1110,16 @@ impl WindowHandle {
         capture
     }
 
+    /// Starts picking an element for the inspector. The view under the pointer is highlighted
+    /// until it's clicked or Escape is pressed.
+    pub(crate) fn start_pick(&mut self) {
+        self.app_state.picking = Some(PickState::default());
+        if let Some(window) = self.window.as_ref() {
+            window.focus_window();
+        }
+        self.schedule_repaint();
+    }
+
     pub(crate) fn process_update(&mut self) {
         if self.process_update_no_paint() {
             self.schedule_repaint();
@@ -847,6 +1264,23 @@ impl WindowHandle {
                             id.request_style_recursive();
                         }
                     }
+                    UpdateMessage::FocusTrap { id, active } => {
+                        cx.app_state.set_focus_trap(id, active);
+                    }
+                    UpdateMessage::DismissOnEscape { id } => {
+                        cx.app_state.dismiss_on_escape.push(id);
+                    }
+                    UpdateMessage::MenuBarShortcuts { id, shortcuts } => {
+                        cx.app_state.menu_bar_shortcuts.insert(id, shortcuts);
+                    }
+                    UpdateMessage::CapturePointer { id, pointer_id } => {
+                        cx.app_state.pointer_captures.insert(pointer_id, id);
+                    }
+                    UpdateMessage::ReleasePointerCapture { id, pointer_id } => {
+                        if cx.app_state.pointer_captures.get(&pointer_id) == Some(&id) {
+                            cx.app_state.pointer_captures.remove(&pointer_id);
+                        }
+                    }
                     UpdateMessage::ClearActive(id) => {
                         if Some(id) == cx.app_state.active {
                             cx.app_state.active = None;
@@ -970,6 +1404,8 @@ impl WindowHandle {
                         inspector::capture(self.window_id);
                     }
                     UpdateMessage::AddOverlay { id, position, view } => {
+                        // The focus returns to where it was when the overlay is removed.
+                        cx.app_state.push_focus_restore(id);
                         let scope = self.scope.create_child();
 
                         let view = with_scope(scope, view);
@@ -1049,27 +1485,32 @@ impl WindowHandle {
         })
     }
 
-    fn update_window_menu(&mut self, _menu: Menu) {
-        // if let Some(action) = menu.item.action.take() {
-        //     self.window_menu.insert(menu.item.id as u32, action);
-        // }
-        // for child in menu.children {
-        //     match child {
-        //         crate::menu::MenuEntry::Separator => {}
-        //         crate::menu::MenuEntry::Item(mut item) => {
-        //             if let Some(action) = item.action.take() {
-        //                 self.window_menu.insert(item.id as u32, action);
-        //             }
-        //         }
-        //         crate::menu::MenuEntry::SubMenu(m) => {
-        //             self.update_window_menu(m);
-        //         }
-        //     }
-        // }
+    fn update_window_menu(&mut self, mut menu: Menu) {
+        self.app_state.window_menu.clear();
+        self.app_state.menu_accelerators.clear();
+        self.app_state.update_window_menu(&mut menu);
     }
 
     fn set_cursor(&mut self) {
-        let cursor = match self.app_state.cursor {
+        let cursor = if self.app_state.is_dragging() {
+            match drag::drop_effect() {
+                DropEffect::None => CursorIcon::NotAllowed,
+                DropEffect::Copy => CursorIcon::Copy,
+                DropEffect::Move => CursorIcon::Grabbing,
+            }
+        } else {
+            self.style_cursor()
+        };
+        if cursor != self.app_state.last_cursor {
+            if let Some(window) = self.window.as_ref() {
+                window.set_cursor_icon(cursor);
+            }
+            self.app_state.last_cursor = cursor;
+        }
+    }
+
+    fn style_cursor(&self) -> CursorIcon {
+        match self.app_state.cursor {
             Some(CursorStyle::Default) => CursorIcon::Default,
             Some(CursorStyle::Pointer) => CursorIcon::Pointer,
             Some(CursorStyle::Text) => CursorIcon::Text,
@@ -1086,12 +1527,6 @@ impl WindowHandle {
             Some(CursorStyle::NeswResize) => CursorIcon::NeswResize,
             Some(CursorStyle::NwseResize) => CursorIcon::NwseResize,
             None => CursorIcon::Default,
-        };
-        if cursor != self.app_state.last_cursor {
-            if let Some(window) = self.window.as_ref() {
-                window.set_cursor_icon(cursor);
-            }
-            self.app_state.last_cursor = cursor;
         }
     }
 
@@ -1211,261 +1646,38 @@ fn context_menu_view(
     context_menu: RwSignal<Option<(Menu, Point)>>,
     window_size: RwSignal<Size>,
 ) -> impl IntoView {
-    use floem_reactive::{create_effect, create_rw_signal};
-    use peniko::Color;
-
     use crate::{
         app::{add_app_update_event, AppUpdateEvent},
-        views::{dyn_stack, empty, svg, text},
+        views::{dyn_container, empty, format_menu, MenuPopup},
     };
 
-    #[derive(Clone, PartialEq, Eq, Hash)]
-    enum MenuDisplay {
-        Separator(usize),
-        Item {
-            id: Option<u64>,
-            enabled: bool,
-            title: String,
-            children: Option<Vec<MenuDisplay>>,
-        },
-    }
-
-    fn format_menu(menu: &Menu) -> Vec<MenuDisplay> {
-        menu.children
-            .iter()
-            .enumerate()
-            .map(|(s, e)| match e {
-                crate::menu::MenuEntry::Separator => MenuDisplay::Separator(s),
-                crate::menu::MenuEntry::Item(i) => MenuDisplay::Item {
-                    id: Some(i.id),
-                    enabled: i.enabled,
-                    title: i.title.clone(),
-                    children: None,
-                },
-                crate::menu::MenuEntry::SubMenu(m) => MenuDisplay::Item {
-                    id: None,
-                    enabled: m.item.enabled,
-                    title: m.item.title.clone(),
-                    children: Some(format_menu(m)),
-                },
-            })
-            .collect()
-    }
-
-    let context_menu_items = cx.create_memo(move |_| {
-        context_menu.with(|menu| {
-            menu.as_ref()
-                .map(|(menu, _): &(Menu, Point)| format_menu(menu))
-        })
-    });
     let context_menu_size = cx.create_rw_signal(Size::ZERO);
-    let focus_count = cx.create_rw_signal(0);
-
-    fn view_fn(
-        window_id: WindowId,
-        menu: MenuDisplay,
-        context_menu: RwSignal<Option<(Menu, Point)>>,
-        focus_count: RwSignal<i32>,
-        on_child_submenu_for_parent: RwSignal<bool>,
-    ) -> impl IntoView {
-        match menu {
-            MenuDisplay::Item {
-                id,
-                enabled,
-                title,
-                children,
-            } => {
-                let menu_width = create_rw_signal(0.0);
-                let show_submenu = create_rw_signal(false);
-                let on_submenu = create_rw_signal(false);
-                let on_child_submenu = create_rw_signal(false);
-                let has_submenu = children.is_some();
-                let submenu_svg = r#"<svg width="16" height="16" viewBox="0 0 16 16" xmlns="http://www.w3.org/2000/svg" fill="currentColor"><path fill-rule="evenodd" clip-rule="evenodd" d="M10.072 8.024L5.715 3.667l.618-.62L11 7.716v.618L6.333 13l-.618-.619 4.357-4.357z"/></svg>"#;
-                container(
-                    stack((
-                        stack((
-                            text(title),
-                            svg(submenu_svg).style(move |s| {
-                                s.size(20.0, 20.0)
-                                    .color(Color::rgb8(201, 201, 201))
-                                    .margin_right(10.0)
-                                    .margin_left(20.0)
-                                    .apply_if(!has_submenu, |s| s.hide())
-                            }),
-                        ))
-                        .on_event_stop(EventListener::PointerEnter, move |_| {
-                            if has_submenu {
-                                show_submenu.set(true);
-                            }
-                        })
-                        .on_event_stop(EventListener::PointerLeave, move |_| {
-                            if has_submenu {
-                                show_submenu.set(false);
-                            }
-                        })
-                        .on_resize(move |rect| {
-                            let width = rect.width();
-                            if menu_width.get_untracked() != width {
-                                menu_width.set(width);
-                            }
-                        })
-                        .on_click_stop(move |_| {
-                            context_menu.set(None);
-                            focus_count.set(0);
-                            if let Some(id) = id {
-                                add_app_update_event(AppUpdateEvent::MenuAction {
-                                    window_id,
-                                    action_id: id as usize,
-                                });
-                            }
-                        })
-                        .on_secondary_click_stop(move |_| {
-                            context_menu.set(None);
-                            focus_count.set(0);
-                            if let Some(id) = id {
-                                add_app_update_event(AppUpdateEvent::MenuAction {
-                                    window_id,
-                                    action_id: id as usize,
-                                });
-                            }
-                        })
-                        .disabled(move || !enabled)
-                        .style(|s| {
-                            s.width(100.pct())
-                                .min_width(100.pct())
-                                .padding_horiz(20.0)
-                                .justify_between()
-                                .items_center()
-                                .hover(|s| {
-                                    s.border_radius(10.0).background(Color::rgb8(65, 65, 65))
-                                })
-                                .active(|s| {
-                                    s.border_radius(10.0).background(Color::rgb8(92, 92, 92))
-                                })
-                                .disabled(|s| s.color(Color::rgb8(92, 92, 92)))
-                        }),
-                        dyn_stack(
-                            move || children.clone().unwrap_or_default(),
-                            move |s| s.clone(),
-                            move |menu| {
-                                view_fn(
-                                    window_id,
-                                    menu,
-                                    context_menu,
-                                    focus_count,
-                                    on_child_submenu,
-                                )
-                            },
-                        )
-                        .keyboard_navigatable()
-                        .on_event_stop(EventListener::FocusGained, move |_| {
-                            focus_count.update(|count| {
-                                *count += 1;
-                            });
-                        })
-                        .on_event_stop(EventListener::FocusLost, move |_| {
-                            let count = focus_count
-                                .try_update(|count| {
-                                    *count -= 1;
-                                    *count
-                                })
-                                .unwrap();
-                            if count < 1 {
-                                context_menu.set(None);
-                            }
-                        })
-                        .on_event_stop(EventListener::KeyDown, move |event| {
-                            if let Event::KeyDown(event) = event {
-                                if event.key.logical_key == Key::Named(NamedKey::Escape) {
-                                    context_menu.set(None);
-                                }
-                            }
-                        })
-                        .on_event_stop(EventListener::PointerDown, move |_| {})
-                        .on_event_stop(EventListener::PointerEnter, move |_| {
-                            if has_submenu {
-                                on_submenu.set(true);
-                                on_child_submenu_for_parent.set(true);
-                            }
-                        })
-                        .on_event_stop(EventListener::PointerLeave, move |_| {
-                            if has_submenu {
-                                on_submenu.set(false);
-                                on_child_submenu_for_parent.set(false);
-                            }
+
+    dyn_container(
+        move || context_menu.with(|menu| menu.as_ref().map(|(menu, _)| format_menu(menu))),
+        move |items| match items {
+            Some(items) => {
+                let view = MenuPopup::new(
+                    items,
+                    move |id| {
+                        add_app_update_event(AppUpdateEvent::MenuAction {
+                            window_id,
+                            action_id: id as usize,
                         })
-                        .style(move |s| {
-                            s.absolute()
-                                .min_width(200.0)
-                                .margin_top(-5.0)
-                                .margin_left(menu_width.get() as f32)
-                                .flex_col()
-                                .border_radius(10.0)
-                                .background(Color::rgb8(44, 44, 44))
-                                .padding(5.0)
-                                .cursor(CursorStyle::Default)
-                                .box_shadow_blur(5.0)
-                                .box_shadow_color(Color::BLACK)
-                                .apply_if(
-                                    !show_submenu.get()
-                                        && !on_submenu.get()
-                                        && !on_child_submenu.get(),
-                                    |s| s.hide(),
-                                )
-                        }),
-                    ))
-                    .style(|s| s.min_width(100.pct())),
+                    },
+                    move || context_menu.set(None),
                 )
-                .style(|s| s.min_width(100.pct()))
-                .into_any()
+                .view()
+                .into_view();
+                view.id().request_focus();
+                view.into_any()
             }
-
-            MenuDisplay::Separator(_) => container(empty().style(|s| {
-                s.width(100.pct())
-                    .height(1.0)
-                    .margin_vert(5.0)
-                    .background(Color::rgb8(92, 92, 92))
-            }))
-            .style(|s| s.min_width(100.pct()).padding_horiz(20.0))
-            .into_any(),
-        }
-    }
-
-    let on_child_submenu = create_rw_signal(false);
-    let view = dyn_stack(
-        move || context_menu_items.get().unwrap_or_default(),
-        move |s| s.clone(),
-        move |menu| view_fn(window_id, menu, context_menu, focus_count, on_child_submenu),
+            None => empty().into_any(),
+        },
     )
     .on_resize(move |rect| {
         context_menu_size.set(rect.size());
     })
-    .on_event_stop(EventListener::PointerDown, move |_| {})
-    .on_event_stop(EventListener::PointerMove, move |_| {})
-    .keyboard_navigatable()
-    .on_event_stop(EventListener::KeyDown, move |event| {
-        if let Event::KeyDown(event) = event {
-            if event.key.logical_key == Key::Named(NamedKey::Escape) {
-                context_menu.set(None);
-            }
-        }
-    })
-    .on_event_stop(EventListener::FocusGained, move |_| {
-        focus_count.update(|count| {
-            *count += 1;
-        });
-    })
-    .on_event_stop(EventListener::FocusLost, move |_| {
-        let count = focus_count
-            .try_update(|count| {
-                *count -= 1;
-                *count
-            })
-            .unwrap();
-        if count < 1 {
-            context_menu.set(None);
-        }
-    })
     .style(move |s| {
         let window_size = window_size.get();
         let menu_size = context_menu_size.get();
@@ -1478,37 +1690,19 @@ fn context_menu_view(
             pos.y = window_size.height - menu_size.height;
         }
         s.absolute()
-            .min_width(200.0)
-            .flex_col()
-            .border_radius(10.0)
-            .background(Color::rgb8(44, 44, 44))
-            .color(Color::rgb8(201, 201, 201))
             .z_index(999)
-            .line_height(2.0)
-            .padding(5.0)
             .margin_left(pos.x as f32)
             .margin_top(pos.y as f32)
             .cursor(CursorStyle::Default)
             .apply_if(!is_active, |s| s.hide())
-            .box_shadow_blur(5.0)
-            .box_shadow_color(Color::BLACK)
-    });
-
-    let id = view.id();
-
-    create_effect(move |_| {
-        if context_menu.with(|m| m.is_some()) {
-            id.request_focus();
-        }
-    });
-
-    view
+    })
 }
 
 struct OverlayView {
     id: ViewId,
     child: ViewId,
-    position: Point,
+    /// The position of the overlay, or `None` for a layer covering the whole window.
+    position: Option<Point>,
     window_origin: Point,
     parent_size: Size,
     size: Size,
@@ -1520,12 +1714,13 @@ impl View for OverlayView {
     }
 
     fn view_style(&self) -> Option<crate::style::Style> {
-        Some(
-            Style::new()
+        Some(match self.position {
+            Some(position) => Style::new()
                 .absolute()
-                .inset_left(self.position.x)
-                .inset_top(self.position.y),
-        )
+                .inset_left(position.x)
+                .inset_top(position.y),
+            None => Style::new().absolute().inset(0.0).size_full(),
+        })
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
@@ -1544,6 +1739,10 @@ impl View for OverlayView {
     }
 
     fn paint(&mut self, cx: &mut PaintCx) {
+        if self.position.is_none() {
+            cx.paint_view(self.child);
+            return;
+        }
         cx.save();
         let x = if (self.window_origin.x + self.size.width) > self.parent_size.width - 5.0 {
             (self.window_origin.x + self.size.width) - (self.parent_size.width - 5.0)
@@ -125,6 +125,11 @@ pub fn root_view_id(window: &WindowId) -> Option<ViewId> {
     with_window_map(|m| m.root_view_id_for(window)).unwrap_or(None)
 }
 
+/// Returns the root views of all windows.
+pub fn root_view_ids() -> Vec<ViewId> {
+    with_window_map(|m| m.window_id_for_root_view_id.keys().copied().collect()).unwrap_or_default()
+}
+
 /// Force a single window to repaint - this is necessary in cases where the
 /// window is not the active window and otherwise would not process update
 /// messages sent to it.